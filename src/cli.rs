@@ -1,4 +1,5 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
 use std::path::PathBuf;
 
 #[derive(Parser)]
@@ -8,22 +9,162 @@ use std::path::PathBuf;
 pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
+    
+    /// Read and write this config file instead of ~/.config/procon_rs/config.toml
+    #[arg(long, global = true, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+    
+    /// Use plain ASCII markers such as [ok] instead of emoji (also PROCON_NO_EMOJI=1)
+    #[arg(long, global = true)]
+    pub no_emoji: bool,
+    
+    /// Print nothing but errors and the command's actual output
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    pub quiet: bool,
+    
+    /// Also list each file written
+    #[arg(short, long, global = true)]
+    pub verbose: bool,
+    
+    /// Diagnostic log level on stderr, e.g. debug to trace template resolution
+    #[arg(long, global = true, value_name = "LEVEL", default_value = "warn")]
+    pub log_level: log::LevelFilter,
+    
+    /// Output format for results and errors
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+}
+
+/// How results and errors are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable status lines
+    Text,
+    /// A single JSON object on stdout, or `{"error": ...}` on stderr
+    Json,
 }
 
 #[derive(Subcommand)]
 pub enum Commands {
     /// Create a new project
     New {
-        /// Project name
-        name: String,
+        /// Project name; when omitted on a terminal, it, the template and the C++
+        /// standard are asked for interactively
+        name: Option<String>,
         
         /// Template to use
         #[arg(short, long, default_value = "default")]
         template: String,
         
+        /// Use the template in this directory, wherever it is, instead of --template
+        #[arg(long, value_name = "PATH", conflicts_with = "template")]
+        template_dir: Option<PathBuf>,
+        
+        /// Read main.cpp from standard input instead of using a template
+        #[arg(long, conflicts_with_all = ["template", "template_dir"])]
+        stdin_template: bool,
+        
+        /// Clone a git+ template again instead of using its cached clone
+        #[arg(long)]
+        refresh: bool,
+        
         /// Directory to create the project in
         #[arg(short, long)]
         path: Option<PathBuf>,
+        
+        /// Create the --path directory and its parents if they don't exist
+        #[arg(long)]
+        create_parents: bool,
+        
+        /// Skip the configured project.on_create hook and the template's post_create hooks
+        #[arg(long)]
+        no_hooks: bool,
+        
+        /// Initialize a git repository and commit the generated files
+        #[arg(long)]
+        git: bool,
+        
+        /// Initial branch name for the git repository (implies --git)
+        #[arg(long, value_name = "BRANCH")]
+        git_branch: Option<String>,
+        
+        /// Create an initial commit with this message (implies --git)
+        #[arg(long, value_name = "MESSAGE")]
+        git_commit: Option<String>,
+        
+        /// Skip the sample files declared by the template
+        #[arg(long)]
+        no_samples: bool,
+        
+        /// Leave out the template's .gitignore
+        #[arg(long)]
+        no_gitignore: bool,
+        
+        /// Report where the template was loaded from (builtin, user or dev) and its path
+        #[arg(long)]
+        print_template_source: bool,
+        
+        /// Set a custom template variable, e.g. -D AUTHOR=me for {{AUTHOR}} (repeatable)
+        #[arg(short = 'D', long = "define", value_name = "KEY=VALUE", value_parser = parse_define)]
+        defines: Vec<(String, String)>,
+        
+        /// Replace an existing project directory (only if it has main.cpp and CMakeLists.txt)
+        #[arg(long)]
+        force: bool,
+        
+        /// List the template's {{...}} variables and where their values come from, then exit
+        #[arg(long)]
+        list_vars: bool,
+        
+        /// Show the files that would be created, with their sizes, without writing anything
+        #[arg(long)]
+        dry_run: bool,
+        
+        /// With --dry-run, show the files as a directory tree
+        #[arg(long, requires = "dry_run")]
+        tree: bool,
+        
+        /// Use a user template even if it lacks main.cpp or CMakeLists.txt
+        #[arg(long)]
+        allow_incomplete: bool,
+        
+        /// Print the project path with symlinks resolved
+        #[arg(long)]
+        canonicalize: bool,
+        
+        /// Only report whether the project directory is free (exit 0) or taken (exit 1)
+        #[arg(long)]
+        check_name_available: bool,
+        
+        /// Open the new project in $EDITOR, $VISUAL or VS Code, without waiting for it
+        #[arg(long)]
+        open: bool,
+    },
+    
+    /// Create a contest directory with one project per problem
+    Contest {
+        /// Contest name, used as the parent directory
+        name: String,
+        
+        /// Comma-separated problem ids, e.g. A,B,C,D
+        #[arg(long, value_delimiter = ',', required_unless_present = "count", conflicts_with = "count")]
+        problems: Vec<String>,
+        
+        /// Number of problems, named A, B, C, ...
+        #[arg(long)]
+        count: Option<usize>,
+        
+        /// Template to use for every problem
+        #[arg(short, long, default_value = "default")]
+        template: String,
+        
+        /// Directory to create the contest in
+        #[arg(short, long)]
+        path: Option<PathBuf>,
+        
+        /// Create the --path directory and its parents if they don't exist
+        #[arg(long)]
+        create_parents: bool,
     },
     
     /// Initialize existing directory
@@ -33,12 +174,137 @@ pub enum Commands {
         force: bool,
     },
     
+    /// Delete the files procon_rs created in a project, keeping files you added
+    Remove {
+        /// Project directory (defaults to the current directory)
+        path: Option<PathBuf>,
+        
+        /// Don't ask for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+    
+    /// Delete the project `new` created last, unless it was changed since
+    Undo {
+        /// Don't ask for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+    
+    /// Rename a project directory and the project name in its CMakeLists.txt
+    Rename {
+        /// Project directory to rename
+        old_dir: PathBuf,
+
+        /// New project name
+        new_name: String,
+    },
+    
+    /// List the builtin and user templates available to `new`
+    ListTemplates,
+    
+    /// Check that a directory is a valid template before publishing it
+    ValidateTemplate {
+        /// Template directory
+        path: PathBuf,
+    },
+    
+    /// Manage templates
+    #[command(alias = "templates")]
+    Template {
+        #[command(subcommand)]
+        action: TemplateCommands,
+    },
+    
     /// Manage configuration
     Config {
         /// Configuration key
-        key: String,
+        #[arg(required_unless_present_any = ["validate", "all"])]
+        key: Option<String>,
         
         /// Configuration value (if not provided, shows current value)
         value: Option<String>,
+        
+        /// Print the value as typed JSON
+        #[arg(long)]
+        json: bool,
+        
+        /// Check a config file (the user's config by default) and report every problem
+        #[arg(long, value_name = "FILE", num_args = 0..=1, conflicts_with_all = ["key", "json"])]
+        validate: Option<Option<PathBuf>>,
+        
+        /// Print every key, including defaults.*, with its current value
+        #[arg(long, conflicts_with_all = ["key", "validate"])]
+        all: bool,
+    },
+    
+    /// Check that cmake, a C++ compiler and git are installed and the config is writable
+    Doctor,
+    
+    /// Print a shell completion script to stdout
+    Completions {
+        /// Shell to generate completions for
+        shell: Shell,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TemplateCommands {
+    /// Create a user template to customize, pre-filled from the default template
+    New {
+        /// Template name
+        name: String,
+        
+        /// Replace an existing template with the same name
+        #[arg(long)]
+        force: bool,
+    },
+    
+    /// Report user templates that fail validation
+    Prune {
+        /// Delete the invalid templates (builtin names are never removed)
+        #[arg(long)]
+        remove: bool,
     },
-}
\ No newline at end of file
+    
+    /// Show the files a template would create
+    Show {
+        /// Template name
+        name: String,
+        
+        /// Render the files as a directory tree instead of a flat list
+        #[arg(long)]
+        tree: bool,
+    },
+    
+    /// Describe a template: its description, required files, variables and files
+    Info {
+        /// Template name
+        name: String,
+    },
+    
+    /// Print the directory where user templates live
+    Dir {
+        /// Print the template cache directory instead
+        #[arg(long)]
+        cache: bool,
+    },
+}
+
+/// Parses a `KEY=VALUE` template variable definition.
+fn parse_define(definition: &str) -> Result<(String, String), String> {
+    let (key, value) = definition
+        .split_once('=')
+        .ok_or_else(|| format!("expected KEY=VALUE, got '{}'", definition))?;
+
+    let is_valid_key =
+        !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !is_valid_key {
+        return Err(format!(
+            "invalid variable name '{}': use letters, digits and underscores",
+            key
+        ));
+    }
+
+    Ok((key.to_string(), value.to_string()))
+}
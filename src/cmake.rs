@@ -0,0 +1,92 @@
+use regex::Regex;
+
+/// Settings recovered from an existing `CMakeLists.txt`.
+///
+/// Each field is `None` when the file doesn't set it in a form the parser understands.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CmakeSettings {
+    /// First argument of the `project(...)` command.
+    pub project_name: Option<String>,
+    /// Value of `CMAKE_CXX_STANDARD`, or the number in a `cxx_std_NN` compile feature.
+    pub cpp_standard: Option<String>,
+}
+
+/// Extracts the project name and C++ standard from CMake source.
+///
+/// The parser is deliberately tolerant: it ignores line and bracket comments, accepts
+/// quoted arguments and any command casing, and skips values that are variable
+/// references such as `${NAME}` rather than failing.
+///
+/// # Examples
+///
+/// ```
+/// use procon_rs::cmake::parse_cmake_lists;
+///
+/// let settings = parse_cmake_lists("project(solver CXX)\nset(CMAKE_CXX_STANDARD 20)\n");
+/// assert_eq!(settings.project_name.as_deref(), Some("solver"));
+/// assert_eq!(settings.cpp_standard.as_deref(), Some("20"));
+/// ```
+pub fn parse_cmake_lists(content: &str) -> CmakeSettings {
+    let code = strip_comments(content);
+
+    let project = Regex::new(r#"(?i)\bproject\s*\(\s*"?([A-Za-z0-9_.+-]+)"?[\s)]"#)
+        .expect("project pattern is valid");
+    let standard = Regex::new(r#"\bCMAKE_CXX_STANDARD\s+"?([0-9]+)"?[\s)]"#)
+        .expect("standard pattern is valid");
+    let feature = Regex::new(r"\bcxx_std_([0-9]+)\b").expect("feature pattern is valid");
+
+    CmakeSettings {
+        project_name: project.captures(&code).map(|captures| captures[1].to_string()),
+        cpp_standard: standard
+            .captures(&code)
+            .or_else(|| feature.captures(&code))
+            .map(|captures| captures[1].to_string()),
+    }
+}
+
+/// Removes `# line` and `#[[bracket]]` comments, leaving `#` inside quoted arguments alone.
+fn strip_comments(content: &str) -> String {
+    let mut code = String::with_capacity(content.len());
+    let mut rest = content;
+    let mut in_quotes = false;
+
+    while let Some(c) = rest.chars().next() {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        } else if c == '\\' && in_quotes {
+            // Keep escaped characters, including `\"`, as-is
+            let escaped: String = rest.chars().take(2).collect();
+            code.push_str(&escaped);
+            rest = &rest[escaped.len()..];
+            continue;
+        } else if c == '#' && !in_quotes {
+            rest = skip_comment(rest);
+            continue;
+        }
+        code.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+
+    code
+}
+
+/// Skips the comment starting at `rest` (which begins with `#`), returning what follows it.
+fn skip_comment(rest: &str) -> &str {
+    let after_hash = &rest[1..];
+    if let Some(open) = after_hash.strip_prefix('[') {
+        let equals = open.len() - open.trim_start_matches('=').len();
+        if open[equals..].starts_with('[') {
+            let close = format!("]{}]", "=".repeat(equals));
+            let body = &open[equals + 1..];
+            return match body.find(&close) {
+                Some(end) => &body[end + close.len()..],
+                None => "",
+            };
+        }
+    }
+
+    match rest.find('\n') {
+        Some(newline) => &rest[newline..],
+        None => "",
+    }
+}
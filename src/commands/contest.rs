@@ -0,0 +1,133 @@
+use crate::commands::new::{
+    check_base_path, create_project, resolve_base_path, validate_project_name, NewCommand,
+    NewCommandArgs,
+};
+use crate::config::Config;
+use crate::error::{ProconError, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Most problems `--count` can generate, one per letter `A` to `Z`.
+pub const MAX_PROBLEM_COUNT: usize = 26;
+
+/// Arguments for creating a contest directory with one project per problem.
+#[derive(Debug, Clone)]
+pub struct ContestCommandArgs {
+    /// Name of the contest directory, e.g. `abc300`.
+    pub name: String,
+    /// Problem ids, each becoming a subproject and its `{{PROJECT_NAME}}`.
+    pub problems: Vec<String>,
+    pub template: String,
+    /// Directory to create the contest in; the current directory when unset.
+    pub path: Option<PathBuf>,
+    /// Create `path` and its parents when they don't exist, instead of failing.
+    pub create_parents: bool,
+}
+
+/// Outcome of a successful `contest` invocation.
+#[derive(Debug)]
+pub struct ContestCommandOutput {
+    pub contest_path: PathBuf,
+    /// Each problem id with the directory of its project, in the order given.
+    pub problems: Vec<(String, PathBuf)>,
+}
+
+pub struct ContestCommand;
+
+impl ContestCommand {
+    /// Returns the problem ids `A`, `B`, ... for a contest with `count` problems.
+    ///
+    /// # Errors
+    ///
+    /// * `ConfigError` - `count` is zero or more than [`MAX_PROBLEM_COUNT`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use procon_rs::commands::contest::ContestCommand;
+    ///
+    /// assert_eq!(ContestCommand::problem_ids(3).unwrap(), ["A", "B", "C"]);
+    /// ```
+    pub fn problem_ids(count: usize) -> Result<Vec<String>> {
+        if count == 0 || count > MAX_PROBLEM_COUNT {
+            return Err(ProconError::ConfigError(format!(
+                "Invalid problem count {}: expected 1 to {}",
+                count, MAX_PROBLEM_COUNT
+            )));
+        }
+        Ok((b'A'..).take(count).map(|letter| (letter as char).to_string()).collect())
+    }
+
+    /// Creates the contest directory and a project for every problem.
+    ///
+    /// Every problem is checked before anything is written, so an existing problem
+    /// directory leaves the filesystem untouched. Should creating a problem fail
+    /// anyway, the problems created so far are removed again, along with the contest
+    /// directory and any parents this run created.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidProjectName` - The contest name or a problem id isn't a valid directory name
+    /// * `ProjectExists` - A problem directory already exists
+    /// * `ProjectCreationFailed` - `path` doesn't exist and `create_parents` is unset
+    /// * `ConfigError` - No problems were given, or one was given twice
+    /// * Any error of [`create_project`]
+    pub fn execute(args: &ContestCommandArgs, config: &Config) -> Result<ContestCommandOutput> {
+        validate_project_name(&args.name)?;
+        if args.problems.is_empty() {
+            return Err(ProconError::ConfigError("A contest needs at least one problem".to_string()));
+        }
+
+        let contest_path = match &args.path {
+            Some(path) => {
+                check_base_path(path, args.create_parents)?;
+                resolve_base_path(path)?.join(&args.name)
+            }
+            None => std::env::current_dir()?.join(&args.name),
+        };
+        let mut planned = Vec::new();
+        for (index, problem) in args.problems.iter().enumerate() {
+            if args.problems[..index].contains(problem) {
+                return Err(ProconError::ConfigError(format!("Problem '{}' is listed twice", problem)));
+            }
+            let problem_args = NewCommandArgs {
+                name: problem.clone(),
+                template: args.template.clone(),
+                path: Some(contest_path.clone()),
+                // The contest directory is created along with its first problem
+                create_parents: true,
+                ..Default::default()
+            };
+            let problem_path = NewCommand::project_path(&problem_args, config)?;
+            if problem_path.exists() {
+                return Err(ProconError::ProjectExists(format!("{}/{}", args.name, problem)));
+            }
+            planned.push((problem_args, problem_path));
+        }
+
+        // The outermost directory this run creates, so a rollback removes exactly that
+        let created_root = contest_path
+            .ancestors()
+            .take_while(|ancestor| !ancestor.exists())
+            .last()
+            .map(Path::to_path_buf);
+        let mut problems: Vec<(String, PathBuf)> = Vec::new();
+        for (problem_args, problem_path) in planned {
+            if let Err(e) = create_project(&problem_args, config) {
+                for (_, created_path) in &problems {
+                    let _ = fs::remove_dir_all(created_path);
+                }
+                if let Some(created_root) = &created_root {
+                    let _ = fs::remove_dir_all(created_root);
+                }
+                return Err(e);
+            }
+            problems.push((problem_args.name, problem_path));
+        }
+
+        Ok(ContestCommandOutput {
+            contest_path,
+            problems,
+        })
+    }
+}
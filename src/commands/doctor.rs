@@ -0,0 +1,115 @@
+use crate::config::Config;
+use crate::error::{ProconError, Result};
+use crate::exec;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Compilers probed by `doctor`; at least one of them must be installed.
+pub const COMPILERS: &[&str] = &["g++", "clang++"];
+
+/// Result of probing one program on PATH.
+#[derive(Debug, Clone)]
+pub struct ToolCheck {
+    pub name: String,
+    /// First line of the program's `--version` output, or `None` when it isn't installed.
+    pub version: Option<String>,
+}
+
+/// Everything `doctor` found out about the environment.
+#[derive(Debug)]
+pub struct DoctorReport {
+    pub cmake: ToolCheck,
+    /// Each of [`COMPILERS`], plus `project.compiler` when it is another one.
+    pub compilers: Vec<ToolCheck>,
+    pub git: ToolCheck,
+    pub config_dir: PathBuf,
+    /// Why the config directory can't be written to, if it can't.
+    pub config_dir_problem: Option<String>,
+}
+
+impl DoctorReport {
+    /// Lists the problems that keep procon_rs projects from being built or configured.
+    ///
+    /// A missing git is not one of them, since only `new --git` needs it.
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.cmake.version.is_none() {
+            problems.push("cmake not found on PATH".to_string());
+        }
+        if self.compilers.iter().all(|compiler| compiler.version.is_none()) {
+            let names: Vec<&str> = self.compilers.iter().map(|compiler| compiler.name.as_str()).collect();
+            problems.push(format!("no C++ compiler found on PATH (tried {})", names.join(", ")));
+        }
+        if let Some(problem) = &self.config_dir_problem {
+            problems.push(problem.clone());
+        }
+        problems
+    }
+}
+
+pub struct DoctorCommand;
+
+impl DoctorCommand {
+    /// Probes the tools procon_rs projects rely on and the config directory.
+    ///
+    /// Nothing is changed, except that a probe file is briefly created in the config
+    /// directory (or its nearest existing ancestor) to check it is writable.
+    pub fn execute(config: &Config, config_path: &Path) -> DoctorReport {
+        let mut compiler_names: Vec<&str> = COMPILERS.to_vec();
+        if !compiler_names.contains(&config.project.compiler.as_str()) {
+            compiler_names.push(&config.project.compiler);
+        }
+        let config_dir = config_path.parent().map(Path::to_path_buf).unwrap_or_default();
+
+        DoctorReport {
+            cmake: Self::probe("cmake"),
+            compilers: compiler_names.into_iter().map(Self::probe).collect(),
+            git: Self::probe("git"),
+            config_dir_problem: Self::check_writable(&config_dir).err().map(|e| e.to_string()),
+            config_dir,
+        }
+    }
+
+    /// Runs `program --version` and keeps the first line it prints.
+    fn probe(program: &str) -> ToolCheck {
+        let version = exec::capture(Command::new(program).arg("--version"))
+            .ok()
+            .filter(|output| output.success())
+            .map(|output| {
+                let text = if output.stdout.trim().is_empty() { output.stderr } else { output.stdout };
+                text.lines().next().unwrap_or_default().trim().to_string()
+            });
+
+        ToolCheck {
+            name: program.to_string(),
+            version,
+        }
+    }
+
+    /// Checks that files can be created in `dir`, or in its nearest existing ancestor
+    /// when it doesn't exist yet, since `config` creates it on first save.
+    ///
+    /// # Errors
+    ///
+    /// * `ConfigError` - The directory is a file, or a probe file can't be written there
+    fn check_writable(dir: &Path) -> Result<()> {
+        let Some(existing) = dir.ancestors().find(|ancestor| ancestor.exists()) else {
+            return Ok(());
+        };
+        if !existing.is_dir() {
+            return Err(ProconError::ConfigError(format!(
+                "config directory {} is blocked by a file at {}",
+                dir.display(),
+                existing.display()
+            )));
+        }
+
+        let probe = existing.join(format!(".procon_rs-doctor-{}", std::process::id()));
+        fs::write(&probe, b"").map_err(|e| {
+            ProconError::ConfigError(format!("config directory {} is not writable: {}", dir.display(), e))
+        })?;
+        let _ = fs::remove_file(&probe);
+        Ok(())
+    }
+}
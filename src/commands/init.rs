@@ -0,0 +1,145 @@
+use crate::cmake::parse_cmake_lists;
+use crate::commands::new::{copy_template, NewCommand};
+use crate::config::Config;
+use crate::error::{ProconError, Result};
+use crate::project::{CreatedRecord, Ledger};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub struct InitCommandArgs {
+    /// Existing directory to scaffold into.
+    pub path: PathBuf,
+    pub template: String,
+    /// Overwrite files that already exist instead of keeping them.
+    pub force: bool,
+}
+
+/// Values substituted into the template when initializing an existing directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InitDefaults {
+    pub project_name: String,
+    pub cpp_standard: String,
+}
+
+/// Outcome of a successful `init` invocation.
+#[derive(Debug)]
+pub struct InitCommandOutput {
+    pub defaults: InitDefaults,
+    /// Template files written into the directory, sorted.
+    pub created: Vec<String>,
+    /// Template files left out because the directory already had them, sorted.
+    pub skipped: Vec<String>,
+    /// The template's post-creation message with variables already substituted.
+    pub post_message: Option<String>,
+}
+
+pub struct InitCommand;
+
+impl InitCommand {
+    /// Derives the project name and C++ standard for initializing `dir`.
+    ///
+    /// An existing `CMakeLists.txt` takes precedence so scaffolding matches what is
+    /// already there. Otherwise the name is the directory's name and the standard
+    /// comes from the configuration.
+    ///
+    /// # Errors
+    ///
+    /// * `Io` - The directory could not be resolved or its `CMakeLists.txt` could not be read
+    pub fn defaults(dir: &Path, config: &Config) -> Result<InitDefaults> {
+        let cmake_lists = dir.join("CMakeLists.txt");
+        let settings = if cmake_lists.is_file() {
+            parse_cmake_lists(&fs::read_to_string(cmake_lists)?)
+        } else {
+            Default::default()
+        };
+
+        let project_name = match settings.project_name {
+            Some(name) => name,
+            None => dir
+                .canonicalize()?
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "main".to_string()),
+        };
+
+        Ok(InitDefaults {
+            project_name,
+            cpp_standard: settings
+                .cpp_standard
+                .unwrap_or_else(|| config.project.cpp_standard.clone()),
+        })
+    }
+
+    /// Scaffolds a template into an existing directory.
+    ///
+    /// Files the directory already has are kept unless `force` is set; a root
+    /// `.gitignore` is always merged rather than replaced. An existing ledger and
+    /// creation record, e.g. from `new`, are extended and kept rather than replaced.
+    ///
+    /// # Errors
+    ///
+    /// * `ProjectNotFound` - The directory doesn't exist
+    /// * `TemplateNotFound` - The template could not be loaded
+    /// * `ConfigError` - The existing ledger is malformed
+    /// * `ProjectCreationFailed` - A template file path would leave the directory
+    /// * `Io` - Files could not be read or written
+    pub fn execute(args: InitCommandArgs, mut config: Config) -> Result<InitCommandOutput> {
+        if !args.path.is_dir() {
+            return Err(ProconError::ProjectNotFound);
+        }
+
+        let defaults = Self::defaults(&args.path, &config)?;
+        config.project.cpp_standard = defaults.cpp_standard.clone();
+
+        let (template, _) = NewCommand::load_template(&args.template, &config, false, false)?;
+        let mut template =
+            NewCommand::process_template_variables(
+            template,
+            &defaults.project_name,
+            &config,
+            &HashMap::new(),
+        )?;
+        template.extend_gitignore(&config.project.gitignore_extra);
+
+        let mut skipped = Vec::new();
+        if !args.force {
+            let exists = |relative: &String| {
+                relative != ".gitignore" && args.path.join(relative).exists()
+            };
+            skipped.extend(template.files.keys().filter(|key| exists(key)).cloned());
+            skipped.extend(template.binary_files.keys().filter(|key| exists(key)).cloned());
+            template.files.retain(|key, _| !skipped.contains(key));
+            template.binary_files.retain(|key, _| !skipped.contains(key));
+        }
+
+        // A merged .gitignore belongs to the user, so only a new one goes in the ledger
+        let gitignore_existed = args.path.join(".gitignore").exists();
+        let ledger = Ledger::read(&args.path)?.unwrap_or_default();
+        copy_template(&template, &args.path)?;
+        // A project created by `new` keeps the record of the template it came from
+        if !CreatedRecord::path_in(&args.path).exists() {
+            CreatedRecord::new(&args.template).write(&args.path)?;
+        }
+
+        // Files from earlier runs stay listed so `remove` still cleans them up
+        let created = template.file_paths();
+        let mut ledger_files = ledger.files;
+        for path in &created {
+            let user_gitignore = gitignore_existed && path.as_str() == ".gitignore";
+            if !user_gitignore && !ledger_files.contains(path) {
+                ledger_files.push(path.clone());
+            }
+        }
+        Ledger::new(ledger_files).write(&args.path)?;
+        skipped.sort();
+        let post_message = template.manifest.and_then(|manifest| manifest.post_message);
+
+        Ok(InitCommandOutput {
+            defaults,
+            created,
+            skipped,
+            post_message,
+        })
+    }
+}
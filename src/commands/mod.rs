@@ -1 +1,8 @@
-pub mod new;
\ No newline at end of file
+pub mod contest;
+pub mod doctor;
+pub mod init;
+pub mod new;
+pub mod remove;
+pub mod rename;
+pub mod template;
+pub mod undo;
\ No newline at end of file
@@ -1,64 +1,660 @@
-use crate::config::Config;
+use crate::config::{check_cpp_standard, Config};
 use crate::error::{ProconError, Result};
-use crate::template::{Template, TemplateLoader};
+use crate::git;
+use crate::glob::glob_match;
+use crate::project::{nested_project_dir, run_on_create_hook, CreatedRecord, Ledger};
+use crate::prompt::Prompter;
+use crate::template::{
+    fill_defaulted_placeholders, validate_file_name, Fnv1a, Template, TemplateHooks, TemplateLoader, TemplateManifest,
+    TemplateSource, BUILTIN_TEMPLATES, BUILTIN_VARIABLES,
+};
+use chrono::{DateTime, Local, NaiveDate};
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::path::PathBuf;
+use std::io::{BufRead, Write};
+use std::path::{Component, Path, PathBuf};
+
+/// Prefix marking a `--template` value as a git URL, e.g. `git+https://host/repo.git`.
+pub const GIT_TEMPLATE_PREFIX: &str = "git+";
+
+/// `CMakeLists.txt` of a project whose `main.cpp` was given as content, e.g. piped in
+/// with `new --stdin-template`; its placeholders are filled from the config.
+pub const STDIN_TEMPLATE_CMAKE: &str = "cmake_minimum_required(VERSION {{CMAKE_VERSION}})
+project({{PROJECT_NAME}})
+
+set(CMAKE_CXX_STANDARD {{CPP_STANDARD}})
+set(CMAKE_CXX_STANDARD_REQUIRED ON)
+
+add_executable({{PROJECT_NAME}} main.cpp)
+";
+
+/// Message of the initial commit made by `--git` when `--git-commit` doesn't give one.
+pub const DEFAULT_GIT_COMMIT_MESSAGE: &str = "Initial commit from procon_rs";
 
 pub struct NewCommandArgs {
     pub name: String,
     pub template: String,
     pub path: Option<PathBuf>,
+    /// Skip the configured `project.on_create` hook and the template's `post_create` hooks.
+    pub no_hooks: bool,
+    /// Initialize a git repository in the new project and commit the scaffolded files.
+    pub git: bool,
+    /// Initial branch name for the new repository.
+    pub git_branch: Option<String>,
+    /// Message for the initial commit; [`DEFAULT_GIT_COMMIT_MESSAGE`] when unset.
+    pub git_commit: Option<String>,
+    /// Leave out the files the template's manifest marks as samples.
+    pub no_samples: bool,
+    /// Leave out the template's `.gitignore`, along with `project.gitignore_extra`.
+    pub no_gitignore: bool,
+    /// Report the project path with symlinks resolved.
+    pub canonicalize: bool,
+    /// Load user templates even when they lack required files.
+    pub allow_incomplete: bool,
+    /// Values for custom `{{NAME}}` variables, overriding the manifest's defaults.
+    pub defines: HashMap<String, String>,
+    /// Replace an existing project directory, as long as it looks like a generated project.
+    pub force: bool,
+    /// Load the template from this directory instead of looking `template` up by name.
+    pub template_dir: Option<PathBuf>,
+    /// Create `path` and its missing ancestors instead of failing when it doesn't exist.
+    pub create_parents: bool,
+    /// Re-clone a `git+` template instead of reusing its cached clone.
+    pub refresh: bool,
+    /// C++ standard for `{{CPP_STANDARD}}`, overriding `project.cpp_standard`.
+    pub cpp_standard: Option<String>,
+    /// Content of `main.cpp` to use instead of a template on disk, paired with
+    /// [`STDIN_TEMPLATE_CMAKE`].
+    pub stdin_template: Option<String>,
+}
+
+impl Default for NewCommandArgs {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            template: "default".to_string(),
+            path: None,
+            no_hooks: false,
+            git: false,
+            git_branch: None,
+            git_commit: None,
+            no_samples: false,
+            no_gitignore: false,
+            canonicalize: false,
+            allow_incomplete: false,
+            defines: HashMap::new(),
+            force: false,
+            template_dir: None,
+            create_parents: false,
+            refresh: false,
+            cpp_standard: None,
+            stdin_template: None,
+        }
+    }
+}
+
+impl NewCommandArgs {
+    /// Asks for the project name, template and C++ standard, for `new` run without a
+    /// name from a terminal.
+    ///
+    /// The available templates are listed first. The template and standard default to
+    /// the ones already set, falling back to `project.cpp_standard`; the name has no
+    /// default and is asked for again until it is valid, as is the standard.
+    ///
+    /// # Errors
+    ///
+    /// * `Io` - The answers could not be read, or the input ended early
+    pub fn prompt<R: BufRead, W: Write>(
+        mut self,
+        prompter: &mut Prompter<R, W>,
+        config: &Config,
+    ) -> Result<Self> {
+        self.name = prompter.ask_until_valid("Project name", None, validate_project_name)?;
+
+        let templates = TemplateLoader::with_search_path(config.template_dir()).list_templates();
+        prompter.say(&format!("Available templates: {}", templates.join(", ")))?;
+        self.template = prompter.ask("Template", Some(&self.template))?;
+
+        let standard = self
+            .cpp_standard
+            .clone()
+            .unwrap_or_else(|| config.project.cpp_standard.clone());
+        self.cpp_standard =
+            Some(prompter.ask_until_valid("C++ standard", Some(&standard), check_cpp_standard)?);
+        Ok(self)
+    }
+
+    /// Names the template being used: `stdin` for a `stdin_template`, the
+    /// `template_dir` path when one is given, otherwise `template`.
+    pub fn template_name(&self) -> String {
+        if self.stdin_template.is_some() {
+            return "stdin".to_string();
+        }
+        match &self.template_dir {
+            Some(dir) => dir.display().to_string(),
+            None => self.template.clone(),
+        }
+    }
+}
+
+/// Outcome of a successful `new` invocation, used by the CLI to report back to the user.
+#[derive(Debug)]
+pub struct NewCommandOutput {
+    pub project_path: PathBuf,
+    /// The template's post-creation message with variables already substituted.
+    pub post_message: Option<String>,
+    /// Non-fatal problems encountered after the project was created.
+    pub warnings: Vec<String>,
+    /// Where the template was loaded from.
+    pub template_source: TemplateSource,
+    /// Relative paths of the files written from the template, sorted.
+    pub files: Vec<String>,
+}
+
+/// A project resolved and rendered in memory, but not yet written to disk.
+#[derive(Debug)]
+pub struct NewCommandPlan {
+    pub project_path: PathBuf,
+    /// The template with all variables substituted.
+    pub template: Template,
+    pub template_source: TemplateSource,
+    /// Required files the template lacks, only possible with `allow_incomplete`.
+    pub missing_files: Vec<String>,
+}
+
+impl NewCommandPlan {
+    /// Returns every file that would be written, as destination path and size in bytes,
+    /// sorted by path. Symlinks have no content and count as 0 bytes.
+    pub fn planned_files(&self) -> Vec<(PathBuf, usize)> {
+        self.template
+            .file_paths()
+            .into_iter()
+            .map(|relative_path| {
+                let size = match self.template.files.get(&relative_path) {
+                    Some(content) => content.len(),
+                    None => self.template.binary_files.get(&relative_path).map_or(0, Vec::len),
+                };
+                (self.project_path.join(relative_path), size)
+            })
+            .collect()
+    }
+}
+
+/// Where a template placeholder gets its value from during `new`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VariableSource {
+    /// Substituted by procon_rs itself, such as `PROJECT_NAME`.
+    Builtin,
+    /// Given on the command line with `--define`.
+    Defined(String),
+    /// Set under `[defaults]` in the user's configuration.
+    Configured(String),
+    /// The default declared in the template manifest, or else written in the
+    /// template as `{{NAME:fallback}}`.
+    Default(String),
+    /// Has no value and is left as-is unless given with `--define`.
+    Undefined,
+}
+
+/// Creates a project as `procon_rs new` would and returns the paths of the files
+/// written from the template, sorted.
+///
+/// This is the entry point for driving project creation from code, such as editor
+/// plugins or test harnesses; nothing is printed and the given `config` is used as is.
+///
+/// # Examples
+///
+/// ```no_run
+/// use procon_rs::commands::new::{create_project, NewCommandArgs};
+/// use procon_rs::config::Config;
+///
+/// let args = NewCommandArgs {
+///     name: "abc300_a".to_string(),
+///     path: Some("contests".into()),
+///     ..Default::default()
+/// };
+/// let files = create_project(&args, &Config::default()).unwrap();
+/// assert!(files.iter().any(|path| path.ends_with("main.cpp")));
+/// ```
+pub fn create_project(args: &NewCommandArgs, config: &Config) -> Result<Vec<PathBuf>> {
+    let output = NewCommand::execute_with_config(args, config)?;
+    Ok(output
+        .files
+        .iter()
+        .map(|file| output.project_path.join(file))
+        .collect())
 }
 
 pub struct NewCommand;
 
 impl NewCommand {
-    pub fn execute(args: NewCommandArgs) -> Result<()> {
-        let config = Config::load().unwrap_or_default();
+    /// Creates a project with the user's configuration.
+    pub fn execute(args: NewCommandArgs) -> Result<NewCommandOutput> {
+        let config = Config::load()?;
+        Self::execute_with_config(&args, &config)
+    }
+
+    /// Creates a project with an explicit configuration, without reading the user's.
+    ///
+    /// This is everything `new` does short of printing: the files are written, then
+    /// the template hooks, git and the `on_create` hook run as configured.
+    pub fn execute_with_config(args: &NewCommandArgs, config: &Config) -> Result<NewCommandOutput> {
+        let NewCommandPlan {
+            project_path,
+            template: processed_template,
+            template_source,
+            missing_files,
+        } = Self::plan(args, config)?;
+
+        // Remember the outermost directory this run creates, so a failure can be rolled
+        // back without touching directories that existed before
+        let created_root = project_path
+            .ancestors()
+            .take_while(|ancestor| !ancestor.exists())
+            .last()
+            .map(Path::to_path_buf);
 
-        // Determine project path
-        let project_path = match args.path {
-            Some(base_path) => base_path.join(&args.name),
-            None => std::env::current_dir()?.join(&args.name),
+        // Build the project next to its destination and move it into place only once
+        // every file is written, so the project path never holds a partial project
+        let staging_dir = Self::staging_dir(&project_path);
+        let replaced_dir = match Self::write_project(
+            &processed_template,
+            &staging_dir,
+            &project_path,
+            &args.template_name(),
+        )
+        .and_then(|()| Self::move_into_place(&staging_dir, &project_path))
+        {
+            Ok(replaced_dir) => replaced_dir,
+            Err(e) => {
+                let _ = fs::remove_dir_all(&staging_dir);
+                if let Some(created_root) = &created_root {
+                    let _ = fs::remove_dir_all(created_root);
+                }
+                return Err(e);
+            }
         };
 
-        // Check if project already exists
+        if !args.no_hooks
+            && let Err(e) = Self::run_template_hooks(&processed_template, &project_path, &args.name)
+        {
+            let _ = fs::remove_dir_all(&project_path);
+            // A project replaced with --force goes back where it was
+            if let Some(replaced_dir) = &replaced_dir {
+                fs::rename(replaced_dir, &project_path)?;
+            } else if let Some(created_root) = &created_root {
+                let _ = fs::remove_dir_all(created_root);
+            }
+            return Err(e);
+        }
+
+        // The new project is complete, so a leftover copy of the old one is only clutter
+        if let Some(replaced_dir) = &replaced_dir {
+            let _ = fs::remove_dir_all(replaced_dir);
+        }
+
+        // The project exists by now, so failures from here on only warn
+        let mut warnings = processed_template.warnings.clone();
+
+        if !missing_files.is_empty() {
+            warnings.push(format!(
+                "Template '{}' is incomplete (missing {}); the project may not build",
+                args.template_name(),
+                missing_files.join(", ")
+            ));
+        }
+
+        let wants_git = args.git || args.git_branch.is_some() || args.git_commit.is_some();
+        if wants_git && let Err(e) = Self::init_git(&project_path, args) {
+            warnings.push(e.to_string());
+        }
+
+        // Run the user's on_create hook
+        if let Some(hook) = config.project.on_create.as_deref().filter(|_| !args.no_hooks)
+            && let Err(e) = run_on_create_hook(hook, &project_path, &args.name)
+        {
+            warnings.push(e.to_string());
+        }
+
+        // Fall back to the joined path if it can't be resolved, e.g. after a hook moved it
+        let project_path = if args.canonicalize {
+            fs::canonicalize(&project_path).unwrap_or(project_path)
+        } else {
+            project_path
+        };
+
+        let files = processed_template.file_paths();
+        let post_message = processed_template
+            .manifest
+            .and_then(|manifest| manifest.post_message);
+
+        Ok(NewCommandOutput {
+            project_path,
+            post_message,
+            warnings,
+            template_source,
+            files,
+        })
+    }
+
+    /// Runs the whole `new` pipeline except writing, for previewing a project.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`NewCommand::execute`] does before it writes anything,
+    /// including `ProjectExists` and `TemplateNotFound`.
+    pub fn dry_run(args: &NewCommandArgs, config: &Config) -> Result<NewCommandPlan> {
+        Self::plan(args, config)
+    }
+
+    /// Creates the project in `staging_dir`, copies the files and writes the `.procon`
+    /// records.
+    ///
+    /// A project at `project_path` is about to be replaced with `--force`; its root
+    /// `.gitignore` is merged into the template's, like `init` does, so user rules
+    /// survive. It only stays in the ledger if that project's ledger listed it.
+    fn write_project(
+        template: &Template,
+        staging_dir: &Path,
+        project_path: &Path,
+        template_name: &str,
+    ) -> Result<()> {
+        // Create project directory and copy files
+        fs::create_dir_all(staging_dir)?;
+        let mut files = template.file_paths();
+        let existing_gitignore = project_path.join(".gitignore");
+        if files.iter().any(|file| file == ".gitignore") && existing_gitignore.is_file() {
+            fs::copy(&existing_gitignore, staging_dir.join(".gitignore"))?;
+            let listed = Ledger::read(project_path)
+                .ok()
+                .flatten()
+                .is_some_and(|ledger| ledger.files.iter().any(|file| file == ".gitignore"));
+            if !listed {
+                files.retain(|file| file != ".gitignore");
+            }
+        }
+        copy_template(template, staging_dir)?;
+
+        // Record how the project was created so later commands can pick up the template,
+        // and which files were written so `remove` can undo it
+        CreatedRecord::new(template_name).write(staging_dir)?;
+        Ledger::new(files).write(staging_dir)?;
+        Ok(())
+    }
+
+    /// Lists the placeholders the template uses, sorted, with where each value comes from.
+    ///
+    /// Only the template is loaded; the project name and destination aren't checked.
+    pub fn list_variables(args: &NewCommandArgs, config: &Config) -> Result<Vec<(String, VariableSource)>> {
+        let (template, _) = Self::load_args_template(args, config)?;
+        let defaults = template
+            .manifest
+            .as_ref()
+            .map(|manifest| manifest.variables.clone())
+            .unwrap_or_default();
+        let inline_defaults = template.inline_defaults();
+
+        Ok(template
+            .variables()
+            .into_iter()
+            .map(|name| {
+                let source = if BUILTIN_VARIABLES.contains(&name.as_str()) {
+                    VariableSource::Builtin
+                } else if let Some(value) = args.defines.get(&name) {
+                    VariableSource::Defined(value.clone())
+                } else if let Some(value) = config.defaults.get(&name) {
+                    VariableSource::Configured(value.clone())
+                } else if let Some(value) = defaults.get(&name).or(inline_defaults.get(&name)) {
+                    VariableSource::Default(value.clone())
+                } else {
+                    VariableSource::Undefined
+                };
+                (name, source)
+            })
+            .collect())
+    }
+
+    /// Returns a hidden sibling of the project directory to build the project in.
+    fn staging_dir(project_path: &Path) -> PathBuf {
+        Self::hidden_sibling(project_path, "tmp")
+    }
+
+    /// Returns a hidden sibling of the project directory, tagged with `purpose` and
+    /// this process's id so concurrent runs don't collide.
+    fn hidden_sibling(project_path: &Path, purpose: &str) -> PathBuf {
+        let name = project_path.file_name().unwrap_or_default().to_string_lossy();
+        project_path.with_file_name(format!(".{}.procon-{}-{}", name, purpose, std::process::id()))
+    }
+
+    /// Moves a fully written staging directory to the project path, returning where
+    /// the project it replaced was moved aside to.
+    ///
+    /// An existing project is only there when `--force` allowed replacing it. It is
+    /// renamed aside, and renamed back if the move fails; the caller deletes it once
+    /// creation has fully succeeded, or renames it back if a later step fails, so a
+    /// failed run never loses it.
+    ///
+    /// The staging directory is a sibling of the project path, so every rename stays
+    /// within one directory and can't fail for crossing filesystems; there is no copy
+    /// fallback because it could never run.
+    fn move_into_place(staging_dir: &Path, project_path: &Path) -> Result<Option<PathBuf>> {
+        if !project_path.exists() {
+            fs::rename(staging_dir, project_path)?;
+            return Ok(None);
+        }
+
+        let replaced_dir = Self::hidden_sibling(project_path, "old");
+        fs::rename(project_path, &replaced_dir)?;
+        if let Err(e) = fs::rename(staging_dir, project_path) {
+            fs::rename(&replaced_dir, project_path)?;
+            return Err(e.into());
+        }
+        Ok(Some(replaced_dir))
+    }
+
+    /// Runs the template's `[hooks] post_create` commands inside the new project.
+    ///
+    /// # Errors
+    ///
+    /// * `ProjectCreationFailed` - A command failed; the message names it and its stderr
+    fn run_template_hooks(template: &Template, project_path: &Path, project_name: &str) -> Result<()> {
+        let Some(manifest) = &template.manifest else {
+            return Ok(());
+        };
+
+        for command in &manifest.hooks.post_create {
+            run_on_create_hook(command, project_path, project_name).map_err(|e| match e {
+                ProconError::HookFailed(reason) => {
+                    ProconError::ProjectCreationFailed(format!("template hook {}", reason))
+                }
+                other => other,
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Resolves the destination, loads the template and substitutes its variables.
+    fn plan(args: &NewCommandArgs, config: &Config) -> Result<NewCommandPlan> {
+        let overridden;
+        let config = match &args.cpp_standard {
+            Some(standard) => {
+                check_cpp_standard(standard)?;
+                let mut owned = config.clone();
+                owned.project.cpp_standard = standard.clone();
+                overridden = owned;
+                &overridden
+            }
+            None => config,
+        };
+        let project_path = Self::project_path(args, config)?;
+
+        if let Some(base_path) = &args.path {
+            check_base_path(base_path, args.create_parents)?;
+        }
+
+        // On case-insensitive filesystems `foo` resolves to an existing `Foo`; report
+        // it under its real name, and never let --force replace it under another case
+        if let Some(existing) = differently_cased_entry(&project_path) {
+            return Err(ProconError::ProjectExists(existing));
+        }
+
+        // Check if project already exists; --force may only replace a generated project
         if project_path.exists() {
-            return Err(ProconError::ProjectExists(args.name));
+            if !args.force {
+                return Err(ProconError::ProjectExists(args.name.clone()));
+            }
+            if !looks_like_project(&project_path) {
+                return Err(ProconError::ProjectCreationFailed(format!(
+                    "refusing to replace '{}' with --force: it has no main.cpp and CMakeLists.txt, \
+                     so it doesn't look like a generated project",
+                    project_path.display()
+                )));
+            }
         }
 
         // Load template
-        let template = Self::load_template(&args.template, &config)?;
+        let (mut template, template_source) = Self::load_args_template(args, config)?;
+        let missing_files = template.missing_required_files();
+        if args.no_samples {
+            template.remove_samples();
+        }
 
         // Process template with variables
-        let processed_template = Self::process_template_variables(template, &args.name, &config);
+        let mut template =
+            Self::process_template_variables(template, &args.name, config, &args.defines)?;
+        if args.no_gitignore {
+            template.remove_gitignore();
+        } else {
+            template.extend_gitignore(&config.project.gitignore_extra);
+        }
 
-        // Create project directory and copy files
-        fs::create_dir_all(&project_path)?;
-        processed_template.copy_to(&project_path)?;
+        Ok(NewCommandPlan {
+            project_path,
+            template,
+            template_source,
+            missing_files,
+        })
+    }
 
-        Ok(())
+    /// Resolves the directory a project would be created in, without creating anything.
+    ///
+    /// The name is joined to `args.path` (or the current directory), nested according
+    /// to `project.default_path_layout` when configured. A relative `args.path` is
+    /// resolved against the current directory first (see [`resolve_base_path`]).
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidProjectName` - The name could escape the base directory or isn't portable
+    /// * `Io` - No path was given and the current directory could not be determined
+    pub fn project_path(args: &NewCommandArgs, config: &Config) -> Result<PathBuf> {
+        validate_project_name(&args.name)?;
+
+        let base_path = match &args.path {
+            Some(base_path) => resolve_base_path(base_path)?,
+            None => std::env::current_dir()?,
+        };
+        let project_dir = config
+            .project
+            .default_path_layout
+            .as_deref()
+            .and_then(|layout| nested_project_dir(layout, &args.name))
+            .unwrap_or_else(|| PathBuf::from(&args.name));
+        Ok(base_path.join(project_dir))
+    }
+
+    /// Initializes the project's git repository and commits the scaffolded files.
+    ///
+    /// The commit uses `--git-commit`'s message, or [`DEFAULT_GIT_COMMIT_MESSAGE`].
+    /// A missing git executable or a failed commit leaves the project in place; the
+    /// caller reports the error as a warning.
+    fn init_git(project_path: &Path, args: &NewCommandArgs) -> Result<()> {
+        if !git::is_available() {
+            return Err(ProconError::GitFailed(
+                "git not found on PATH, skipping repository initialization".to_string(),
+            ));
+        }
+
+        git::init_repository(project_path, args.git_branch.as_deref())?;
+
+        let message = args.git_commit.as_deref().unwrap_or(DEFAULT_GIT_COMMIT_MESSAGE);
+        git::commit_all(project_path, message)
+    }
+
+    /// Loads the template `new` was asked for: the `stdin_template` content, the
+    /// `template_dir` directory as is, or else `template` by name (see
+    /// [`NewCommand::load_template`]).
+    ///
+    /// # Errors
+    ///
+    /// * `TemplateNotFound` - `template_dir` is not a directory, or lacks required files
+    ///   without `allow_incomplete`
+    fn load_args_template(args: &NewCommandArgs, config: &Config) -> Result<(Template, TemplateSource)> {
+        if let Some(main_cpp) = &args.stdin_template {
+            let template = Template::from_main_and_cmake(main_cpp, STDIN_TEMPLATE_CMAKE);
+            return Ok((template, TemplateSource::Stdin));
+        }
+        let Some(dir) = &args.template_dir else {
+            return Self::load_template(&args.template, config, args.allow_incomplete, args.refresh);
+        };
+        if !dir.is_dir() {
+            return Err(ProconError::TemplateNotFound(dir.display().to_string()));
+        }
+
+        let template = if args.allow_incomplete {
+            Template::load_incomplete_from_path(dir)?
+        } else {
+            Template::load_from_path(dir)?
+        };
+        Ok((template, TemplateSource::Directory(dir.clone())))
     }
 
-    fn load_template(template_name: &str, _config: &Config) -> Result<Template> {
-        let loader = TemplateLoader::new();
+    /// Loads a template by name from the user directory, the builtins, or the dev checkout.
+    ///
+    /// With `allow_incomplete`, a user template missing required files still loads.
+    /// With `refresh`, a `git+` template is cloned again even if it is cached.
+    pub(crate) fn load_template(
+        template_name: &str,
+        config: &Config,
+        allow_incomplete: bool,
+        refresh: bool,
+    ) -> Result<(Template, TemplateSource)> {
+        if let Some(url) = template_name.strip_prefix(GIT_TEMPLATE_PREFIX) {
+            return Self::load_git_template(url, allow_incomplete, refresh);
+        }
+
+        let loader = TemplateLoader::with_search_path(config.template_dir());
+        log::debug!(
+            "Searching template roots for '{}': {}",
+            template_name,
+            loader
+                .roots()
+                .iter()
+                .map(|root| root.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
         
         // Try to find user template first
         match loader.find_template(template_name) {
             Ok(template_path) => {
-                Template::load_from_path(&template_path)
+                let template = if allow_incomplete {
+                    Template::load_incomplete_from_path(&template_path)?
+                } else {
+                    Template::load_from_path(&template_path)?
+                };
+                Ok((template, TemplateSource::User(template_path)))
             }
-            Err(_) => {
+            Err(not_found) => {
                 // Template not found in user directory, try builtin templates
                 
                 // First try to load from built-in embedded templates
                 if let Ok(builtin_template) = Template::from_builtin(template_name) {
-                    return Ok(builtin_template);
+                    log::debug!("Using builtin template '{}'", template_name);
+                    return Ok((builtin_template, TemplateSource::Builtin));
                 }
                 
                 // If not a built-in template, try development environment
-                let builtin_templates = vec!["default", "advanced"];
-                if builtin_templates.contains(&template_name) {
+                if BUILTIN_TEMPLATES.contains(&template_name) {
                     // Try cargo manifest dir for development
                     if let Ok(manifest_dir) = std::env::var("CARGO_MANIFEST_DIR") {
                         let dev_template_path = PathBuf::from(manifest_dir)
@@ -66,34 +662,462 @@ impl NewCommand {
                             .join(template_name);
                         
                         if dev_template_path.exists() {
-                            return Template::load_from_path(&dev_template_path);
+                            log::debug!("Using development template {}", dev_template_path.display());
+                            let template = Template::load_from_path(&dev_template_path)?;
+                            return Ok((template, TemplateSource::Dev(dev_template_path)));
                         }
                     }
                     
                     // If neither embedded nor development template works, suggest user template
-                    return Err(ProconError::TemplateNotFoundWithHint(template_name.to_string()));
+                    return Err(ProconError::TemplateNotFoundWithHint(
+                        template_name.to_string(),
+                        config.template_dir().join(template_name),
+                    ));
                 }
                 
-                Err(ProconError::TemplateNotFound(template_name.to_string()))
+                // Report the directories that were searched
+                Err(not_found)
             }
         }
     }
 
-    fn process_template_variables(
+    /// Loads a `git+` template from its clone in the cache, cloning it first unless
+    /// a loadable clone is already there.
+    ///
+    /// Clones live in [`config::cache_dir`](crate::config::cache_dir), one directory per
+    /// URL named after its hash. A cached clone that no longer loads as a template is
+    /// replaced, as it is with `refresh`.
+    fn load_git_template(
+        url: &str,
+        allow_incomplete: bool,
+        refresh: bool,
+    ) -> Result<(Template, TemplateSource)> {
+        let mut key = Fnv1a::default();
+        key.feed(url.as_bytes());
+        let clone_dir = crate::config::cache_dir().join(key.hex());
+
+        if !refresh && clone_dir.is_dir() {
+            match Self::load_clone(&clone_dir, allow_incomplete) {
+                Ok(template) => {
+                    log::debug!("Using cached clone of {} in {}", url, clone_dir.display());
+                    return Ok((template, TemplateSource::Git(url.to_string(), clone_dir)));
+                }
+                Err(e) => log::debug!("Cached clone of {} is unusable ({}), cloning again", url, e),
+            }
+        }
+
+        log::debug!("Cloning template from {} into {}", url, clone_dir.display());
+        if clone_dir.exists() {
+            fs::remove_dir_all(&clone_dir)?;
+        }
+        let result = Self::load_cloned_template(url, &clone_dir, allow_incomplete);
+        if result.is_err() {
+            // Don't leave a broken clone to be retried from the cache
+            let _ = fs::remove_dir_all(&clone_dir);
+        }
+        Ok((result?, TemplateSource::Git(url.to_string(), clone_dir)))
+    }
+
+    fn load_cloned_template(url: &str, clone_dir: &Path, allow_incomplete: bool) -> Result<Template> {
+        let fetch_failed = |reason: String| ProconError::TemplateFetchFailed(url.to_string(), reason);
+
+        if let Some(cache_dir) = clone_dir.parent() {
+            fs::create_dir_all(cache_dir)?;
+        }
+        git::clone_shallow(url, clone_dir).map_err(|e| match e {
+            ProconError::GitFailed(reason) => fetch_failed(reason),
+            other => fetch_failed(other.to_string()),
+        })?;
+        // The repository metadata is not part of the template
+        fs::remove_dir_all(clone_dir.join(".git"))?;
+
+        Self::load_clone(clone_dir, allow_incomplete).map_err(|e| match e {
+            ProconError::TemplateNotFound(reason) => {
+                fetch_failed(format!("repository is not a template ({})", reason))
+            }
+            other => other,
+        })
+    }
+
+    fn load_clone(clone_dir: &Path, allow_incomplete: bool) -> Result<Template> {
+        if allow_incomplete {
+            Template::load_incomplete_from_path(clone_dir)
+        } else {
+            Template::load_from_path(clone_dir)
+        }
+    }
+
+    /// Substitutes template variables in file contents, file names, and the manifest.
+    ///
+    /// Supported variables:
+    /// * `{{PROJECT_NAME}}` - The project name, as given
+    /// * `{{PROJECT_NAME_UPPER}}` - The name uppercased, with every character other than
+    ///   an ASCII letter or digit replaced by `_`, for include guards: `my-cool project`
+    ///   becomes `MY_COOL_PROJECT`
+    /// * `{{PROJECT_NAME_SNAKE}}` - The name's words in lowercase joined by `_`
+    ///   (`my_cool_project`); words are split at non-alphanumeric characters and at
+    ///   lowercase-to-uppercase changes, so `myCoolProject` gives the same
+    /// * `{{PROJECT_NAME_CAMEL}}` - The same words as camelCase (`myCoolProject`)
+    /// * `{{CMAKE_VERSION}}` - The configured CMake version (or policy range)
+    /// * `{{CPP_STANDARD}}` - The configured C++ standard
+    /// * `{{DATE}}` - Today's local date as `YYYY-MM-DD`, computed once so every file
+    ///   and file name of the project agrees (see [`today`] for reproducible dates)
+    /// * `{{YEAR}}` - The four-digit year of that same date
+    /// * `{{PRAGMAS}}` - GCC optimization pragmas when `project.gcc_pragmas` is on and the
+    ///   compiler is GCC, otherwise nothing
+    /// * `{{COMPILER_FLAGS}}` - `project.compiler_flags` joined by spaces, or nothing
+    ///   when there are none
+    /// * Custom variables from `defines`, falling back to the config's `[defaults]` and
+    ///   then to the defaults in the manifest's `[variables]` table. Built-in variables
+    ///   take precedence, and placeholders that are neither built in nor defined are
+    ///   left untouched.
+    ///
+    /// Any of these may be written `{{NAME:fallback}}`, which is replaced by the
+    /// variable's value when it has one and by `fallback` otherwise. The fallback runs
+    /// up to the closing `}}`, so it may itself contain colons, as in
+    /// `{{URL:https://atcoder.jp}}`.
+    ///
+    /// Binary files, symlinks (whose targets are kept), and files matched by the
+    /// manifest's `no_substitute` (see [`Template::is_verbatim`]), keep their content
+    /// but have their names substituted. Files matched by the manifest's
+    /// `conditional_files` are dropped first when their variable is undefined or falsey.
+    pub(crate) fn process_template_variables(
         template: Template,
         project_name: &str,
         config: &Config,
-    ) -> Template {
-        let mut files = std::collections::HashMap::new();
+        defines: &HashMap<String, String>,
+    ) -> Result<Template> {
+        let cmake_version = config.project.cmake_version_spec()?;
+        let today = today();
+        let date = today.format("%Y-%m-%d").to_string();
+        let year = today.format("%Y").to_string();
+        let pragmas = config.project.pragmas();
+        let compiler_flags = config.project.compiler_flags.join(" ");
+        let upper_name = upper_case_name(project_name);
+        let snake_name = snake_case_name(project_name);
+        let camel_name = camel_case_name(project_name);
+        // Defines override the configured defaults, which override the manifest's;
+        // undefined placeholders are left intact
+        let mut values: BTreeMap<String, String> = template
+            .manifest
+            .as_ref()
+            .map(|manifest| manifest.variables.clone())
+            .unwrap_or_default();
+        values.extend(config.defaults.iter().map(|(name, value)| (name.clone(), value.clone())));
+        values.extend(defines.iter().map(|(name, value)| (name.clone(), value.clone())));
+        let conditions = template
+            .manifest
+            .as_ref()
+            .map(|manifest| manifest.conditional_files.clone())
+            .unwrap_or_default();
+        let is_included = |path: &str| {
+            conditions.iter().all(|(pattern, variable)| {
+                !glob_match(pattern, path) || values.get(variable).is_some_and(|value| is_truthy(value))
+            })
+        };
+        let custom_variables: Vec<(String, String)> = values
+            .iter()
+            .map(|(name, value)| (format!("{{{{{}}}}}", name), value.clone()))
+            .collect();
+        let substitute = |content: &str| {
+            let mut content = content
+                // A marker on its own line disappears entirely when there are no pragmas
+                .replace("{{PRAGMAS}}\n", pragmas)
+                .replace("{{PRAGMAS}}", pragmas.trim_end())
+                .replace("{{COMPILER_FLAGS}}", &compiler_flags)
+                .replace("{{PROJECT_NAME}}", project_name)
+                .replace("{{PROJECT_NAME_UPPER}}", &upper_name)
+                .replace("{{PROJECT_NAME_SNAKE}}", &snake_name)
+                .replace("{{PROJECT_NAME_CAMEL}}", &camel_name)
+                .replace("{{CMAKE_VERSION}}", &cmake_version)
+                .replace("{{CPP_STANDARD}}", &config.project.cpp_standard)
+                .replace("{{DATE}}", &date)
+                .replace("{{YEAR}}", &year);
+            for (placeholder, value) in &custom_variables {
+                content = content.replace(placeholder, value);
+            }
+            fill_defaulted_placeholders(&content, |name| match name {
+                "PRAGMAS" => Some(pragmas.trim_end().to_string()),
+                "COMPILER_FLAGS" => Some(compiler_flags.clone()),
+                "PROJECT_NAME" => Some(project_name.to_string()),
+                "PROJECT_NAME_UPPER" => Some(upper_name.clone()),
+                "PROJECT_NAME_SNAKE" => Some(snake_name.clone()),
+                "PROJECT_NAME_CAMEL" => Some(camel_name.clone()),
+                "CMAKE_VERSION" => Some(cmake_version.clone()),
+                "CPP_STANDARD" => Some(config.project.cpp_standard.clone()),
+                "DATE" => Some(date.clone()),
+                "YEAR" => Some(year.clone()),
+                _ => values.get(name).cloned(),
+            })
+        };
+        let substitute_name = |filename: &str| -> Result<String> {
+            let processed_name = substitute(filename);
+            validate_file_name(&processed_name)?;
+            Ok(processed_name)
+        };
+
+        let mut files = HashMap::new();
 
+        let verbatim: Vec<String> =
+            template.files.keys().filter(|path| template.is_verbatim(path)).cloned().collect();
         for (filename, content) in template.files {
-            let processed_content = content
-                .replace("{{PROJECT_NAME}}", project_name)
-                .replace("{{CMAKE_VERSION}}", &config.project.cmake_minimum_version)
-                .replace("{{CPP_STANDARD}}", &config.project.cpp_standard);
-            files.insert(filename, processed_content);
+            if !is_included(&filename) {
+                continue;
+            }
+            let processed_content = if verbatim.contains(&filename) {
+                content
+            } else {
+                substitute(&content)
+            };
+            files.insert(substitute_name(&filename)?, processed_content);
+        }
+
+        let mut binary_files = HashMap::new();
+        for (filename, bytes) in template.binary_files {
+            if !is_included(&filename) {
+                continue;
+            }
+            binary_files.insert(substitute_name(&filename)?, bytes);
         }
 
-        Template { files }
+        let mut symlinks = HashMap::new();
+        for (filename, target) in template.symlinks {
+            if !is_included(&filename) {
+                continue;
+            }
+            symlinks.insert(substitute_name(&filename)?, target);
+        }
+
+        let manifest = template.manifest.map(|manifest| TemplateManifest {
+            post_message: manifest.post_message.as_deref().map(substitute),
+            hooks: TemplateHooks {
+                post_create: manifest.hooks.post_create.iter().map(|command| substitute(command)).collect(),
+            },
+            ..manifest
+        });
+
+        Ok(Template {
+            files,
+            binary_files,
+            symlinks,
+            manifest,
+            warnings: template.warnings,
+        })
+    }
+}
+
+/// Copies a template's files into a project directory, reporting a file path that
+/// would escape it, e.g. one built from a `--define` value, as a failed creation.
+///
+/// # Errors
+///
+/// * `ProjectCreationFailed` - A file path is absolute or leaves `project_path`
+/// * Any other error of [`Template::copy_to`]
+pub(crate) fn copy_template(template: &Template, project_path: &Path) -> Result<()> {
+    template.copy_to(project_path).map_err(|e| match e {
+        ProconError::UnsafeTemplatePath(_) => ProconError::ProjectCreationFailed(e.to_string()),
+        other => other,
+    })
+}
+
+/// Checks that a `--path` base directory exists, unless its creation was asked for.
+///
+/// A mistyped `--path` would otherwise scatter directories across the filesystem.
+///
+/// # Errors
+///
+/// * `ProjectCreationFailed` - `base_path` is not a directory and `create_parents` is unset
+pub(crate) fn check_base_path(base_path: &Path, create_parents: bool) -> Result<()> {
+    if !create_parents && !base_path.is_dir() {
+        return Err(ProconError::ProjectCreationFailed(format!(
+            "directory {} does not exist (pass --create-parents to create it)",
+            base_path.display()
+        )));
+    }
+    Ok(())
+}
+
+/// Makes a base directory absolute, resolving it against the current directory and
+/// folding its `.` and `..` components, so the reported project path doesn't depend
+/// on how `--path` was spelled.
+///
+/// The base need not exist yet. Symlinks are kept as given; `--canonicalize`
+/// resolves those.
+///
+/// # Errors
+///
+/// * `Io` - The path is relative and the current directory could not be determined
+pub(crate) fn resolve_base_path(path: &Path) -> Result<PathBuf> {
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir()?.join(path)
+    };
+
+    let mut resolved = PathBuf::new();
+    for component in absolute.components() {
+        match component {
+            Component::CurDir => {}
+            // `..` above the root stays at the root, as it does for the OS
+            Component::ParentDir => {
+                if resolved.parent().is_some() {
+                    resolved.pop();
+                }
+            }
+            other => resolved.push(other),
+        }
+    }
+    Ok(resolved)
+}
+
+/// Returns whether a directory has the files every generated project starts with.
+fn looks_like_project(dir: &Path) -> bool {
+    dir.join("main.cpp").is_file() && dir.join("CMakeLists.txt").is_file()
+}
+
+/// Returns the actual name of an existing entry that `path` resolves to under a
+/// different case, which only happens on case-insensitive filesystems.
+///
+/// On case-sensitive filesystems `Foo` and `foo` are distinct, so this is `None`
+/// and the usual exact-match check applies.
+fn differently_cased_entry(path: &Path) -> Option<String> {
+    let name = path.file_name()?.to_str()?;
+    if !path.exists() {
+        return None;
+    }
+
+    let parent = match path.parent()? {
+        parent if parent.as_os_str().is_empty() => Path::new("."),
+        parent => parent,
+    };
+    let entries: Vec<String> = fs::read_dir(parent)
+        .ok()?
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .collect();
+    if entries.iter().any(|entry| entry == name) {
+        return None;
+    }
+    entries.into_iter().find(|entry| entry.eq_ignore_ascii_case(name))
+}
+
+/// Uppercases a project name and replaces every character that isn't an ASCII letter
+/// or digit with `_`, as substituted for `{{PROJECT_NAME_UPPER}}`.
+///
+/// # Examples
+///
+/// ```
+/// use procon_rs::commands::new::upper_case_name;
+///
+/// assert_eq!(upper_case_name("my-cool project"), "MY_COOL_PROJECT");
+/// ```
+pub fn upper_case_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+        .collect()
+}
+
+/// Joins a project name's lowercase words with `_`, as substituted for `{{PROJECT_NAME_SNAKE}}`.
+///
+/// # Examples
+///
+/// ```
+/// use procon_rs::commands::new::snake_case_name;
+///
+/// assert_eq!(snake_case_name("my-cool project"), "my_cool_project");
+/// assert_eq!(snake_case_name("myCoolProject"), "my_cool_project");
+/// ```
+pub fn snake_case_name(name: &str) -> String {
+    name_words(name).join("_")
+}
+
+/// Joins a project name's words as camelCase, as substituted for `{{PROJECT_NAME_CAMEL}}`.
+///
+/// # Examples
+///
+/// ```
+/// use procon_rs::commands::new::camel_case_name;
+///
+/// assert_eq!(camel_case_name("my-cool project"), "myCoolProject");
+/// ```
+pub fn camel_case_name(name: &str) -> String {
+    name_words(name)
+        .iter()
+        .enumerate()
+        .map(|(index, word)| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) if index > 0 => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                _ => word.clone(),
+            }
+        })
+        .collect()
+}
+
+/// Splits a name into lowercase words at non-alphanumeric characters and at
+/// lowercase-to-uppercase changes.
+fn name_words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut previous_lower = false;
+    for c in name.chars() {
+        if !c.is_alphanumeric() {
+            words.extend((!current.is_empty()).then(|| std::mem::take(&mut current)));
+            previous_lower = false;
+            continue;
+        }
+        if c.is_uppercase() && previous_lower {
+            words.push(std::mem::take(&mut current));
+        }
+        previous_lower = c.is_lowercase() || c.is_ascii_digit();
+        current.extend(c.to_lowercase());
+    }
+    words.extend((!current.is_empty()).then_some(current));
+    words
+}
+
+/// Returns whether a variable value switches a conditional file on.
+fn is_truthy(value: &str) -> bool {
+    let value = value.trim().to_ascii_lowercase();
+    !matches!(value.as_str(), "" | "0" | "false" | "no" | "off")
+}
+
+/// Checks that a project name is a single, portable directory name.
+///
+/// The name becomes a path component (and `{{PROJECT_NAME}}`), so separators and a
+/// leading dot (which covers `.` and `..`) are rejected to keep the project inside
+/// its base directory, along with control characters and the characters Windows
+/// forbids (`<>:"|?*`). Dots elsewhere, as in `a..b`, are harmless.
+///
+/// # Errors
+///
+/// * `InvalidProjectName` - The name is empty or breaks one of the rules above
+pub fn validate_project_name(name: &str) -> Result<()> {
+    let is_forbidden = |c: char| c.is_control() || "/\\<>:\"|?*".contains(c);
+
+    if name.is_empty() || name.starts_with('.') || name.chars().any(is_forbidden) {
+        return Err(ProconError::InvalidProjectName(name.to_string()));
+    }
+    Ok(())
+}
+
+/// Environment variable fixing the build time for reproducible output, as a Unix timestamp.
+///
+/// See <https://reproducible-builds.org/specs/source-date-epoch/>.
+pub const SOURCE_DATE_EPOCH_ENV: &str = "SOURCE_DATE_EPOCH";
+
+/// Returns the date substituted for `{{DATE}}` and `{{YEAR}}`.
+///
+/// When `SOURCE_DATE_EPOCH` holds a valid timestamp, its UTC date is used so that
+/// creating the same project twice yields byte-identical files. Otherwise this is
+/// today's local date.
+pub fn today() -> NaiveDate {
+    let epoch = std::env::var(SOURCE_DATE_EPOCH_ENV)
+        .ok()
+        .and_then(|value| value.trim().parse::<i64>().ok())
+        .and_then(|seconds| DateTime::from_timestamp(seconds, 0));
+
+    match epoch {
+        Some(time) => time.date_naive(),
+        None => Local::now().date_naive(),
     }
 }
@@ -0,0 +1,90 @@
+use crate::error::{ProconError, Result};
+use crate::project::{Ledger, CREATED_RECORD_FILE, LEDGER_FILE, PROJECT_STATE_DIR};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Outcome of a `remove` run.
+#[derive(Debug, Default)]
+pub struct RemoveReport {
+    /// Ledger entries that were deleted, sorted.
+    pub removed: Vec<String>,
+    /// Ledger entries that no longer existed or were unsafe to touch, sorted.
+    pub missing: Vec<String>,
+    /// Whether the project directory itself was deleted because nothing was left in it.
+    pub removed_project_dir: bool,
+}
+
+pub struct RemoveCommand;
+
+impl RemoveCommand {
+    /// Returns the files `remove` would delete from a project, as listed in its ledger.
+    ///
+    /// # Errors
+    ///
+    /// * `LedgerNotFound` - The directory has no `.procon/ledger.json`
+    /// * `ConfigError` - The ledger is malformed
+    pub fn planned(project_dir: &Path) -> Result<Ledger> {
+        Ledger::read(project_dir)?
+            .ok_or_else(|| ProconError::LedgerNotFound(project_dir.display().to_string()))
+    }
+
+    /// Deletes the files procon_rs created in a project, leaving everything else intact.
+    ///
+    /// Directories that become empty are deleted too, as are the bookkeeping files in
+    /// `.procon/` and, when nothing else remains, the project directory. Ledger entries
+    /// that are absolute or contain `..` are never touched.
+    ///
+    /// # Errors
+    ///
+    /// * `LedgerNotFound` - The directory has no `.procon/ledger.json`
+    /// * `ConfigError` - The ledger is malformed
+    /// * `Io` - A listed file exists but could not be deleted
+    pub fn execute(project_dir: &Path) -> Result<RemoveReport> {
+        let ledger = Self::planned(project_dir)?;
+        let mut report = RemoveReport::default();
+        let mut parents: Vec<PathBuf> = Vec::new();
+
+        for entry in &ledger.files {
+            let relative = Path::new(entry);
+            let is_safe = relative
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)));
+            let path = project_dir.join(relative);
+            if !is_safe || !path.is_file() {
+                report.missing.push(entry.clone());
+                continue;
+            }
+
+            fs::remove_file(&path)?;
+            report.removed.push(entry.clone());
+            parents.extend(relative.ancestors().skip(1).map(|parent| project_dir.join(parent)));
+        }
+
+        let state_dir = project_dir.join(PROJECT_STATE_DIR);
+        for file in [LEDGER_FILE, CREATED_RECORD_FILE] {
+            let path = state_dir.join(file);
+            if path.is_file() {
+                fs::remove_file(path)?;
+            }
+        }
+        parents.push(state_dir);
+
+        // Deepest directories first, so nested empty directories collapse upwards
+        parents.sort_by_key(|dir| std::cmp::Reverse(dir.components().count()));
+        parents.dedup();
+        for dir in parents.iter().filter(|dir| dir.as_path() != project_dir) {
+            remove_dir_if_empty(dir);
+        }
+        report.removed_project_dir = remove_dir_if_empty(project_dir);
+
+        report.removed.sort();
+        report.missing.sort();
+        Ok(report)
+    }
+}
+
+/// Deletes a directory when it is empty, returning whether it was deleted.
+fn remove_dir_if_empty(dir: &Path) -> bool {
+    let is_empty = fs::read_dir(dir).is_ok_and(|mut entries| entries.next().is_none());
+    is_empty && fs::remove_dir(dir).is_ok()
+}
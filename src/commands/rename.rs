@@ -0,0 +1,101 @@
+use crate::commands::new::validate_project_name;
+use crate::error::{ProconError, Result};
+use regex::{Captures, Regex};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A CMake command call that names the project or its target, with its arguments.
+///
+/// Besides `project()` and `add_executable()`, the `target_*()` commands refer to
+/// the executable target, so they must follow its new name for the project to build.
+const NAMING_CALL_PATTERN: &str = r"(?i)\b(project|add_executable|target_[a-z_]+)(\s*\()([^)]*)\)";
+
+/// Outcome of a successful `rename`.
+#[derive(Debug)]
+pub struct RenameOutput {
+    pub project_path: PathBuf,
+    /// Whether `CMakeLists.txt` mentioned the old name and was rewritten.
+    pub cmake_rewritten: bool,
+}
+
+pub struct RenameCommand;
+
+impl RenameCommand {
+    /// Replaces the old project name with the new one in a `CMakeLists.txt`.
+    ///
+    /// Only arguments of `project()`, `add_executable()` and `target_*()` calls that
+    /// are exactly the old name are replaced; a longer name containing it, or the
+    /// name anywhere else in the file, is left alone.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use procon_rs::commands::rename::RenameCommand;
+    ///
+    /// let cmake = "project(foo)\nadd_executable(foo foo_main.cpp)\nset(FOO foo)\n";
+    /// assert_eq!(
+    ///     RenameCommand::rename_in_cmake(cmake, "foo", "bar"),
+    ///     "project(bar)\nadd_executable(bar foo_main.cpp)\nset(FOO foo)\n"
+    /// );
+    /// ```
+    pub fn rename_in_cmake(content: &str, old_name: &str, new_name: &str) -> String {
+        let call = Regex::new(NAMING_CALL_PATTERN).expect("naming call pattern is valid");
+        let argument = Regex::new(r"[^\s()]+").expect("argument pattern is valid");
+
+        call.replace_all(content, |caps: &Captures| {
+            let arguments = argument.replace_all(&caps[3], |arg: &Captures| {
+                if &arg[0] == old_name {
+                    new_name.to_string()
+                } else {
+                    arg[0].to_string()
+                }
+            });
+            format!("{}{}{})", &caps[1], &caps[2], arguments)
+        })
+        .into_owned()
+    }
+
+    /// Renames a project directory and updates the name in its `CMakeLists.txt`.
+    ///
+    /// The project keeps its parent directory. The old name is the directory's
+    /// current name; see [`RenameCommand::rename_in_cmake`] for what is rewritten.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidProjectName` - The new name isn't a valid directory name
+    /// * `ProjectNotFound` - `project_dir` is not a directory
+    /// * `ProjectExists` - A directory with the new name already exists next to it
+    /// * `Io` - The directory could not be renamed or `CMakeLists.txt` rewritten
+    pub fn execute(project_dir: &Path, new_name: &str) -> Result<RenameOutput> {
+        validate_project_name(new_name)?;
+        if !project_dir.is_dir() {
+            return Err(ProconError::ProjectNotFound);
+        }
+        let old_name = project_dir
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .ok_or(ProconError::ProjectNotFound)?;
+        let project_path = project_dir.with_file_name(new_name);
+        if project_path.exists() {
+            return Err(ProconError::ProjectExists(new_name.to_string()));
+        }
+
+        fs::rename(project_dir, &project_path)?;
+
+        let cmake_path = project_path.join("CMakeLists.txt");
+        let mut cmake_rewritten = false;
+        if cmake_path.is_file() {
+            let content = fs::read_to_string(&cmake_path)?;
+            let renamed = Self::rename_in_cmake(&content, &old_name, new_name);
+            if renamed != content {
+                fs::write(&cmake_path, renamed)?;
+                cmake_rewritten = true;
+            }
+        }
+
+        Ok(RenameOutput {
+            project_path,
+            cmake_rewritten,
+        })
+    }
+}
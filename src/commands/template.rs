@@ -0,0 +1,254 @@
+use crate::commands::new::{validate_project_name, NewCommand};
+use crate::config::Config;
+use crate::error::{ProconError, Result};
+use crate::template::{Template, TemplateSource, BUILTIN_TEMPLATES, BUILTIN_VARIABLES, MANIFEST_FILE};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Starter manifest written by `template new`, documenting the optional keys.
+const STARTER_MANIFEST: &str = r#"# Shown to users browsing templates
+description = "My competitive programming template"
+
+# Printed after a project is created; template variables are substituted
+# post_message = "Next: cd {{PROJECT_NAME}} && cmake -B build"
+
+# Example test cases, skipped with `new --no-samples`
+# samples = ["tests/*.in", "tests/*.out"]
+
+# Custom variables, substituted as {{AUTHOR}} etc., with their defaults
+# [variables]
+# AUTHOR = "anonymous"
+"#;
+
+/// A template directory that failed validation.
+#[derive(Debug)]
+pub struct InvalidTemplate {
+    pub name: String,
+    pub reason: String,
+    pub removed: bool,
+}
+
+/// Summary of a `template prune` run.
+#[derive(Debug, Default)]
+pub struct PruneReport {
+    /// Names of templates that passed validation, sorted.
+    pub kept: Vec<String>,
+    /// Templates that failed validation, sorted by name.
+    pub invalid: Vec<InvalidTemplate>,
+}
+
+/// Summary of a template printed by `template info`.
+#[derive(Debug)]
+pub struct TemplateInfo {
+    pub name: String,
+    pub source: TemplateSource,
+    /// The manifest's description, or a generated one when it has none.
+    pub description: String,
+    pub required_files: Vec<String>,
+    /// Custom variables the template uses, with their default if it declares one.
+    pub variables: Vec<(String, Option<String>)>,
+    /// Relative paths of every file, sorted.
+    pub files: Vec<String>,
+    pub total_bytes: usize,
+}
+
+pub struct TemplateCommand;
+
+impl TemplateCommand {
+    /// Checks a template directory the way a template author would before publishing it.
+    ///
+    /// Every problem is collected: an unreadable directory or manifest, missing required
+    /// files, and `{{NAME}}` placeholders that are neither builtin variables nor declared
+    /// in the manifest's `[variables]`. Returns the loaded template when there are none.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidTemplate` - One or more problems were found; each is listed
+    pub fn check(path: &Path) -> Result<Template> {
+        if !path.is_dir() {
+            return Err(ProconError::InvalidTemplate(vec![format!(
+                "{} is not a directory",
+                path.display()
+            )]));
+        }
+        let template = Template::load_incomplete_from_path(path)
+            .map_err(|e| ProconError::InvalidTemplate(vec![e.to_string()]))?;
+
+        let mut problems: Vec<String> = template
+            .missing_required_files()
+            .into_iter()
+            .map(|file_name| format!("missing required file {}", file_name))
+            .collect();
+
+        let declared = template.manifest.as_ref().map(|manifest| &manifest.variables);
+        let inline_defaults = template.inline_defaults();
+        for name in template.variables() {
+            let is_declared = declared.is_some_and(|variables| variables.contains_key(&name))
+                || inline_defaults.contains_key(&name);
+            if !BUILTIN_VARIABLES.contains(&name.as_str()) && !is_declared {
+                problems.push(format!(
+                    "{{{{{}}}}} is not a builtin variable and has no default in [variables]",
+                    name
+                ));
+            }
+        }
+
+        if !problems.is_empty() {
+            return Err(ProconError::InvalidTemplate(problems));
+        }
+        Ok(template)
+    }
+
+    /// Creates a user template `name` under `templates_dir`, starting from the builtin
+    /// default's `main.cpp`, `CMakeLists.txt` and `.gitignore` plus a starter manifest.
+    ///
+    /// Returns the new template directory. With `force`, an existing template of that
+    /// name is replaced.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidProjectName` - The name isn't a single portable directory name
+    /// * `TemplateExists` - The template exists and `force` is not set
+    /// * `Io` - The files could not be written
+    pub fn create(templates_dir: &Path, name: &str, force: bool) -> Result<PathBuf> {
+        validate_project_name(name)?;
+
+        let template_dir = templates_dir.join(name);
+        if template_dir.exists() {
+            if !force {
+                return Err(ProconError::TemplateExists(name.to_string()));
+            }
+            fs::remove_dir_all(&template_dir)?;
+        }
+
+        // Files are written with their placeholders intact
+        Template::from_builtin("default")?.copy_to(&template_dir)?;
+        fs::write(template_dir.join(MANIFEST_FILE), STARTER_MANIFEST)?;
+        Ok(template_dir)
+    }
+
+    /// Validates every template directory under `templates_dir`, optionally deleting broken ones.
+    ///
+    /// Each subdirectory is checked with [`Template::validate`]. With `remove`, invalid
+    /// templates are deleted, except those named like a builtin template, which are
+    /// only reported. A missing `templates_dir` yields an empty report.
+    ///
+    /// # Errors
+    ///
+    /// * `Io` - The directory could not be listed or an invalid template could not be removed
+    pub fn prune(templates_dir: &Path, remove: bool) -> Result<PruneReport> {
+        Self::prune_dir(
+            templates_dir,
+            remove,
+            |name| name.to_string(),
+            |name| BUILTIN_TEMPLATES.contains(&name),
+        )
+    }
+
+    /// Validates every cached `git+` template clone under `cache_dir` (see
+    /// [`cache_dir`](crate::config::cache_dir)), optionally deleting broken ones.
+    ///
+    /// Clones are named by a hash of their URL, so they are reported as
+    /// `cache/<hash>`. A deleted clone is simply fetched again by the next `new`
+    /// using it. A missing `cache_dir` yields an empty report.
+    ///
+    /// # Errors
+    ///
+    /// * `Io` - The directory could not be listed or an invalid clone could not be removed
+    pub fn prune_cache(cache_dir: &Path, remove: bool) -> Result<PruneReport> {
+        Self::prune_dir(cache_dir, remove, |name| format!("cache/{}", name), |_| false)
+    }
+
+    /// Validates each subdirectory of `dir`, deleting invalid ones with `remove`
+    /// unless `protected` says otherwise, and reports them as named by `label`.
+    fn prune_dir(
+        dir: &Path,
+        remove: bool,
+        label: impl Fn(&str) -> String,
+        protected: impl Fn(&str) -> bool,
+    ) -> Result<PruneReport> {
+        let mut report = PruneReport::default();
+        if !dir.is_dir() {
+            return Ok(report);
+        }
+
+        let mut entries: Vec<_> = fs::read_dir(dir)?
+            .collect::<std::io::Result<Vec<_>>>()?
+            .into_iter()
+            .filter(|entry| entry.path().is_dir())
+            .collect();
+        entries.sort_by_key(|entry| entry.file_name());
+
+        for entry in entries {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            match Template::validate(&entry.path()) {
+                Ok(()) => report.kept.push(label(&name)),
+                Err(e) => {
+                    let removable = remove && !protected(&name);
+                    if removable {
+                        fs::remove_dir_all(entry.path())?;
+                    }
+                    report.invalid.push(InvalidTemplate {
+                        name: label(&name),
+                        reason: e.to_string(),
+                        removed: removable,
+                    });
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Loads a template by name the same way `new` would, for inspection.
+    ///
+    /// # Errors
+    ///
+    /// * `TemplateNotFound` - No user, builtin, or development template has this name
+    /// * `Io` - The template directory could not be read
+    pub fn load(name: &str, config: &Config) -> Result<(Template, TemplateSource)> {
+        NewCommand::load_template(name, config, false, false)
+    }
+
+    /// Loads a template by name and summarizes it for `template info`.
+    ///
+    /// Variable defaults come from the manifest's `[variables]`, then from inline
+    /// `{{NAME:fallback}}` defaults. Templates without a description, such as the
+    /// builtins, get one naming their source and size.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`TemplateCommand::load`].
+    pub fn info(name: &str, config: &Config) -> Result<TemplateInfo> {
+        let (template, source) = Self::load(name, config)?;
+        let manifest = template.manifest.clone().unwrap_or_default();
+        let inline_defaults = template.inline_defaults();
+
+        let variables = template
+            .variables()
+            .into_iter()
+            .filter(|variable| !BUILTIN_VARIABLES.contains(&variable.as_str()))
+            .map(|variable| {
+                let default = manifest
+                    .variables
+                    .get(&variable)
+                    .or_else(|| inline_defaults.get(&variable))
+                    .cloned();
+                (variable, default)
+            })
+            .collect();
+        let description = manifest.description.clone().unwrap_or_else(|| {
+            format!("{} template '{}' with {} file(s)", source.kind(), name, template.file_count())
+        });
+
+        Ok(TemplateInfo {
+            name: name.to_string(),
+            description,
+            required_files: template.required_files(),
+            variables,
+            files: template.file_paths(),
+            total_bytes: template.total_bytes(),
+            source,
+        })
+    }
+}
@@ -0,0 +1,143 @@
+use crate::error::{ProconError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File next to the config file remembering the project `new` created last.
+pub const LAST_PROJECT_FILE: &str = "last_project.json";
+
+/// The project `undo` would delete, as it was when `new` finished.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LastProject {
+    pub path: PathBuf,
+    /// Size in bytes of every file in the project, by `/`-separated relative path.
+    pub files: BTreeMap<String, u64>,
+}
+
+impl LastProject {
+    /// Records every file below `project_dir` with its size.
+    ///
+    /// Symlinks are recorded, not followed.
+    pub fn snapshot(project_dir: &Path) -> Result<Self> {
+        let mut files = BTreeMap::new();
+        collect_sizes(project_dir, "", &mut files)?;
+        Ok(Self {
+            path: project_dir.to_path_buf(),
+            files,
+        })
+    }
+
+    /// Returns the files added, removed or resized since the snapshot, sorted.
+    pub fn changed_files(&self) -> Result<Vec<String>> {
+        let current = Self::snapshot(&self.path)?;
+        let mut changed: Vec<String> = self
+            .files
+            .iter()
+            .filter(|(path, size)| current.files.get(*path) != Some(*size))
+            .chain(
+                current
+                    .files
+                    .iter()
+                    .filter(|(path, _)| !self.files.contains_key(*path)),
+            )
+            .map(|(path, _)| path.clone())
+            .collect();
+        changed.sort();
+        Ok(changed)
+    }
+}
+
+/// Adds the size of every file below `dir` to `files`, keyed by `prefix`ed relative path.
+fn collect_sizes(dir: &Path, prefix: &str, files: &mut BTreeMap<String, u64>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let relative_path = if prefix.is_empty() {
+            name
+        } else {
+            format!("{}/{}", prefix, name)
+        };
+
+        let metadata = fs::symlink_metadata(entry.path())?;
+        if metadata.is_dir() {
+            collect_sizes(&entry.path(), &relative_path, files)?;
+        } else {
+            files.insert(relative_path, metadata.len());
+        }
+    }
+    Ok(())
+}
+
+pub struct UndoCommand;
+
+impl UndoCommand {
+    /// Returns where the last created project is remembered: [`LAST_PROJECT_FILE`]
+    /// in the directory of the config file in use.
+    pub fn state_path(config_path: &Path) -> PathBuf {
+        config_path
+            .parent()
+            .map(|config_dir| config_dir.join(LAST_PROJECT_FILE))
+            .unwrap_or_else(|| PathBuf::from(LAST_PROJECT_FILE))
+    }
+
+    /// Remembers a just-created project, replacing the previously remembered one.
+    ///
+    /// # Errors
+    ///
+    /// * `Io` - The project could not be read or the state file could not be written
+    pub fn record(state_path: &Path, project_dir: &Path) -> Result<()> {
+        let last = LastProject::snapshot(project_dir)?;
+        if let Some(parent) = state_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(&last)
+            .map_err(|e| ProconError::ConfigError(e.to_string()))?;
+        fs::write(state_path, json)?;
+        Ok(())
+    }
+
+    /// Returns the project `undo` would delete, checking it is unchanged since `new`.
+    ///
+    /// # Errors
+    ///
+    /// * `NothingToUndo` - No project is remembered, or it no longer exists
+    /// * `ProjectModified` - Files were added, removed or resized since it was created
+    /// * `ConfigError` - The state file is malformed
+    pub fn planned(state_path: &Path) -> Result<LastProject> {
+        if !state_path.is_file() {
+            return Err(ProconError::NothingToUndo);
+        }
+        let content = fs::read_to_string(state_path)?;
+        let last: LastProject = serde_json::from_str(&content).map_err(|e| {
+            ProconError::ConfigError(format!("Malformed {}: {}", state_path.display(), e))
+        })?;
+
+        if !last.path.is_dir() {
+            // Already gone, so there is nothing left to remember either
+            fs::remove_file(state_path)?;
+            return Err(ProconError::NothingToUndo);
+        }
+        let changed = last.changed_files()?;
+        if !changed.is_empty() {
+            return Err(ProconError::ProjectModified(
+                last.path.display().to_string(),
+                changed,
+            ));
+        }
+        Ok(last)
+    }
+
+    /// Deletes the last created project and forgets it, so it can only be undone once.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`UndoCommand::planned`], and `Io` when the project could
+    /// not be deleted.
+    pub fn execute(state_path: &Path) -> Result<PathBuf> {
+        let last = Self::planned(state_path)?;
+        fs::remove_dir_all(&last.path)?;
+        fs::remove_file(state_path)?;
+        Ok(last.path)
+    }
+}
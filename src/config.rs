@@ -1,48 +1,534 @@
 use crate::error::{ProconError, Result};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Config {
     pub template: TemplateConfig,
     pub project: ProjectConfig,
+    #[serde(default)]
+    pub ui: UiConfig,
+    /// Values for custom template variables such as `AUTHOR`, used when `new` is not
+    /// given them with `--define`. They take precedence over a template's own defaults.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub defaults: HashMap<String, String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TemplateConfig {
     pub default: String,
     pub path: PathBuf,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProjectConfig {
     pub cpp_standard: String,
     pub cmake_minimum_version: String,
+    /// Optional nested layout such as `{contest}/{problem}` applied to project names
+    /// of the form `{contest}_{problem}`. Unset keeps projects flat.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_path_layout: Option<String>,
+    /// Optional shell command run inside each newly created project.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_create: Option<String>,
+    /// Optional newest CMake version whose policies the project opts into.
+    /// When set, `{{CMAKE_VERSION}}` renders as `min...max`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cmake_policy_max: Option<String>,
+    /// Extra rules appended to every generated `.gitignore`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub gitignore_extra: Vec<String>,
+    /// C++ compiler the projects are built with, e.g. `g++`, `g++-13` or `clang++`.
+    #[serde(default = "default_compiler")]
+    pub compiler: String,
+    /// Compiler flags substituted, space-joined, for `{{COMPILER_FLAGS}}`.
+    #[serde(default = "default_compiler_flags")]
+    pub compiler_flags: Vec<String>,
+    /// Inject GCC optimization pragmas at `{{PRAGMAS}}` when the compiler is GCC.
+    #[serde(default)]
+    pub gcc_pragmas: bool,
 }
 
-impl Default for Config {
+fn default_compiler() -> String {
+    "g++".to_string()
+}
+
+fn default_compiler_flags() -> Vec<String> {
+    ["-O2", "-Wall", "-DLOCAL"].map(str::to_string).to_vec()
+}
+
+/// Optimization pragmas substituted for `{{PRAGMAS}}` when `project.gcc_pragmas` is on.
+pub const GCC_PRAGMAS: &str = "#pragma GCC optimize(\"O3,unroll-loops\")\n\
+                               #pragma GCC target(\"avx2,bmi,bmi2,popcnt,lzcnt\")\n";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UiConfig {
+    /// Prefix status lines with emoji; when false, ASCII markers such as `[ok]` are used.
+    #[serde(default = "default_true")]
+    pub emoji: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for TemplateConfig {
+    fn default() -> Self {
+        Self {
+            default: "default".to_string(),
+            path: dirs::config_dir()
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join("procon_rs")
+                .join("templates"),
+        }
+    }
+}
+
+impl Default for ProjectConfig {
     fn default() -> Self {
         Self {
-            template: TemplateConfig {
-                default: "default".to_string(),
-                path: dirs::config_dir()
-                    .unwrap_or_else(|| PathBuf::from("."))
-                    .join("procon_rs")
-                    .join("templates"),
-            },
-            project: ProjectConfig {
-                cpp_standard: "17".to_string(),
-                cmake_minimum_version: "3.16".to_string(),
-            },
+            cpp_standard: "17".to_string(),
+            cmake_minimum_version: "3.16".to_string(),
+            default_path_layout: None,
+            on_create: None,
+            cmake_policy_max: None,
+            gitignore_extra: Vec::new(),
+            compiler: default_compiler(),
+            compiler_flags: default_compiler_flags(),
+            gcc_pragmas: false,
         }
     }
 }
 
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self { emoji: true }
+    }
+}
+
+impl ProjectConfig {
+    /// Returns whether the configured compiler is GCC rather than Clang or another compiler.
+    ///
+    /// Only the file name of the last word counts, so wrappers and cross-compiler
+    /// prefixes such as `ccache g++` and `x86_64-linux-gnu-g++-13` are recognized.
+    pub fn uses_gcc(&self) -> bool {
+        let name = self
+            .compiler
+            .split_whitespace()
+            .last()
+            .and_then(|program| Path::new(program).file_name())
+            .map(|name| name.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        !name.contains("clang") && (name.contains("g++") || name.contains("gcc"))
+    }
+
+    /// Renders the value substituted for `{{PRAGMAS}}`.
+    ///
+    /// This is [`GCC_PRAGMAS`] when `gcc_pragmas` is on and the compiler is GCC, and
+    /// empty otherwise; Clang ignores `#pragma GCC optimize`, so nothing is injected for it.
+    pub fn pragmas(&self) -> &'static str {
+        if self.gcc_pragmas && self.uses_gcc() {
+            GCC_PRAGMAS
+        } else {
+            ""
+        }
+    }
+
+    /// Renders the value substituted for `{{CMAKE_VERSION}}`.
+    ///
+    /// This is the minimum version alone, or a `min...max` policy range when
+    /// `cmake_policy_max` is set, as accepted by `cmake_minimum_required`.
+    ///
+    /// # Errors
+    ///
+    /// * `ConfigError` - Either version is malformed or the minimum exceeds the maximum
+    pub fn cmake_version_spec(&self) -> Result<String> {
+        match &self.cmake_policy_max {
+            Some(max) => {
+                check_cmake_range(&self.cmake_minimum_version, max)?;
+                Ok(format!("{}...{}", self.cmake_minimum_version, max))
+            }
+            None => Ok(self.cmake_minimum_version.clone()),
+        }
+    }
+}
+
+/// C++ standards accepted for `project.cpp_standard`.
+pub const SUPPORTED_CPP_STANDARDS: &[&str] = &["11", "14", "17", "20", "23", "26"];
+
+/// Builtin templates that were renamed, as `(old name, new name)` pairs.
+///
+/// Configs referring to an old name are rewritten by [`Config::migrate`]. Add an entry
+/// here whenever a builtin template is renamed.
+pub const RENAMED_BUILTINS: &[(&str, &str)] = &[];
+
+/// Expands a leading `~` in a path to the user's home directory.
+///
+/// Paths without a leading `~`, and `~user` forms, are returned unchanged, as is
+/// everything when the home directory cannot be determined.
+pub fn expand_tilde(path: &Path) -> PathBuf {
+    let Ok(rest) = path.strip_prefix("~") else {
+        return path.to_path_buf();
+    };
+
+    match dirs::home_dir() {
+        Some(home) => home.join(rest),
+        None => path.to_path_buf(),
+    }
+}
+
+/// Returns the directory where procon_rs caches fetched data (`<config dir>/procon_rs/cache`).
+pub fn cache_dir() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("procon_rs")
+        .join("cache")
+}
+
+/// Parses a CMake version of the form `MAJOR.MINOR` or `MAJOR.MINOR.PATCH`.
+///
+/// # Errors
+///
+/// * `ConfigError` - The version has the wrong number of components or a non-numeric one
+///
+/// # Examples
+///
+/// ```
+/// use procon_rs::config::parse_cmake_version;
+///
+/// assert_eq!(parse_cmake_version("3.16").unwrap(), (3, 16, None));
+/// assert_eq!(parse_cmake_version("3.16.3").unwrap(), (3, 16, Some(3)));
+/// assert!(parse_cmake_version("3.x").is_err());
+/// ```
+pub fn parse_cmake_version(version: &str) -> Result<(u32, u32, Option<u32>)> {
+    let invalid = || {
+        ProconError::ConfigError(format!(
+            "Invalid CMake version '{}': expected MAJOR.MINOR or MAJOR.MINOR.PATCH",
+            version
+        ))
+    };
+
+    let parts = version
+        .split('.')
+        .map(|part| {
+            if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(invalid());
+            }
+            part.parse::<u32>().map_err(|_| invalid())
+        })
+        .collect::<Result<Vec<u32>>>()?;
+
+    match parts[..] {
+        [major, minor] => Ok((major, minor, None)),
+        [major, minor, patch] => Ok((major, minor, Some(patch))),
+        _ => Err(invalid()),
+    }
+}
+
+/// Checks that a C++ standard is one of [`SUPPORTED_CPP_STANDARDS`].
+pub(crate) fn check_cpp_standard(standard: &str) -> Result<()> {
+    if !SUPPORTED_CPP_STANDARDS.contains(&standard) {
+        return Err(ProconError::ConfigError(format!(
+            "Unsupported C++ standard '{}': expected one of {}",
+            standard,
+            SUPPORTED_CPP_STANDARDS.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+/// Rejects a config path that is a directory, which would otherwise surface as a
+/// bare "Is a directory" I/O error.
+fn check_not_directory(path: &Path) -> Result<()> {
+    if path.is_dir() {
+        return Err(ProconError::ConfigError(format!(
+            "Config path {} is a directory, not a file",
+            path.display()
+        )));
+    }
+    Ok(())
+}
+
+/// Prefix of the keys addressing [`Config::defaults`], as in `defaults.AUTHOR`.
+pub const DEFAULTS_PREFIX: &str = "defaults.";
+
+/// Checks that a `defaults.<name>` key names a usable `{{NAME}}` placeholder.
+fn check_variable_name(name: &str) -> Result<()> {
+    let mut chars = name.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        return Err(ProconError::ConfigError(format!(
+            "Invalid variable name '{}': expected letters, digits and underscores, not starting with a digit",
+            name
+        )));
+    }
+    Ok(())
+}
+
+/// Parses a boolean configuration value.
+fn parse_bool(key: &str, value: &str) -> Result<bool> {
+    value.parse().map_err(|_| {
+        ProconError::ConfigError(format!(
+            "Invalid value '{}' for {}: expected true or false",
+            value, key
+        ))
+    })
+}
+
+/// Checks that `min...max` is a valid CMake policy range.
+fn check_cmake_range(min: &str, max: &str) -> Result<()> {
+    let (min_major, min_minor, min_patch) = parse_cmake_version(min)?;
+    let (max_major, max_minor, max_patch) = parse_cmake_version(max)?;
+
+    let lower = (min_major, min_minor, min_patch.unwrap_or(0));
+    let upper = (max_major, max_minor, max_patch.unwrap_or(0));
+    if lower > upper {
+        return Err(ProconError::ConfigError(format!(
+            "CMake policy range {}...{} is invalid: minimum is newer than maximum",
+            min, max
+        )));
+    }
+
+    Ok(())
+}
+
 impl Config {
+    /// Every key accepted by [`Config::get`] and [`Config::set`], in display order.
+    pub const KEYS: &'static [&'static str] = &[
+        "template.default",
+        "template.path",
+        "project.cpp_standard",
+        "project.cmake_minimum_version",
+        "project.cmake_policy_max",
+        "project.default_path_layout",
+        "project.on_create",
+        "project.gitignore_extra",
+        "project.compiler",
+        "project.compiler_flags",
+        "project.gcc_pragmas",
+        "ui.emoji",
+    ];
+
+    /// Returns the configured user template directory with `~` expanded.
+    pub fn template_dir(&self) -> PathBuf {
+        expand_tilde(&self.template.path)
+    }
+
+    /// Returns the path of the user's config file (`<config dir>/procon_rs/config.toml`).
+    pub fn default_path() -> PathBuf {
+        dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("procon_rs")
+            .join("config.toml")
+    }
+
+    /// Reads a configuration from a TOML file without validating its values.
+    ///
+    /// # Errors
+    ///
+    /// * `Io` - The file could not be read
+    /// * `TomlParse` - The file is not a valid configuration
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(toml::from_str(&content)?)
+    }
+
+    /// Checks every field of the configuration.
+    ///
+    /// Unlike [`Config::set`], which rejects one bad value at a time, this reports all
+    /// problems at once so a hand-edited file can be fixed in one pass.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidConfig` - One or more fields are invalid, listing each problem
+    pub fn validate(&self) -> Result<()> {
+        let mut problems = Vec::new();
+        let mut report = |error: ProconError| match error {
+            ProconError::ConfigError(message) => problems.push(message),
+            other => problems.push(other.to_string()),
+        };
+
+        if self.template.default.trim().is_empty() {
+            report(ProconError::ConfigError("template.default must not be empty".to_string()));
+        }
+
+        let template_dir = self.template_dir();
+        if template_dir.exists() && !template_dir.is_dir() {
+            report(ProconError::ConfigError(format!(
+                "template.path '{}' is not a directory",
+                template_dir.display()
+            )));
+        }
+
+        if self.project.compiler.trim().is_empty() {
+            report(ProconError::ConfigError("project.compiler must not be empty".to_string()));
+        }
+
+        if let Err(e) = check_cpp_standard(&self.project.cpp_standard) {
+            report(e);
+        }
+
+        match parse_cmake_version(&self.project.cmake_minimum_version) {
+            Err(e) => report(e),
+            Ok(_) => {
+                // The range check re-parses the minimum, so only run it when that is valid
+                if let Some(max) = &self.project.cmake_policy_max
+                    && let Err(e) = check_cmake_range(&self.project.cmake_minimum_version, max)
+                {
+                    report(e);
+                }
+            }
+        }
+
+        if let Some(layout) = &self.project.default_path_layout {
+            if !layout.contains("{problem}") {
+                report(ProconError::ConfigError(format!(
+                    "project.default_path_layout '{}' must contain {{problem}}",
+                    layout
+                )));
+            }
+            if layout.starts_with('/') || layout.split('/').any(|segment| segment == "..") {
+                report(ProconError::ConfigError(format!(
+                    "project.default_path_layout '{}' must stay inside the project directory",
+                    layout
+                )));
+            }
+        }
+
+        let mut default_names: Vec<&String> = self.defaults.keys().collect();
+        default_names.sort();
+        for name in default_names {
+            if let Err(e) = check_variable_name(name) {
+                report(e);
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(ProconError::InvalidConfig(problems))
+        }
+    }
+
+    /// Loads the user's config file (see [`Config::default_path`]).
+    ///
+    /// A missing file yields the default configuration. Renamed builtin templates are
+    /// migrated in memory; use [`Config::load_with_warnings`] to report them.
+    ///
+    /// # Errors
+    ///
+    /// * `TomlParse` - The file exists but is malformed
+    /// * `Io` - The file exists but could not be read
     pub fn load() -> Result<Self> {
-        // For now, just return default config
-        // In a real implementation, this would load from config file
-        Ok(Config::default())
+        Self::load_from(&Self::default_path())
+    }
+
+    /// Loads a config file like [`Config::load`], from an explicit path.
+    ///
+    /// # Errors
+    ///
+    /// * `TomlParse` - The file exists but is malformed
+    /// * `Io` - The file exists but could not be read
+    /// * `ConfigError` - The path is a directory, or the file sets an unsupported
+    ///   `project.cpp_standard`, which would otherwise produce a broken CMakeLists.txt
+    pub fn load_from(path: &Path) -> Result<Self> {
+        Self::load_with_warnings(path).map(|(config, _)| config)
+    }
+
+    /// Loads a config file like [`Config::load_from`], also returning one warning per
+    /// setting [`Config::migrate`] rewrote, for the caller to report.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Config::load_from`].
+    pub fn load_with_warnings(path: &Path) -> Result<(Self, Vec<String>)> {
+        let (config, warnings) = Self::read_and_migrate(path)?;
+        if let Err(ProconError::ConfigError(message)) = check_cpp_standard(&config.project.cpp_standard) {
+            return Err(ProconError::ConfigError(format!("{} (in {})", message, path.display())));
+        }
+        Ok((config, warnings))
+    }
+
+    /// Loads a config file to change it with [`Config::set`] and save it back.
+    ///
+    /// Unlike [`Config::load_from`], an unsupported `project.cpp_standard` is kept,
+    /// so that setting it is how a hand-edited typo gets fixed.
+    ///
+    /// # Errors
+    ///
+    /// * `TomlParse` - The file exists but is malformed
+    /// * `Io` - The file exists but could not be read
+    /// * `ConfigError` - The path is a directory
+    pub fn load_for_edit(path: &Path) -> Result<Self> {
+        Self::read_and_migrate(path).map(|(config, _)| config)
+    }
+
+    /// Reads a config file, or the defaults when it is missing, and migrates it.
+    fn read_and_migrate(path: &Path) -> Result<(Self, Vec<String>)> {
+        check_not_directory(path)?;
+        let mut config = if path.exists() {
+            Self::from_file(path)?
+        } else {
+            Config::default()
+        };
+        let warnings = config.migrate();
+        Ok((config, warnings))
+    }
+
+    /// Writes the configuration to the user's config file, creating its directory.
+    ///
+    /// # Errors
+    ///
+    /// * `TomlSerialize` - The configuration could not be serialized
+    /// * `Io` - The file or its directory could not be written
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&Self::default_path())
+    }
+
+    /// Writes the configuration to an explicit path, creating its parent directory.
+    ///
+    /// # Errors
+    ///
+    /// * `TomlSerialize` - The configuration could not be serialized
+    /// * `Io` - The file or its directory could not be written
+    /// * `ConfigError` - The path is a directory
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        check_not_directory(path)?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, toml::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Rewrites references to renamed builtin templates (see [`RENAMED_BUILTINS`]).
+    ///
+    /// Returns one human-readable warning per rewritten setting.
+    pub fn migrate(&mut self) -> Vec<String> {
+        self.migrate_with(RENAMED_BUILTINS)
+    }
+
+    /// Rewrites template names using the given `(old name, new name)` renames.
+    ///
+    /// Returns one human-readable warning per rewritten setting.
+    pub fn migrate_with(&mut self, renames: &[(&str, &str)]) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        if let Some((old, new)) = renames
+            .iter()
+            .find(|(old, _)| *old == self.template.default)
+        {
+            warnings.push(format!(
+                "Builtin template '{}' was renamed to '{}'; updated template.default",
+                old, new
+            ));
+            self.template.default = new.to_string();
+        }
+
+        warnings
     }
     
     pub fn get(&self, key: &str) -> Option<String> {
@@ -51,7 +537,22 @@ impl Config {
             "template.path" => Some(self.template.path.display().to_string()),
             "project.cpp_standard" => Some(self.project.cpp_standard.clone()),
             "project.cmake_minimum_version" => Some(self.project.cmake_minimum_version.clone()),
-            _ => None,
+            "project.cmake_policy_max" => Some(self.project.cmake_policy_max.clone().unwrap_or_default()),
+            "project.default_path_layout" => {
+                Some(self.project.default_path_layout.clone().unwrap_or_default())
+            }
+            "project.on_create" => Some(self.project.on_create.clone().unwrap_or_default()),
+            "project.gitignore_extra" => Some(self.project.gitignore_extra.join(",")),
+            "project.compiler" => Some(self.project.compiler.clone()),
+            "project.compiler_flags" => Some(self.project.compiler_flags.join(" ")),
+            "project.gcc_pragmas" => Some(self.project.gcc_pragmas.to_string()),
+            "ui.emoji" => Some(self.ui.emoji.to_string()),
+            _ => {
+                // Like other optional keys, an unset default reads as empty
+                let name = key.strip_prefix(DEFAULTS_PREFIX)?;
+                check_variable_name(name).ok()?;
+                Some(self.defaults.get(name).cloned().unwrap_or_default())
+            }
         }
     }
     
@@ -59,10 +560,114 @@ impl Config {
         match key {
             "template.default" => self.template.default = value.to_string(),
             "template.path" => self.template.path = PathBuf::from(value),
-            "project.cpp_standard" => self.project.cpp_standard = value.to_string(),
-            "project.cmake_minimum_version" => self.project.cmake_minimum_version = value.to_string(),
-            _ => return Err(ProconError::ConfigError(format!("Unknown configuration key: {}", key))),
+            "project.cpp_standard" => {
+                check_cpp_standard(value)?;
+                self.project.cpp_standard = value.to_string();
+            }
+            "project.cmake_minimum_version" => {
+                parse_cmake_version(value)?;
+                // Keep an existing policy range valid
+                if let Some(max) = &self.project.cmake_policy_max {
+                    check_cmake_range(value, max)?;
+                }
+                self.project.cmake_minimum_version = value.to_string();
+            }
+            "project.cmake_policy_max" => {
+                if value.is_empty() {
+                    self.project.cmake_policy_max = None;
+                } else {
+                    check_cmake_range(&self.project.cmake_minimum_version, value)?;
+                    self.project.cmake_policy_max = Some(value.to_string());
+                }
+            }
+            "project.default_path_layout" => {
+                // An empty value switches back to flat project directories
+                self.project.default_path_layout =
+                    (!value.is_empty()).then(|| value.to_string());
+            }
+            "project.on_create" => {
+                self.project.on_create = (!value.is_empty()).then(|| value.to_string());
+            }
+            "project.gitignore_extra" => {
+                // Comma-separated so a single value can carry several rules
+                self.project.gitignore_extra = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|rule| !rule.is_empty())
+                    .map(str::to_string)
+                    .collect();
+            }
+            "project.compiler" => self.project.compiler = value.to_string(),
+            "project.compiler_flags" => {
+                // Space-separated like on a command line; an empty value clears them
+                self.project.compiler_flags = value.split_whitespace().map(str::to_string).collect();
+            }
+            "project.gcc_pragmas" => self.project.gcc_pragmas = parse_bool(key, value)?,
+            "ui.emoji" => self.ui.emoji = parse_bool(key, value)?,
+            _ => {
+                let Some(name) = key.strip_prefix(DEFAULTS_PREFIX) else {
+                    return Err(ProconError::ConfigError(format!("Unknown configuration key: {}", key)));
+                };
+                check_variable_name(name)?;
+                // An empty value removes the default again
+                if value.is_empty() {
+                    self.defaults.remove(name);
+                } else {
+                    self.defaults.insert(name.to_string(), value.to_string());
+                }
+            }
         }
         Ok(())
     }
-}
\ No newline at end of file
+    
+    /// Returns the value of a key as a typed JSON value, for machine-readable output.
+    ///
+    /// Unlike [`Config::get`], unset optional keys are reported as `null` rather than
+    /// an empty string. Paths are reported as strings.
+    pub fn get_json(&self, key: &str) -> Option<Value> {
+        let optional = |value: &Option<String>| value.clone().map_or(Value::Null, Value::String);
+
+        match key {
+            "project.cmake_policy_max" => Some(optional(&self.project.cmake_policy_max)),
+            "project.default_path_layout" => Some(optional(&self.project.default_path_layout)),
+            "project.on_create" => Some(optional(&self.project.on_create)),
+            "project.gitignore_extra" => Some(Value::from(self.project.gitignore_extra.clone())),
+            "project.compiler_flags" => Some(Value::from(self.project.compiler_flags.clone())),
+            "project.gcc_pragmas" => Some(Value::Bool(self.project.gcc_pragmas)),
+            "ui.emoji" => Some(Value::Bool(self.ui.emoji)),
+            _ => self.get(key).map(Value::String),
+        }
+    }
+    
+    /// Returns every key with its value as [`Config::get`] reports it, including the
+    /// `defaults.<NAME>` entries, sorted by key.
+    pub fn entries(&self) -> Vec<(String, String)> {
+        let mut entries: Vec<(String, String)> = Self::KEYS
+            .iter()
+            .filter_map(|key| Some((key.to_string(), self.get(key)?)))
+            .chain(
+                self.defaults
+                    .iter()
+                    .map(|(name, value)| (format!("{}{}", DEFAULTS_PREFIX, name), value.clone())),
+            )
+            .collect();
+        entries.sort();
+        entries
+    }
+
+    /// Returns all configuration values as a flat JSON object keyed by dotted key.
+    ///
+    /// Variable defaults appear as `defaults.<NAME>` keys after the fixed ones.
+    pub fn to_json(&self) -> Value {
+        let mut values: serde_json::Map<String, Value> = Self::KEYS
+            .iter()
+            .filter_map(|key| Some((key.to_string(), self.get_json(key)?)))
+            .collect();
+        let mut defaults: Vec<_> = self.defaults.iter().collect();
+        defaults.sort();
+        for (name, value) in defaults {
+            values.insert(format!("{}{}", DEFAULTS_PREFIX, name), Value::String(value.clone()));
+        }
+        Value::Object(values)
+    }
+}
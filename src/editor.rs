@@ -0,0 +1,61 @@
+use crate::error::Result;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Editor tried when neither `EDITOR` nor `VISUAL` is set, if it is on PATH.
+pub const FALLBACK_EDITOR: &str = "code";
+
+/// Returns the command used to open projects, if one can be found.
+///
+/// `EDITOR` is preferred over `VISUAL`; empty values are ignored. Without either,
+/// [`FALLBACK_EDITOR`] is used when it is installed.
+pub fn resolve_editor() -> Option<String> {
+    ["EDITOR", "VISUAL"]
+        .iter()
+        .filter_map(|key| env::var(key).ok())
+        .map(|value| value.trim().to_string())
+        .find(|value| !value.is_empty())
+        .or_else(|| find_on_path(FALLBACK_EDITOR).map(|_| FALLBACK_EDITOR.to_string()))
+}
+
+/// Starts `editor` on `path` without waiting for it to exit.
+///
+/// The editor is run through the platform shell, so values such as `code -n` work,
+/// and its standard streams are detached from this process so scripts calling
+/// `new --open` are never blocked by it.
+///
+/// # Errors
+///
+/// * `Io` - The shell could not be started
+pub fn open(editor: &str, path: &Path) -> Result<()> {
+    let mut shell = if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C").arg(format!("{} \"{}\"", editor, path.display()));
+        shell
+    } else {
+        // The path is passed as `$1` so it never needs quoting
+        let mut shell = Command::new("sh");
+        shell.arg("-c").arg(format!("{} \"$1\"", editor)).arg("procon_rs").arg(path);
+        shell
+    };
+
+    shell
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    Ok(())
+}
+
+/// Looks for an executable named `program` in the directories listed in `PATH`.
+fn find_on_path(program: &str) -> Option<PathBuf> {
+    let candidates: &[String] = if cfg!(windows) {
+        &[format!("{}.cmd", program), format!("{}.exe", program)]
+    } else {
+        &[program.to_string()]
+    };
+    env::split_paths(&env::var_os("PATH")?)
+        .flat_map(|dir| candidates.iter().map(move |name| dir.join(name)))
+        .find(|candidate| candidate.is_file())
+}
@@ -5,29 +5,98 @@ pub enum ProconError {
     #[error("Project '{0}' already exists")]
     ProjectExists(String),
     
+    #[error("Invalid project name '{0}': it must be non-empty, not start with '.', and not contain path separators or any of <>:\"|?*")]
+    InvalidProjectName(String),
+    
     #[error("Project directory not found")]
     ProjectNotFound,
     
     #[error("Template '{0}' not found")]
     TemplateNotFound(String),
     
-    #[error("Template '{0}' not found. Please create it in ~/.config/procon_rs/templates/{0}")]
-    TemplateNotFoundWithHint(String),
+    #[error("Template '{0}' not found (searched: {})", display_paths(.1))]
+    TemplateNotFoundIn(String, Vec<std::path::PathBuf>),
+    
+    #[error("Template '{0}' not found. Create it with `procon_rs template new {0}`, which places it in {}", .1.display())]
+    TemplateNotFoundWithHint(String, std::path::PathBuf),
+    
+    #[error("Template '{0}' already exists (use --force to replace it)")]
+    TemplateExists(String),
+    
+    #[error("Failed to fetch template '{0}': {1}")]
+    TemplateFetchFailed(String, String),
+    
+    #[error("Refusing to write template file '{0}': path escapes the project directory")]
+    UnsafeTemplatePath(String),
+    
+    #[error("Refusing to create symlink '{0}': its target '{1}' is outside the project directory")]
+    UnsafeSymlinkTarget(String, String),
+    
+    #[error("Template file name '{0}' is not a valid file name")]
+    InvalidTemplateFileName(String),
+    
+    #[error("No ledger found in '{0}': only projects created by procon_rs can be removed")]
+    LedgerNotFound(String),
+    
+    #[error("Nothing to undo: no project created by `new` is remembered")]
+    NothingToUndo,
+    
+    #[error("Refusing to undo: {0} changed since it was created:\n{}", bullet_list(.1))]
+    ProjectModified(String, Vec<String>),
     
     #[error("Failed to create project: {0}")]
     ProjectCreationFailed(String),
     
+    #[error("Git error: {0}")]
+    GitFailed(String),
+    
+    #[error("Hook failed: {0}")]
+    HookFailed(String),
+    
     #[error("Configuration error: {0}")]
     ConfigError(String),
     
+    #[error("Invalid configuration:\n{}", bullet_list(.0))]
+    InvalidConfig(Vec<String>),
+    
+    #[error("Invalid template:\n{}", bullet_list(.0))]
+    InvalidTemplate(Vec<String>),
+    
+    #[error("Environment check failed:\n{}", bullet_list(.0))]
+    EnvironmentProblems(Vec<String>),
+    
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
     
     #[error("TOML parse error: {0}")]
     TomlParse(#[from] toml::de::Error),
     
+    #[error("YAML parse error: {0}")]
+    YamlParse(#[from] serde_yaml::Error),
+    
     #[error("TOML serialize error: {0}")]
     TomlSerialize(#[from] toml::ser::Error),
 }
 
-pub type Result<T> = std::result::Result<T, ProconError>;
\ No newline at end of file
+pub type Result<T> = std::result::Result<T, ProconError>;
+
+/// Formats each problem of an aggregate error on its own indented line.
+fn bullet_list(problems: &[String]) -> String {
+    problems
+        .iter()
+        .map(|problem| format!("  - {}", problem))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Formats searched directories as a comma-separated list.
+fn display_paths(paths: &[std::path::PathBuf]) -> String {
+    if paths.is_empty() {
+        return "no template directories".to_string();
+    }
+    paths
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
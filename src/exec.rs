@@ -0,0 +1,89 @@
+use crate::error::Result;
+use std::io::{self, Read, Write};
+use std::process::{Command, ExitStatus, Stdio};
+use std::thread;
+
+/// Output of a finished child process, decoded as text.
+#[derive(Debug)]
+pub struct CapturedOutput {
+    pub status: ExitStatus,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl CapturedOutput {
+    /// Returns whether the process exited successfully.
+    pub fn success(&self) -> bool {
+        self.status.success()
+    }
+}
+
+/// Runs a command to completion and captures stdout and stderr separately.
+///
+/// Both streams are decoded as UTF-8 lossily, so compiler output in a legacy
+/// encoding shows up with replacement characters instead of failing the capture.
+///
+/// # Errors
+///
+/// * `Io` - The process could not be started
+pub fn capture(command: &mut Command) -> Result<CapturedOutput> {
+    let output = command.stdin(Stdio::null()).output()?;
+
+    Ok(CapturedOutput {
+        status: output.status,
+        stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+        stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+    })
+}
+
+/// Runs a command like [`capture`], while also echoing its output to the terminal as it arrives.
+///
+/// Each stream is forwarded to the corresponding stream of this process, which keeps
+/// long-running tools like CMake responsive while still making their output available
+/// to the caller afterwards.
+///
+/// # Errors
+///
+/// * `Io` - The process could not be started or its output could not be read
+pub fn capture_streaming(command: &mut Command) -> Result<CapturedOutput> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let stdout = child.stdout.take().expect("stdout is piped");
+    let stderr = child.stderr.take().expect("stderr is piped");
+
+    // Drain both pipes concurrently so a chatty stream can't block the other
+    let stdout_reader = thread::spawn(move || tee(stdout, io::stdout()));
+    let stderr_reader = thread::spawn(move || tee(stderr, io::stderr()));
+
+    let status = child.wait()?;
+    let stdout = stdout_reader.join().expect("stdout reader panicked")?;
+    let stderr = stderr_reader.join().expect("stderr reader panicked")?;
+
+    Ok(CapturedOutput {
+        status,
+        stdout: String::from_utf8_lossy(&stdout).into_owned(),
+        stderr: String::from_utf8_lossy(&stderr).into_owned(),
+    })
+}
+
+/// Copies everything from `source` into `sink` and returns the bytes that were copied.
+fn tee(mut source: impl Read, mut sink: impl Write) -> io::Result<Vec<u8>> {
+    let mut captured = Vec::new();
+    let mut buffer = [0u8; 8192];
+
+    loop {
+        let read = source.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        captured.extend_from_slice(&buffer[..read]);
+        // Echo failures (e.g. a closed terminal) must not lose the captured output
+        let _ = sink.write_all(&buffer[..read]).and_then(|()| sink.flush());
+    }
+
+    Ok(captured)
+}
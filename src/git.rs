@@ -0,0 +1,77 @@
+use crate::error::{ProconError, Result};
+use crate::exec;
+use std::path::Path;
+use std::process::Command;
+
+/// Returns whether a usable `git` executable is on PATH.
+pub fn is_available() -> bool {
+    exec::capture(Command::new("git").arg("--version"))
+        .map(|output| output.success())
+        .unwrap_or(false)
+}
+
+/// Runs a git subcommand inside `repo_dir`, returning its trimmed stdout.
+///
+/// # Errors
+///
+/// * `GitFailed` - git could not be started or exited unsuccessfully; the message
+///   carries git's stderr
+fn run_git(repo_dir: &Path, args: &[&str]) -> Result<String> {
+    let output = exec::capture(Command::new("git").args(args).current_dir(repo_dir))
+        .map_err(|e| ProconError::GitFailed(format!("could not run git: {}", e)))?;
+
+    if !output.success() {
+        return Err(ProconError::GitFailed(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            output.stderr.trim()
+        )));
+    }
+
+    Ok(output.stdout.trim().to_string())
+}
+
+/// Initializes a git repository in `repo_dir`, optionally on a named initial branch.
+///
+/// The branch is set by pointing HEAD at it rather than with `git init -b`, which
+/// keeps this working on git versions older than 2.28.
+pub fn init_repository(repo_dir: &Path, branch: Option<&str>) -> Result<()> {
+    run_git(repo_dir, &["init", "--quiet"])?;
+
+    if let Some(branch) = branch {
+        let head = format!("refs/heads/{}", branch);
+        run_git(repo_dir, &["symbolic-ref", "HEAD", &head])?;
+    }
+
+    Ok(())
+}
+
+/// Clones the latest commit of `url` into `dest`, which must not exist yet.
+///
+/// # Errors
+///
+/// * `GitFailed` - The repository could not be reached or cloned
+pub fn clone_shallow(url: &str, dest: &Path) -> Result<()> {
+    let parent = dest.parent().unwrap_or(Path::new("."));
+    let dest = dest.to_string_lossy();
+    run_git(parent, &["clone", "--quiet", "--depth", "1", url, &dest])?;
+    Ok(())
+}
+
+/// Stages every file in `repo_dir` and records a commit with `message`.
+///
+/// # Errors
+///
+/// * `GitFailed` - Staging or committing failed, most commonly because no git
+///   identity (`user.name`/`user.email`) is configured
+pub fn commit_all(repo_dir: &Path, message: &str) -> Result<()> {
+    run_git(repo_dir, &["add", "-A"])?;
+    run_git(repo_dir, &["commit", "--quiet", "-m", message]).map_err(|e| match e {
+        ProconError::GitFailed(reason) => ProconError::GitFailed(format!(
+            "{} (is git user.name/user.email configured?)",
+            reason
+        )),
+        other => other,
+    })?;
+    Ok(())
+}
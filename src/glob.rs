@@ -0,0 +1,61 @@
+/// Matches a `/`-separated relative path against a simple glob pattern.
+///
+/// Supported syntax:
+/// * `*` - Any run of characters within one path segment
+/// * `**` - Any run of characters across segments, including none
+/// * `?` - Exactly one character other than `/`
+///
+/// A pattern ending in `/` matches everything below that directory, so `build/`
+/// is shorthand for `build/**`.
+///
+/// # Examples
+///
+/// ```
+/// use procon_rs::glob::glob_match;
+///
+/// assert!(glob_match("tests/*.in", "tests/01.in"));
+/// assert!(!glob_match("tests/*.in", "tests/sub/01.in"));
+/// assert!(glob_match("tests/**", "tests/sub/01.in"));
+/// assert!(glob_match("build/", "build/out.o"));
+/// ```
+pub fn glob_match(pattern: &str, path: &str) -> bool {
+    let pattern: Vec<char> = match pattern.strip_suffix('/') {
+        Some(dir) => format!("{}/**", dir).chars().collect(),
+        None => pattern.chars().collect(),
+    };
+    let path: Vec<char> = path.chars().collect();
+    match_from(&pattern, &path)
+}
+
+fn match_from(pattern: &[char], path: &[char]) -> bool {
+    match pattern.first() {
+        None => path.is_empty(),
+        Some('*') if pattern.get(1) == Some(&'*') => {
+            // `**/` may also match zero directories, so `a/**/b` matches `a/b`
+            let rest = &pattern[2..];
+            if let Some(after_slash) = rest.strip_prefix(&['/'])
+                && match_from(after_slash, path)
+            {
+                return true;
+            }
+            (0..=path.len()).any(|skip| match_from(rest, &path[skip..]))
+        }
+        Some('*') => {
+            let rest = &pattern[1..];
+            for skip in 0..=path.len() {
+                if match_from(rest, &path[skip..]) {
+                    return true;
+                }
+                if path.get(skip) == Some(&'/') {
+                    break;
+                }
+            }
+            false
+        }
+        Some('?') => match path.first() {
+            Some(c) if *c != '/' => match_from(&pattern[1..], &path[1..]),
+            _ => false,
+        },
+        Some(literal) => path.first() == Some(literal) && match_from(&pattern[1..], &path[1..]),
+    }
+}
@@ -1,5 +1,15 @@
 pub mod error;
 pub mod config;
 pub mod template;
+pub mod project;
+pub mod git;
+pub mod editor;
+pub mod exec;
+pub mod suggest;
+pub mod glob;
+pub mod cmake;
+pub mod ui;
+pub mod tree;
+pub mod prompt;
 pub mod commands;
 pub mod cli;
\ No newline at end of file
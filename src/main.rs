@@ -1,67 +1,880 @@
-use clap::Parser;
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser};
 use colored::*;
-use procon_rs::cli::{Cli, Commands};
-use procon_rs::commands::new::{NewCommand, NewCommandArgs};
+use procon_rs::cli::{Cli, Commands, OutputFormat, TemplateCommands};
+use procon_rs::commands::contest::{ContestCommand, ContestCommandArgs};
+use procon_rs::commands::doctor::{DoctorCommand, ToolCheck};
+use procon_rs::commands::init::{InitCommand, InitCommandArgs};
+use procon_rs::commands::new::{NewCommand, NewCommandArgs, VariableSource};
+use procon_rs::commands::remove::RemoveCommand;
+use procon_rs::commands::template::TemplateCommand;
+use procon_rs::commands::rename::RenameCommand;
+use procon_rs::commands::undo::UndoCommand;
+use procon_rs::config::Config;
+use procon_rs::editor;
+use procon_rs::error::ProconError;
+use procon_rs::prompt::Prompter;
+use procon_rs::suggest::did_you_mean;
+use procon_rs::template::TemplateLoader;
+use procon_rs::tree::render_tree;
+use procon_rs::ui::{self, Marker, Verbosity};
+use std::io::{IsTerminal, Read};
+use std::path::{Path, PathBuf};
 
 fn main() {
     let cli = Cli::parse();
+    env_logger::Builder::new()
+        .filter_level(cli.log_level)
+        .format_timestamp(None)
+        .init();
+    let config_path = cli.config.clone().unwrap_or_else(Config::default_path);
+
+    // Decide before reporting the config's warnings so they already use the right markers
+    let no_emoji = cli.no_emoji || ui::emoji_disabled_by_env();
+    ui::set_emoji(!no_emoji);
+    let (config, config_warnings) = Config::load_with_warnings(&config_path).unwrap_or_default();
+    if !no_emoji && !config.ui.emoji {
+        ui::set_emoji(false);
+    }
+
+    let verbosity = Verbosity::from_flags(cli.quiet, cli.verbose);
+    // Reported once here; the commands load the config again without them
+    if verbosity.shows_status() && cli.format == OutputFormat::Text {
+        for warning in &config_warnings {
+            print_warning(warning);
+        }
+    }
 
     let result = match cli.command {
         Commands::New {
             name,
             template,
+            template_dir,
+            refresh,
+            stdin_template,
             path,
+            create_parents,
+            no_hooks,
+            git,
+            git_branch,
+            git_commit,
+            no_samples,
+            no_gitignore,
+            print_template_source,
+            check_name_available,
+            canonicalize,
+            allow_incomplete,
+            defines,
+            force,
+            list_vars,
+            dry_run,
+            tree,
+            open,
         } => {
-            println!(
-                "{} Creating project '{}'...",
-                "✨".bright_yellow(),
-                name.bright_cyan()
-            );
-
             let args = NewCommandArgs {
-                name: name.clone(),
+                name: name.clone().unwrap_or_default(),
                 template,
                 path,
+                no_hooks,
+                git,
+                git_branch,
+                git_commit,
+                no_samples,
+                no_gitignore,
+                canonicalize,
+                allow_incomplete,
+                defines: defines.into_iter().collect(),
+                force,
+                template_dir,
+                create_parents,
+                refresh,
+                cpp_standard: None,
+                stdin_template: None,
+            };
+            let args = if stdin_template { read_stdin_template(args) } else { Ok(args) };
+            let args = match name {
+                Some(_) => args,
+                None => args.and_then(|args| prompt_new_args(args, &config_path)),
             };
 
-            match NewCommand::execute(args) {
-                Ok(()) => {
-                    println!(
-                        "{} Project '{}' created successfully!",
-                        "✅".bright_green(),
-                        name.bright_cyan()
-                    );
-                    Ok(())
+            match args {
+                Ok(args) => {
+                    if check_name_available {
+                        check_name_available_and_exit(&args, &config_path);
+                    }
+                    if list_vars {
+                        list_template_variables(&args, &config_path)
+                    } else if dry_run {
+                        preview_project(&args, tree, &config_path)
+                    } else {
+                        new_project(args, &config_path, print_template_source, open, verbosity, cli.format)
+                    }
                 }
                 Err(e) => Err(e),
             }
         }
 
-        Commands::Init { .. } => {
-            println!("{} Init command not yet implemented", "⚠️".bright_yellow());
-            Ok(())
+        Commands::Contest {
+            name,
+            problems,
+            count,
+            template,
+            path,
+            create_parents,
+        } => {
+            let args = ContestCommandArgs {
+                name,
+                problems,
+                template,
+                path,
+                create_parents,
+            };
+            create_contest(args, count, &config_path, verbosity, cli.format)
         }
 
-        Commands::Config { key, value } => {
+        Commands::Init { force } => init_project(force, &config_path, verbosity, cli.format),
+
+        Commands::Remove { path, yes } => remove_project(path, yes, verbosity),
+
+        Commands::Undo { yes } => undo_project(yes, &config_path, verbosity),
+
+        Commands::Rename { old_dir, new_name } => rename_project(&old_dir, &new_name, verbosity),
+
+        Commands::ListTemplates => list_templates(&config_path),
+
+        Commands::ValidateTemplate { path } => validate_template(&path, verbosity),
+
+        Commands::Template { action } => match action {
+            TemplateCommands::New { name, force } => {
+                create_template(&name, force, &config_path, verbosity, cli.format)
+            }
+            TemplateCommands::Prune { remove } => prune_templates(remove, &config_path, verbosity),
+            TemplateCommands::Show { name, tree } => show_template(&name, tree, &config_path),
+            TemplateCommands::Info { name } => describe_template(&name, &config_path),
+            TemplateCommands::Dir { cache } => {
+                // Plain output so it can be used as `cp -r tpl "$(procon_rs templates dir)"`
+                let dir = if cache {
+                    Ok(procon_rs::config::cache_dir())
+                } else {
+                    Config::load_from(&config_path).map(|config| config.template_dir())
+                };
+                dir.map(|dir| println!("{}", dir.display()))
+            }
+        },
+
+        Commands::Config {
+            validate: Some(file),
+            ..
+        } => validate_config(file, &config_path, verbosity),
+
+        Commands::Config { all: true, json, .. } => {
+            list_config(json || cli.format == OutputFormat::Json, &config_path)
+        }
+
+        Commands::Config { key, value, json, .. } => {
+            // clap requires a key whenever --validate is absent
+            let key = key.unwrap_or_default();
             match value {
-                Some(val) => println!(
-                    "{} Set {} = {}",
-                    "⚙️".bright_blue(),
-                    key.bright_cyan(),
-                    val.bright_green()
-                ),
-                None => println!(
-                    "{} Get {} (not implemented)",
-                    "⚙️".bright_blue(),
-                    key.bright_cyan()
-                ),
+                Some(val) => set_config(&key, &val, &config_path, verbosity),
+                None if json || cli.format == OutputFormat::Json => {
+                    print_config_json(&key, &config_path);
+                    Ok(())
+                }
+                None => get_config(&key, &config_path),
             }
+        }
+
+        Commands::Doctor => check_environment(&config_path),
+
+        Commands::Completions { shell } => {
+            let mut command = Cli::command();
+            let name = command.get_name().to_string();
+            clap_complete::generate(shell, &mut command, name, &mut std::io::stdout());
             Ok(())
         }
     };
 
     if let Err(e) = result {
-        eprintln!("{} {}", "❌".bright_red(), e.to_string().bright_red());
+        if cli.format == OutputFormat::Json {
+            eprintln!("{}", serde_json::json!({ "error": e.to_string() }));
+            std::process::exit(1);
+        }
+        eprintln!("{} {}", Marker::Error.text().bright_red(), e.to_string().bright_red());
+        if let Some(suggestion) = template_suggestion(&e, &config_path) {
+            eprintln!("   Did you mean '{}'?", suggestion.bright_cyan());
+        }
         std::process::exit(1);
     }
 }
+
+/// Reads `main.cpp` for `new --stdin-template` from standard input.
+fn read_stdin_template(mut args: NewCommandArgs) -> Result<NewCommandArgs, ProconError> {
+    let mut main_cpp = String::new();
+    std::io::stdin().read_to_string(&mut main_cpp)?;
+    args.stdin_template = Some(main_cpp);
+    Ok(args)
+}
+
+/// Asks for `new`'s name, template and C++ standard when no name was given.
+///
+/// Only a terminal is prompted; otherwise the name stays a required argument and
+/// this exits with clap's usual error.
+fn prompt_new_args(args: NewCommandArgs, config_path: &Path) -> Result<NewCommandArgs, ProconError> {
+    if !std::io::stdin().is_terminal() {
+        let mut command = Cli::command();
+        command.build();
+        command
+            .find_subcommand_mut("new")
+            .expect("new is a subcommand")
+            .error(
+                ErrorKind::MissingRequiredArgument,
+                "the following required arguments were not provided:\n  <NAME>",
+            )
+            .exit();
+    }
+
+    let config = Config::load_from(config_path)?;
+    let mut prompter = Prompter::new(std::io::stdin().lock(), std::io::stdout());
+    args.prompt(&mut prompter, &config)
+}
+
+/// Creates a project and reports the result, its warnings and the template's post message.
+fn new_project(
+    args: NewCommandArgs,
+    config_path: &Path,
+    print_template_source: bool,
+    open: bool,
+    verbosity: Verbosity,
+    format: OutputFormat,
+) -> Result<(), ProconError> {
+    let config = Config::load_from(config_path)?;
+    if format == OutputFormat::Json {
+        let (name, template) = (args.name.clone(), args.template_name());
+        let mut output = NewCommand::execute_with_config(&args, &config)?;
+        // stderr is reserved for the error object, so these go into the warnings
+        output.warnings.extend(remember_project(&output.project_path, config_path));
+        if open {
+            output.warnings.extend(open_project(&output.project_path));
+        }
+        println!(
+            "{}",
+            serde_json::json!({
+                "name": name,
+                "path": output.project_path.display().to_string(),
+                "template": template,
+                "files": output.files,
+                "warnings": output.warnings,
+                "post_message": output.post_message,
+                "template_source": output.template_source.kind(),
+                "template_path": output.template_source.path().map(|path| path.display().to_string()),
+            })
+        );
+        return Ok(());
+    }
+
+    let name = args.name.clone();
+    if verbosity.shows_status() {
+        println!(
+            "{} Creating project '{}'...",
+            Marker::Create.text().bright_yellow(),
+            name.bright_cyan()
+        );
+    }
+
+    let output = NewCommand::execute_with_config(&args, &config)?;
+    if let Some(warning) = remember_project(&output.project_path, config_path) {
+        print_warning(&warning);
+    }
+    if open && let Some(warning) = open_project(&output.project_path) {
+        print_warning(&warning);
+    }
+    if verbosity.shows_details() {
+        for file in &output.files {
+            println!("   Wrote {}", output.project_path.join(file).display());
+        }
+    }
+    if !verbosity.shows_status() {
+        return Ok(());
+    }
+
+    println!(
+        "{} Project '{}' created successfully!",
+        Marker::Success.text().bright_green(),
+        name.bright_cyan()
+    );
+    println!("   Path: {}", output.project_path.display());
+    if print_template_source || verbosity.shows_details() {
+        println!("{} Template source: {}", Marker::Info.text().bright_blue(), output.template_source);
+    }
+    for warning in &output.warnings {
+        print_warning(warning);
+    }
+    if let Some(message) = output.post_message {
+        println!("{}", message);
+    }
+    Ok(())
+}
+
+/// Opens a new project in the user's editor, returning a hint instead of failing
+/// when no editor is configured or it can't be started.
+fn open_project(project_path: &Path) -> Option<String> {
+    let Some(editor) = editor::resolve_editor() else {
+        return Some("No editor found to open the project; set $EDITOR or $VISUAL".to_string());
+    };
+    editor::open(&editor, project_path)
+        .err()
+        .map(|e| format!("Could not start editor '{}': {}", editor, e))
+}
+
+/// Prints a warning to stderr in the usual text style.
+fn print_warning(warning: &str) {
+    eprintln!("{} {}", Marker::Warning.text().bright_yellow(), warning.bright_yellow());
+}
+
+/// Prints whether a project could be created under the given name as JSON, exiting 0
+/// when it is available and 1 when the target directory already exists.
+fn check_name_available_and_exit(args: &NewCommandArgs, config_path: &Path) -> ! {
+    let project_path = Config::load_from(config_path).and_then(|config| NewCommand::project_path(args, &config));
+    match project_path {
+        Ok(project_path) => {
+            let available = !project_path.exists();
+            println!(
+                "{}",
+                serde_json::json!({
+                    "name": args.name,
+                    "path": project_path.display().to_string(),
+                    "available": available,
+                })
+            );
+            std::process::exit(if available { 0 } else { 1 });
+        }
+        Err(e) => {
+            eprintln!("{}", serde_json::json!({ "error": e.to_string() }));
+            std::process::exit(2);
+        }
+    }
+}
+
+/// Prints each placeholder of the template with where its value would come from.
+fn list_template_variables(args: &NewCommandArgs, config_path: &Path) -> Result<(), ProconError> {
+    let config = Config::load_from(config_path)?;
+    for (name, source) in NewCommand::list_variables(args, &config)? {
+        let source = match source {
+            VariableSource::Builtin => "builtin".to_string(),
+            VariableSource::Defined(value) => format!("defined: {}", value),
+            VariableSource::Configured(value) => format!("config: {}", value),
+            VariableSource::Default(value) => format!("default: {}", value),
+            VariableSource::Undefined => "requires --define".yellow().to_string(),
+        };
+        println!("{} ({})", name.bright_cyan(), source);
+    }
+    Ok(())
+}
+
+/// Prints the files `new` would create, with their sizes, without writing anything.
+fn preview_project(args: &NewCommandArgs, tree: bool, config_path: &Path) -> Result<(), ProconError> {
+    let plan = NewCommand::dry_run(args, &Config::load_from(config_path)?)?;
+    println!(
+        "{} Would create {} (dry run)",
+        Marker::Info.text().bright_blue(),
+        plan.project_path.display()
+    );
+
+    if tree {
+        print!("{}", render_tree(&args.name, &plan.template.file_paths()));
+    } else {
+        for (path, size) in plan.planned_files() {
+            println!("   {} ({} bytes)", path.display(), size);
+        }
+    }
+    println!(
+        "   {} file(s), {} bytes in total",
+        plan.template.file_count(),
+        plan.template.total_bytes()
+    );
+    for missing in &plan.missing_files {
+        eprintln!(
+            "{} {}",
+            Marker::Warning.text().bright_yellow(),
+            format!("Template is missing {}", missing).bright_yellow()
+        );
+    }
+    for warning in &plan.template.warnings {
+        eprintln!("{} {}", Marker::Warning.text().bright_yellow(), warning.bright_yellow());
+    }
+    Ok(())
+}
+
+/// Creates a contest directory with a project per problem and lists them.
+fn create_contest(
+    mut args: ContestCommandArgs,
+    count: Option<usize>,
+    config_path: &Path,
+    verbosity: Verbosity,
+    format: OutputFormat,
+) -> Result<(), ProconError> {
+    if let Some(count) = count {
+        args.problems = ContestCommand::problem_ids(count)?;
+    }
+
+    let output = ContestCommand::execute(&args, &Config::load_from(config_path)?)?;
+    if format == OutputFormat::Json {
+        let problems: Vec<_> = output
+            .problems
+            .iter()
+            .map(|(problem, problem_path)| {
+                serde_json::json!({ "id": problem, "path": problem_path.display().to_string() })
+            })
+            .collect();
+        println!(
+            "{}",
+            serde_json::json!({
+                "name": args.name,
+                "path": output.contest_path.display().to_string(),
+                "problems": problems,
+            })
+        );
+        return Ok(());
+    }
+    if !verbosity.shows_status() {
+        return Ok(());
+    }
+    for (problem, problem_path) in &output.problems {
+        println!("   {} {}", problem.bright_cyan(), problem_path.display());
+    }
+    println!(
+        "{} Contest '{}' created with {} problem(s)",
+        Marker::Success.text().bright_green(),
+        args.name.bright_cyan(),
+        output.problems.len()
+    );
+    println!("   Path: {}", output.contest_path.display());
+    Ok(())
+}
+
+/// Scaffolds the default template into the current directory, keeping existing files
+/// unless forced, and prints the template's post message.
+fn init_project(
+    force: bool,
+    config_path: &Path,
+    verbosity: Verbosity,
+    format: OutputFormat,
+) -> Result<(), ProconError> {
+    let config = Config::load_from(config_path)?;
+    let path = std::env::current_dir()?;
+    let args = InitCommandArgs {
+        path: path.clone(),
+        template: config.template.default.clone(),
+        force,
+    };
+
+    let output = InitCommand::execute(args, config)?;
+    if format == OutputFormat::Json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "name": output.defaults.project_name,
+                "path": path.display().to_string(),
+                "cpp_standard": output.defaults.cpp_standard,
+                "created": output.created,
+                "skipped": output.skipped,
+                "post_message": output.post_message,
+            })
+        );
+        return Ok(());
+    }
+    if verbosity.shows_details() {
+        for created in &output.created {
+            println!("   Wrote {}", created);
+        }
+    }
+    if !verbosity.shows_status() {
+        return Ok(());
+    }
+    for skipped in &output.skipped {
+        println!("{} Kept existing {}", Marker::Skip.text().bright_yellow(), skipped.bright_cyan());
+    }
+    println!(
+        "{} Initialized '{}' (C++{}), {} file(s) created",
+        Marker::Success.text().bright_green(),
+        output.defaults.project_name.bright_cyan(),
+        output.defaults.cpp_standard,
+        output.created.len()
+    );
+    if let Some(message) = output.post_message {
+        println!("{}", message);
+    }
+    Ok(())
+}
+
+/// Remembers a just-created project for `undo`; failing to only returns a warning.
+fn remember_project(project_path: &Path, config_path: &Path) -> Option<String> {
+    UndoCommand::record(&UndoCommand::state_path(config_path), project_path)
+        .err()
+        .map(|e| format!("Could not remember the project for undo: {}", e))
+}
+
+/// Asks a `[y/N]` question on the terminal, printing "Aborted" unless it is confirmed.
+fn confirm(question: &str) -> Result<bool, ProconError> {
+    let mut prompter = Prompter::new(std::io::stdin().lock(), std::io::stdout());
+    let confirmed = prompter.confirm(question)?;
+    if !confirmed {
+        println!("Aborted");
+    }
+    Ok(confirmed)
+}
+
+/// Deletes the project `new` created last after asking for confirmation.
+fn undo_project(yes: bool, config_path: &Path, verbosity: Verbosity) -> Result<(), ProconError> {
+    let state_path = UndoCommand::state_path(config_path);
+    let last = UndoCommand::planned(&state_path)?;
+    if !yes && !confirm(&format!("Delete {} and everything in it?", last.path.display()))? {
+        return Ok(());
+    }
+
+    let project_path = UndoCommand::execute(&state_path)?;
+    if verbosity.shows_status() {
+        println!(
+            "{} Removed {}",
+            Marker::Remove.text().bright_yellow(),
+            project_path.display()
+        );
+    }
+    Ok(())
+}
+
+/// Renames a project directory along with the name in its CMakeLists.txt.
+fn rename_project(old_dir: &Path, new_name: &str, verbosity: Verbosity) -> Result<(), ProconError> {
+    let output = RenameCommand::execute(old_dir, new_name)?;
+    if verbosity.shows_status() {
+        println!(
+            "{} Renamed {} to {}",
+            Marker::Success.text().bright_green(),
+            old_dir.display(),
+            output.project_path.display()
+        );
+        if !output.cmake_rewritten {
+            println!("  CMakeLists.txt didn't mention the old name and was left unchanged");
+        }
+    }
+    Ok(())
+}
+
+/// Removes a project's scaffolded files after asking for confirmation.
+fn remove_project(path: Option<PathBuf>, yes: bool, verbosity: Verbosity) -> Result<(), ProconError> {
+    let project_dir = match path {
+        Some(path) => path,
+        None => std::env::current_dir()?,
+    };
+
+    let ledger = RemoveCommand::planned(&project_dir)?;
+    let question = format!(
+        "Remove {} file(s) created by procon_rs from {}?",
+        ledger.files.len(),
+        project_dir.display()
+    );
+    if !yes && !confirm(&question)? {
+        return Ok(());
+    }
+
+    let report = RemoveCommand::execute(&project_dir)?;
+    if !verbosity.shows_status() {
+        return Ok(());
+    }
+    for file in &report.removed {
+        println!("{} Removed {}", Marker::Remove.text().bright_yellow(), file);
+    }
+    println!(
+        "{} Removed {} file(s), {} already gone",
+        Marker::Success.text().bright_green(),
+        report.removed.len(),
+        report.missing.len()
+    );
+    Ok(())
+}
+
+/// Prints every available template with whether it is builtin or user-provided.
+fn list_templates(config_path: &Path) -> Result<(), ProconError> {
+    let config = Config::load_from(config_path)?;
+    let loader = TemplateLoader::with_search_path(config.template_dir());
+
+    for listing in loader.list_template_sources() {
+        let source = match (&listing.user_dir, listing.builtin) {
+            (Some(_), true) => "user, overrides builtin",
+            (Some(_), false) => "user",
+            (None, _) => "builtin",
+        };
+        println!("{} ({})", listing.name.bright_cyan(), source);
+    }
+    Ok(())
+}
+
+/// Checks a template directory and prints a pass summary; problems are reported as the error.
+fn validate_template(path: &Path, verbosity: Verbosity) -> Result<(), ProconError> {
+    let template = TemplateCommand::check(path)?;
+    if verbosity.shows_status() {
+        println!(
+            "{} Template {} is valid ({} files, {} variables)",
+            Marker::Success.text().bright_green(),
+            path.display(),
+            template.file_paths().len(),
+            template.variables().len()
+        );
+        for warning in &template.warnings {
+            eprintln!("{} {}", Marker::Warning.text().bright_yellow(), warning.bright_yellow());
+        }
+    }
+    Ok(())
+}
+
+/// Creates a user template in the configured template directory.
+fn create_template(
+    name: &str,
+    force: bool,
+    config_path: &Path,
+    verbosity: Verbosity,
+    format: OutputFormat,
+) -> Result<(), ProconError> {
+    let templates_dir = Config::load_from(config_path)?.template_dir();
+    let template_dir = TemplateCommand::create(&templates_dir, name, force)?;
+    if format == OutputFormat::Json {
+        println!(
+            "{}",
+            serde_json::json!({ "name": name, "path": template_dir.display().to_string() })
+        );
+    } else if verbosity.shows_status() {
+        println!(
+            "{} Created template '{}' in {}",
+            Marker::Success.text().bright_green(),
+            name.bright_cyan(),
+            template_dir.display()
+        );
+        println!("   Use it with: procon_rs new <name> --template {}", name);
+    }
+    Ok(())
+}
+
+/// Prints a template's files as a flat list or, with `tree`, as a directory tree.
+fn show_template(name: &str, tree: bool, config_path: &Path) -> Result<(), ProconError> {
+    let (template, _) = TemplateCommand::load(name, &Config::load_from(config_path)?)?;
+    let paths = template.file_paths();
+
+    if tree {
+        print!("{}", render_tree(name, &paths));
+    } else {
+        for path in paths {
+            println!("{}", path);
+        }
+    }
+    Ok(())
+}
+
+/// Prints a template's description, required files, variables and files.
+fn describe_template(name: &str, config_path: &Path) -> Result<(), ProconError> {
+    let info = TemplateCommand::info(name, &Config::load_from(config_path)?)?;
+
+    println!("{} {} ({})", Marker::Info.text().bright_blue(), info.name.bright_cyan(), info.source);
+    println!("   {}", info.description);
+    println!("Required files: {}", info.required_files.join(", "));
+    if info.variables.is_empty() {
+        println!("Variables: none");
+    } else {
+        println!("Variables:");
+        for (variable, default) in &info.variables {
+            match default {
+                Some(default) => println!("   {} (default: {})", variable, default),
+                None => println!("   {}", variable),
+            }
+        }
+    }
+    println!("Files ({}, {} bytes):", info.files.len(), info.total_bytes);
+    for file in &info.files {
+        println!("   {}", file);
+    }
+    Ok(())
+}
+
+/// Validates the user templates and cached template clones and prints which were
+/// kept, reported, or removed.
+fn prune_templates(remove: bool, config_path: &Path, verbosity: Verbosity) -> Result<(), ProconError> {
+    let templates_dir = Config::load_from(config_path)?.template_dir();
+    let mut report = TemplateCommand::prune(&templates_dir, remove)?;
+    let cached = TemplateCommand::prune_cache(&procon_rs::config::cache_dir(), remove)?;
+    report.kept.extend(cached.kept);
+    report.invalid.extend(cached.invalid);
+    if !verbosity.shows_status() {
+        return Ok(());
+    }
+
+    for invalid in &report.invalid {
+        let status = if invalid.removed { "removed" } else { "invalid" };
+        println!(
+            "{} {} ({}): {}",
+            Marker::Prune.text().bright_yellow(),
+            invalid.name.bright_cyan(),
+            status,
+            invalid.reason
+        );
+    }
+
+    let removed = report.invalid.iter().filter(|invalid| invalid.removed).count();
+    println!(
+        "{} Kept {}, invalid {}, removed {}",
+        Marker::Success.text().bright_green(),
+        report.kept.len(),
+        report.invalid.len(),
+        removed
+    );
+    Ok(())
+}
+
+/// Validates a config file, falling back to the defaults when the user has none yet.
+fn validate_config(file: Option<PathBuf>, config_path: &Path, verbosity: Verbosity) -> Result<(), ProconError> {
+    let path = file.clone().unwrap_or_else(|| config_path.to_path_buf());
+    let config = if file.is_none() && !path.exists() {
+        Config::default()
+    } else {
+        Config::from_file(&path)?
+    };
+
+    config.validate()?;
+    if verbosity.shows_status() {
+        println!(
+            "{} Configuration is valid ({})",
+            Marker::Success.text().bright_green(),
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+/// Prints a configuration value, failing for unknown keys.
+fn get_config(key: &str, config_path: &Path) -> Result<(), ProconError> {
+    let config = Config::load_from(config_path)?;
+    let Some(value) = config.get(key) else {
+        return Err(ProconError::ConfigError(format!("Unknown configuration key: {}", key)));
+    };
+
+    println!(
+        "{} {} = {}",
+        Marker::Config.text().bright_blue(),
+        key.bright_cyan(),
+        value.bright_green()
+    );
+    Ok(())
+}
+
+/// Prints every configuration key with its value, sorted by key, as `key = value`
+/// lines or as a single JSON object.
+fn list_config(json: bool, config_path: &Path) -> Result<(), ProconError> {
+    let config = Config::load_from(config_path)?;
+    if json {
+        println!("{}", config.to_json());
+        return Ok(());
+    }
+
+    for (key, value) in config.entries() {
+        println!("{} = {}", key, value);
+    }
+    Ok(())
+}
+
+/// Sets and saves a configuration value, warning about settings that look like typos.
+fn set_config(key: &str, value: &str, config_path: &Path, verbosity: Verbosity) -> Result<(), ProconError> {
+    // Not load_from, which would refuse the very value being fixed
+    let mut config = Config::load_for_edit(config_path)?;
+    config.set(key, value)?;
+    config.save_to(config_path)?;
+    if !verbosity.shows_status() {
+        return Ok(());
+    }
+    println!(
+        "{} Set {} = {}",
+        Marker::Config.text().bright_blue(),
+        key.bright_cyan(),
+        value.bright_green()
+    );
+
+    // Config::set accepts any path so templates can be created afterwards
+    if key == "template.path" && !config.template_dir().is_dir() {
+        eprintln!(
+            "{} {}",
+            Marker::Warning.text().bright_yellow(),
+            format!(
+                "{} does not exist; templates won't be found there until it is created",
+                config.template_dir().display()
+            )
+            .bright_yellow()
+        );
+    }
+    Ok(())
+}
+
+/// Prints a configuration value as a JSON object, or a JSON error object for unknown keys.
+fn print_config_json(key: &str, config_path: &Path) {
+    let value = Config::load_from(config_path).and_then(|config| {
+        config
+            .get_json(key)
+            .ok_or_else(|| ProconError::ConfigError(format!("Unknown configuration key: {}", key)))
+    });
+
+    match value {
+        Ok(value) => println!("{}", serde_json::json!({ key: value })),
+        Err(e) => {
+            eprintln!("{}", serde_json::json!({ "error": e.to_string() }));
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Prints the installed tools and config directory status, failing when a required
+/// tool is missing or the config directory can't be written.
+fn check_environment(config_path: &Path) -> Result<(), ProconError> {
+    // A broken config shouldn't keep the tools from being checked, but it is reported
+    let config = Config::load_from(config_path).unwrap_or_else(|e| {
+        print_warning(&format!("Using the default configuration: {}", e));
+        Config::default()
+    });
+    let report = DoctorCommand::execute(&config, config_path);
+
+    let print_tool = |tool: &ToolCheck, missing_marker: Marker, missing_note: &str| match &tool.version {
+        Some(version) => println!("{} {}: {}", Marker::Success.text().bright_green(), tool.name, version),
+        None => println!("{} {}: not found{}", missing_marker.text(), tool.name, missing_note),
+    };
+    print_tool(&report.cmake, Marker::Error, "");
+    let has_compiler = report.compilers.iter().any(|compiler| compiler.version.is_some());
+    for compiler in &report.compilers {
+        let marker = if has_compiler { Marker::Skip } else { Marker::Error };
+        print_tool(compiler, marker, "");
+    }
+    print_tool(&report.git, Marker::Warning, " (only needed for new --git)");
+    match &report.config_dir_problem {
+        None => println!(
+            "{} config directory: {} is writable",
+            Marker::Success.text().bright_green(),
+            report.config_dir.display()
+        ),
+        Some(problem) => println!("{} {}", Marker::Error.text().bright_red(), problem),
+    }
+
+    let problems = report.problems();
+    if !problems.is_empty() {
+        return Err(ProconError::EnvironmentProblems(problems));
+    }
+    Ok(())
+}
+
+/// Suggests an available template name when an error was caused by a likely typo.
+fn template_suggestion(error: &ProconError, config_path: &Path) -> Option<String> {
+    let name = match error {
+        ProconError::TemplateNotFound(name)
+        | ProconError::TemplateNotFoundIn(name, _)
+        | ProconError::TemplateNotFoundWithHint(name, _) => name,
+        _ => return None,
+    };
+
+    // Suggestions from the default configuration could name templates that don't apply
+    let config = Config::load_from(config_path).ok()?;
+    let templates = TemplateLoader::with_search_path(config.template_dir()).list_templates();
+    did_you_mean(name, templates.iter().map(String::as_str)).map(str::to_string)
+}
@@ -0,0 +1,173 @@
+use crate::error::{ProconError, Result};
+use crate::exec;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Directory inside a generated project holding procon_rs bookkeeping files.
+pub const PROJECT_STATE_DIR: &str = ".procon";
+
+/// File recording how a project was created, relative to the project root.
+pub const CREATED_RECORD_FILE: &str = "created.toml";
+
+/// File listing the files procon_rs created, relative to the project state directory.
+pub const LEDGER_FILE: &str = "ledger.json";
+
+/// Record of how a project was scaffolded, written at creation time.
+///
+/// Nothing reads it back yet; it is kept so a later command operating on an existing
+/// project can pick up its template without the user repeating the name.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreatedRecord {
+    pub template: String,
+}
+
+impl CreatedRecord {
+    pub fn new(template: &str) -> Self {
+        Self {
+            template: template.to_string(),
+        }
+    }
+
+    /// Returns the path of the record file for the given project directory.
+    pub fn path_in(project_dir: &Path) -> PathBuf {
+        project_dir.join(PROJECT_STATE_DIR).join(CREATED_RECORD_FILE)
+    }
+
+    /// Writes the record to `<project_dir>/.procon/created.toml`.
+    pub fn write(&self, project_dir: &Path) -> Result<()> {
+        let record_path = Self::path_in(project_dir);
+        if let Some(parent) = record_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(record_path, toml::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// List of the files procon_rs wrote into a project, used by `remove` to undo scaffolding.
+///
+/// Paths are `/`-separated and relative to the project root. Files the user adds later
+/// are never listed, so removing a project leaves them in place.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Ledger {
+    pub files: Vec<String>,
+}
+
+impl Ledger {
+    /// Creates a ledger listing the given files, sorted.
+    pub fn new(mut files: Vec<String>) -> Self {
+        files.sort();
+        Self { files }
+    }
+
+    /// Returns the path of the ledger file for the given project directory.
+    pub fn path_in(project_dir: &Path) -> PathBuf {
+        project_dir.join(PROJECT_STATE_DIR).join(LEDGER_FILE)
+    }
+
+    /// Writes the ledger to `<project_dir>/.procon/ledger.json`.
+    pub fn write(&self, project_dir: &Path) -> Result<()> {
+        let ledger_path = Self::path_in(project_dir);
+        if let Some(parent) = ledger_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| ProconError::ProjectCreationFailed(e.to_string()))?;
+        fs::write(ledger_path, json)?;
+        Ok(())
+    }
+
+    /// Reads the ledger from a project directory.
+    ///
+    /// Returns `Ok(None)` when the project has no ledger.
+    ///
+    /// # Errors
+    ///
+    /// * `ConfigError` - The ledger exists but is malformed
+    /// * `Io` - The ledger exists but could not be read
+    pub fn read(project_dir: &Path) -> Result<Option<Self>> {
+        let ledger_path = Self::path_in(project_dir);
+        if !ledger_path.is_file() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&ledger_path)?;
+        serde_json::from_str(&content).map(Some).map_err(|e| {
+            ProconError::ConfigError(format!("Malformed {}: {}", ledger_path.display(), e))
+        })
+    }
+}
+
+/// Derives a nested project directory from a name using a configured layout.
+///
+/// Names of the form `{contest}_{problem}` are split at their last underscore, where
+/// the contest part must end in a digit (`abc300_a`, `codeforces_1234_b`). The parts
+/// are substituted into `layout`, whose `/` separators become nested directories, so
+/// `abc300_a` with `{contest}/{problem}` yields `abc300/a`.
+///
+/// Returns `None` when the name doesn't follow the convention, in which case the
+/// project should be created as a flat directory named after it.
+pub fn nested_project_dir(layout: &str, name: &str) -> Option<PathBuf> {
+    let pattern = Regex::new(r"^(?P<contest>[A-Za-z0-9_-]*[0-9])_(?P<problem>[A-Za-z0-9]+)$")
+        .expect("contest name pattern is valid");
+    let captures = pattern.captures(name)?;
+
+    let expanded = layout
+        .replace("{contest}", &captures["contest"])
+        .replace("{problem}", &captures["problem"]);
+
+    Some(
+        expanded
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect(),
+    )
+}
+
+/// Runs the configured `project.on_create` command for a freshly created project.
+///
+/// The command is run through the platform shell (`sh -c` or `cmd /C`) from inside
+/// the project directory, with these variables in its environment:
+///
+/// * `PROCON_PROJECT_PATH` - Absolute or caller-relative path of the project
+/// * `PROCON_PROJECT_NAME` - The project name as given on the command line
+/// * `PROCON_JUDGE_URL` - The problem's judge URL; always empty for now, since no
+///   command knows which judge a project belongs to
+///
+/// # Errors
+///
+/// * `HookFailed` - The command could not be started or exited unsuccessfully
+pub fn run_on_create_hook(command: &str, project_path: &Path, project_name: &str) -> Result<()> {
+    let mut shell = if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C");
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+
+    shell
+        .arg(command)
+        .current_dir(project_path)
+        .env("PROCON_PROJECT_PATH", project_path)
+        .env("PROCON_PROJECT_NAME", project_name)
+        .env("PROCON_JUDGE_URL", "");
+    let output = exec::capture(&mut shell)
+        .map_err(|e| ProconError::HookFailed(format!("'{}': {}", command, e)))?;
+
+    if !output.success() {
+        return Err(ProconError::HookFailed(format!(
+            "'{}' exited with {}: {}",
+            command,
+            output.status,
+            output.stderr.trim()
+        )));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use crate::error::Result;
+use std::io::{self, BufRead, Write};
+
+/// Asks questions on `output` and reads the answers from `input`, one line each.
+///
+/// Interactive commands use it with stdin and stdout; tests feed it scripted input.
+pub struct Prompter<R, W> {
+    input: R,
+    output: W,
+}
+
+impl<R: BufRead, W: Write> Prompter<R, W> {
+    pub fn new(input: R, output: W) -> Self {
+        Self { input, output }
+    }
+
+    /// Writes a line of text, such as the choices for the next question.
+    pub fn say(&mut self, text: &str) -> Result<()> {
+        writeln!(self.output, "{}", text)?;
+        Ok(())
+    }
+
+    /// Asks `question` and returns the trimmed answer, or `default` when the answer
+    /// is empty. The default, if any, is shown in brackets.
+    ///
+    /// # Errors
+    ///
+    /// * `Io` - The input could not be read, or ended before an answer was given
+    pub fn ask(&mut self, question: &str, default: Option<&str>) -> Result<String> {
+        match default {
+            Some(default) => write!(self.output, "{} [{}]: ", question, default)?,
+            None => write!(self.output, "{}: ", question)?,
+        }
+        self.output.flush()?;
+
+        let mut answer = String::new();
+        if self.input.read_line(&mut answer)? == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "input ended before every question was answered",
+            )
+            .into());
+        }
+        let answer = answer.trim();
+        Ok(match default {
+            Some(default) if answer.is_empty() => default.to_string(),
+            _ => answer.to_string(),
+        })
+    }
+
+    /// Asks a yes/no `question` with a `[y/N]` suffix. Only `y`, `Y` and `yes` confirm;
+    /// anything else, including ended input, is a no.
+    ///
+    /// # Errors
+    ///
+    /// * `Io` - The input could not be read
+    pub fn confirm(&mut self, question: &str) -> Result<bool> {
+        write!(self.output, "{} [y/N] ", question)?;
+        self.output.flush()?;
+
+        let mut answer = String::new();
+        self.input.read_line(&mut answer)?;
+        Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+    }
+
+    /// Asks `question` until `check` accepts the answer, showing each rejection.
+    ///
+    /// # Errors
+    ///
+    /// * `Io` - The input could not be read, or ended before an accepted answer
+    pub fn ask_until_valid(
+        &mut self,
+        question: &str,
+        default: Option<&str>,
+        check: impl Fn(&str) -> Result<()>,
+    ) -> Result<String> {
+        loop {
+            let answer = self.ask(question, default)?;
+            match check(&answer) {
+                Ok(()) => return Ok(answer),
+                Err(e) => self.say(&e.to_string())?,
+            }
+        }
+    }
+}
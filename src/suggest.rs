@@ -0,0 +1,46 @@
+/// Maximum edit distance at which a candidate is still offered as a suggestion.
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+/// Computes the Levenshtein edit distance between two strings, counted in characters.
+///
+/// # Examples
+///
+/// ```
+/// use procon_rs::suggest::levenshtein;
+///
+/// assert_eq!(levenshtein("defualt", "default"), 2);
+/// ```
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b_chars: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b_chars.len()).collect();
+    let mut current = vec![0; b_chars.len() + 1];
+
+    for (i, a_char) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b_chars.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b_chars.len()]
+}
+
+/// Picks the candidate closest to `input`, if any is close enough to be a likely typo.
+///
+/// Exact matches are not suggested, and ties resolve to the first candidate given.
+pub fn did_you_mean<'a, I>(input: &str, candidates: I) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    candidates
+        .into_iter()
+        .filter(|candidate| *candidate != input)
+        .map(|candidate| (levenshtein(input, candidate), candidate))
+        .filter(|(distance, candidate)| {
+            *distance <= MAX_SUGGESTION_DISTANCE && *distance < candidate.chars().count()
+        })
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
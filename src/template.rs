@@ -1,45 +1,355 @@
 use crate::error::{ProconError, Result};
-use std::collections::HashMap;
+use crate::glob::glob_match;
+use serde::{Deserialize, Serialize};
+use regex::Regex;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
+use std::sync::LazyLock;
 
 // Embedded template content
-const DEFAULT_MAIN_CPP: &str = include_str!("../templates/default/main.cpp");
-const DEFAULT_CMAKE: &str = include_str!("../templates/default/CMakeLists.txt");
+const DEFAULT_FILES: &[(&str, &str)] = &[
+    ("main.cpp", include_str!("../templates/default/main.cpp")),
+    ("CMakeLists.txt", include_str!("../templates/default/CMakeLists.txt")),
+    (".gitignore", include_str!("../templates/default/.gitignore")),
+];
+const ADVANCED_FILES: &[(&str, &str)] = &[
+    ("main.cpp", include_str!("../templates/advanced/main.cpp")),
+    ("CMakeLists.txt", include_str!("../templates/advanced/CMakeLists.txt")),
+    (".gitignore", include_str!("../templates/advanced/.gitignore")),
+    ("lib/debug.hpp", include_str!("../templates/advanced/lib/debug.hpp")),
+    ("tests/sample_1.in", include_str!("../templates/advanced/tests/sample_1.in")),
+    ("tests/sample_1.out", include_str!("../templates/advanced/tests/sample_1.out")),
+];
+
+const GITIGNORE_FILE: &str = ".gitignore";
+
+/// Names of the templates compiled into the binary.
+pub const BUILTIN_TEMPLATES: &[&str] = &["advanced", "default"];
+
+/// Files every template directory must contain, relative to its root.
+pub const REQUIRED_FILES: &[&str] = &["main.cpp", "CMakeLists.txt"];
+
+/// Variables `new` substitutes without needing a manifest default or `--define`.
+pub const BUILTIN_VARIABLES: &[&str] = &[
+    "PROJECT_NAME",
+    "PROJECT_NAME_UPPER",
+    "PROJECT_NAME_SNAKE",
+    "PROJECT_NAME_CAMEL",
+    "CMAKE_VERSION",
+    "CPP_STANDARD",
+    "DATE",
+    "YEAR",
+    "PRAGMAS",
+    "COMPILER_FLAGS",
+];
+
+/// A `{{NAME}}` or `{{NAME:fallback}}` placeholder; the fallback ends at the first `}}`.
+static PLACEHOLDER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\{\{([A-Za-z_][A-Za-z0-9_]*)(?::(.*?))?\}\}").expect("placeholder pattern is valid")
+});
+
+/// File name of the optional manifest at the root of a template directory.
+pub const MANIFEST_FILE: &str = "template.toml";
+
+/// File names of a YAML manifest, read when there is no [`MANIFEST_FILE`].
+pub const YAML_MANIFEST_FILES: &[&str] = &["template.yaml", "template.yml"];
+
+/// Returns whether a root-level file name is one of the manifest file names.
+fn is_manifest_file(name: &str) -> bool {
+    name == MANIFEST_FILE || YAML_MANIFEST_FILES.contains(&name)
+}
+
+/// File name of the optional ignore list at the root of a template directory.
+///
+/// Each non-empty line not starting with `#` is a glob pattern (see
+/// [`glob_match`]); matching files and directories are not loaded. A pattern
+/// without a `/` matches a name at any depth, like in `.gitignore`.
+pub const IGNORE_FILE: &str = ".procon_rsignore";
+
+/// How many leading bytes of a file are inspected when deciding whether it is binary.
+const BINARY_SNIFF_LEN: usize = 1024;
+
+/// Where [`Template::load_directory_recursively`] collects what it finds.
+struct LoadedEntries<'a> {
+    files: &'a mut HashMap<String, String>,
+    binary_files: &'a mut HashMap<String, Vec<u8>>,
+    symlinks: &'a mut HashMap<String, PathBuf>,
+    warnings: &'a mut Vec<String>,
+}
 
 #[derive(Debug, Clone)]
 pub struct Template {
     pub files: HashMap<String, String>,
+    /// Files copied byte-for-byte, without variable substitution.
+    pub binary_files: HashMap<String, Vec<u8>>,
+    /// Symbolic links, by relative path, with the target they point to. They are
+    /// recreated as links on Unix, target unchanged, as long as the target stays
+    /// inside the project.
+    pub symlinks: HashMap<String, PathBuf>,
+    pub manifest: Option<TemplateManifest>,
+    /// Problems noticed while loading that didn't stop it, such as a manifest
+    /// shadowing another, for the caller to report.
+    pub warnings: Vec<String>,
+}
+
+/// Optional metadata declared by a template in its `template.toml`.
+///
+/// The manifest itself is never copied into generated projects.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TemplateManifest {
+    /// Message shown to the user after a project has been created from the template.
+    /// Template variables are substituted in it like in any template file.
+    #[serde(default)]
+    pub post_message: Option<String>,
+    /// Glob patterns marking example test files (e.g. `tests/*.in`), which users
+    /// can leave out with `new --no-samples`.
+    #[serde(default)]
+    pub samples: Vec<String>,
+    /// Human-readable summary of what the template is for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Files the template directory must contain, replacing the default
+    /// `main.cpp` and `CMakeLists.txt` when declared.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required_files: Option<Vec<String>>,
+    /// Custom `{{NAME}}` variables with their default values.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub variables: BTreeMap<String, String>,
+    /// Files included only when a variable is set, as glob pattern (a trailing `/`
+    /// matches a whole directory) to variable name. A variable that is undefined,
+    /// empty, `0`, `false`, `no` or `off` leaves the matching files out.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub conditional_files: BTreeMap<String, String>,
+    /// Glob patterns of files whose content is copied verbatim, for files that
+    /// legitimately contain `{{...}}`. Their names are still substituted, and
+    /// required files are always substituted.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub no_substitute: Vec<String>,
+    /// Commands run by `new` in the created project.
+    #[serde(default)]
+    pub hooks: TemplateHooks,
+    /// Template whose files are inherited, with this template's files overriding
+    /// or adding to them. Resolved next to the template directory, then among the builtins.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extends: Option<String>,
+}
+
+/// The `[hooks]` table of a template manifest.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TemplateHooks {
+    /// Shell commands run in order inside the new project after its files are written,
+    /// e.g. `cmake -B build`. Template variables are substituted in them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub post_create: Vec<String>,
+}
+
+/// Where a template used for a project was loaded from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateSource {
+    /// Embedded in the binary.
+    Builtin,
+    /// A directory in the user template directory.
+    User(PathBuf),
+    /// The repository's `templates/` directory, used when running from a checkout.
+    Dev(PathBuf),
+    /// A git repository by URL, cloned into the given directory of the template cache
+    /// (see [`cache_dir`](crate::config::cache_dir)) and reused by later runs.
+    Git(String, PathBuf),
+    /// A directory given explicitly with `new --template-dir`.
+    Directory(PathBuf),
+    /// A `main.cpp` read from standard input with `new --stdin-template`.
+    Stdin,
+}
+
+impl TemplateSource {
+    /// Short machine-friendly name of the source kind (`builtin`, `user`, `dev`, `git`,
+    /// `dir` or `stdin`).
+    pub fn kind(&self) -> &'static str {
+        match self {
+            TemplateSource::Builtin => "builtin",
+            TemplateSource::User(_) => "user",
+            TemplateSource::Dev(_) => "dev",
+            TemplateSource::Git(..) => "git",
+            TemplateSource::Directory(_) => "dir",
+            TemplateSource::Stdin => "stdin",
+        }
+    }
+
+    /// The directory the template was read from, if it came from disk. For a git
+    /// template, this is its cached clone.
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            TemplateSource::Builtin | TemplateSource::Stdin => None,
+            TemplateSource::User(path)
+            | TemplateSource::Dev(path)
+            | TemplateSource::Directory(path)
+            | TemplateSource::Git(_, path) => Some(path),
+        }
+    }
+}
+
+impl std::fmt::Display for TemplateSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self, self.path()) {
+            (TemplateSource::Git(url, _), _) => write!(f, "{} ({})", self.kind(), url),
+            (_, Some(path)) => write!(f, "{} ({})", self.kind(), path.display()),
+            (_, None) => write!(f, "{}", self.kind()),
+        }
+    }
 }
 
-pub struct TemplateLoader;
+/// Environment variable listing extra template directories, searched before the
+/// configured one. Entries are separated like `PATH` (`:` on Unix, `;` on Windows).
+pub const TEMPLATE_PATH_ENV: &str = "PROCON_TEMPLATE_PATH";
+
+/// Finds templates in an ordered list of user template directories.
+pub struct TemplateLoader {
+    roots: Vec<PathBuf>,
+}
+
+/// A template name available to `new`, and where it is provided.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateListing {
+    pub name: String,
+    /// Whether a builtin template has this name.
+    pub builtin: bool,
+    /// The user template directory with this name, which takes precedence over a builtin.
+    pub user_dir: Option<PathBuf>,
+}
+
+impl Default for TemplateLoader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl TemplateLoader {
+    /// Creates a loader for the default user template directory.
     pub fn new() -> Self {
-        Self
+        Self {
+            roots: Self::user_template_dir().into_iter().collect(),
+        }
+    }
+
+    /// Creates a loader for a specific user template directory, such as the
+    /// configured `template.path`.
+    pub fn with_dir(template_dir: PathBuf) -> Self {
+        Self::with_roots(vec![template_dir])
+    }
+
+    /// Creates a loader searching `roots` in order; the first match wins.
+    pub fn with_roots(roots: Vec<PathBuf>) -> Self {
+        Self { roots }
+    }
+
+    /// Creates a loader with the full search path: the directories in
+    /// [`TEMPLATE_PATH_ENV`], then `template_dir` (the configured `template.path`),
+    /// then the default user template directory. Duplicates are searched once.
+    pub fn with_search_path(template_dir: PathBuf) -> Self {
+        let from_env: Vec<PathBuf> = std::env::var_os(TEMPLATE_PATH_ENV)
+            .map(|paths| std::env::split_paths(&paths).filter(|path| !path.as_os_str().is_empty()).collect())
+            .unwrap_or_default();
+
+        let mut roots: Vec<PathBuf> = Vec::new();
+        for root in from_env.into_iter().chain([template_dir]).chain(Self::user_template_dir()) {
+            if !roots.contains(&root) {
+                roots.push(root);
+            }
+        }
+        Self::with_roots(roots)
+    }
+
+    /// Returns the directory holding user templates (`<config dir>/procon_rs/templates`).
+    pub fn user_template_dir() -> Option<PathBuf> {
+        dirs::config_dir().map(|config_dir| config_dir.join("procon_rs").join("templates"))
     }
 
+    /// Returns the directories searched for templates, in order.
+    pub fn roots(&self) -> &[PathBuf] {
+        &self.roots
+    }
+
+    /// Returns the directory of the first root containing a template named `name`.
+    ///
+    /// # Errors
+    ///
+    /// * `TemplateNotFoundIn` - No root has the template; the error lists every root
+    ///   searched. Builtin templates are not considered here, so callers fall back to them.
     pub fn find_template(&self, name: &str) -> Result<PathBuf> {
-        // First, check user config directory
-        if let Some(config_dir) = dirs::config_dir() {
-            let user_template_path = config_dir
-                .join("procon_rs")
-                .join("templates")
-                .join(name);
-            
-            if user_template_path.exists() {
-                return Ok(user_template_path);
+        for root in &self.roots {
+            let template_path = root.join(name);
+            if template_path.exists() {
+                log::debug!("Template '{}' found at {}", name, template_path.display());
+                return Ok(template_path);
             }
+            log::debug!("Template '{}' not in {}", name, root.display());
         }
-        
-        // For builtin templates, we need to return an error since they don't exist
-        // The caller should handle the fallback
-        Err(ProconError::TemplateNotFound(name.to_string()))
+        Err(ProconError::TemplateNotFoundIn(name.to_string(), self.roots.clone()))
+    }
+
+    /// Lists the names of all available templates, builtin and user-provided.
+    ///
+    /// The result is sorted and contains each name once, even when a user template
+    /// shadows a builtin. See [`TemplateLoader::list_template_sources`].
+    pub fn list_templates(&self) -> Vec<String> {
+        self.list_template_sources()
+            .into_iter()
+            .map(|listing| listing.name)
+            .collect()
+    }
+
+    /// Lists all available templates along with where each is provided.
+    ///
+    /// User templates are the directories in the search roots that contain the
+    /// required `main.cpp` and `CMakeLists.txt`, or a manifest. The result is sorted
+    /// by name and has one entry per name; a user template shadowing a builtin is
+    /// reported once, with both `builtin` and `user_dir` set.
+    pub fn list_template_sources(&self) -> Vec<TemplateListing> {
+        let mut listings: Vec<TemplateListing> = BUILTIN_TEMPLATES
+            .iter()
+            .map(|name| TemplateListing {
+                name: name.to_string(),
+                builtin: true,
+                user_dir: None,
+            })
+            .collect();
+
+        for entries in self.roots.iter().filter_map(|root| fs::read_dir(root).ok()) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                // Templates with a manifest may declare other required files
+                let has_required_files =
+                    REQUIRED_FILES.iter().all(|file_name| path.join(file_name).is_file());
+                let has_manifest = [MANIFEST_FILE]
+                    .iter()
+                    .chain(YAML_MANIFEST_FILES)
+                    .any(|file_name| path.join(file_name).is_file());
+                if !has_required_files && !has_manifest {
+                    continue;
+                }
+
+                // Earlier roots shadow later ones, like in find_template
+                let name = entry.file_name().to_string_lossy().into_owned();
+                match listings.iter_mut().find(|listing| listing.name == name) {
+                    Some(listing) if listing.user_dir.is_some() => {}
+                    Some(builtin) => builtin.user_dir = Some(path),
+                    None => listings.push(TemplateListing {
+                        name,
+                        builtin: false,
+                        user_dir: Some(path),
+                    }),
+                }
+            }
+        }
+
+        listings.sort_by(|a, b| a.name.cmp(&b.name));
+        listings
     }
 }
 
 impl Template {
-    /// Creates a template from embedded content strings.
+    /// Creates a template from embedded `(relative path, content)` pairs.
     /// 
     /// This method allows creating templates from compile-time embedded strings,
     /// enabling built-in templates to be included in the binary without requiring
@@ -48,9 +358,7 @@ impl Template {
     /// 
     /// # Arguments
     /// 
-    /// * `_name` - The template name (currently unused but kept for future extensibility)
-    /// * `main_cpp_content` - Content for the main.cpp file
-    /// * `cmake_content` - Content for the CMakeLists.txt file
+    /// * `files` - Relative paths, which may include subdirectories, and their contents
     /// 
     /// # Returns
     /// 
@@ -61,16 +369,115 @@ impl Template {
     /// ```
     /// use procon_rs::template::Template;
     /// 
-    /// let main_cpp = "#include <iostream>\nint main() { return 0; }";
-    /// let cmake = "project({{PROJECT_NAME}})";
-    /// let template = Template::from_embedded_content("default", main_cpp, cmake);
+    /// let template = Template::from_embedded_content(&[
+    ///     ("main.cpp", "#include <iostream>\nint main() { return 0; }"),
+    ///     ("CMakeLists.txt", "project({{PROJECT_NAME}})"),
+    ///     ("lib/io.hpp", "#pragma once"),
+    /// ]);
+    /// assert!(template.files.contains_key("lib/io.hpp"));
+    /// ```
+    pub fn from_embedded_content(files: &[(&str, &str)]) -> Self {
+        Self {
+            files: files
+                .iter()
+                .map(|(path, content)| (path.to_string(), content.to_string()))
+                .collect(),
+            binary_files: HashMap::new(),
+            symlinks: HashMap::new(),
+            manifest: None,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Creates a template containing only `main.cpp` and `CMakeLists.txt`.
+    /// 
+    /// Shorthand for [`Template::from_embedded_content`] with those two files.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `main_cpp_content` - Content for the main.cpp file
+    /// * `cmake_content` - Content for the CMakeLists.txt file
+    pub fn from_main_and_cmake(main_cpp_content: &str, cmake_content: &str) -> Self {
+        Self::from_embedded_content(&[
+            ("main.cpp", main_cpp_content),
+            ("CMakeLists.txt", cmake_content),
+        ])
+    }
+
+    /// Creates a template from in-memory `(path, content)` pairs without touching the filesystem.
+    /// 
+    /// This is the programmatic counterpart of [`Template::load_from_path`]: contents are
+    /// sorted into text and binary files the same way, and an entry named
+    /// [`MANIFEST_FILE`] (or, without one, one of [`YAML_MANIFEST_FILES`]) is parsed as
+    /// the manifest instead of becoming a file. Unlike
+    /// loading from disk, `main.cpp` and `CMakeLists.txt` are not required, so partial
+    /// templates can be built for tests or merged into others.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `entries` - Pairs of `/`-separated relative path and raw file content
+    /// 
+    /// # Errors
+    /// 
+    /// * `InvalidTemplateFileName` - A path is not a valid relative file name
+    /// * `TomlParse` - The manifest entry is not valid UTF-8 TOML
+    /// * `YamlParse` - The YAML manifest entry is not valid YAML
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use procon_rs::template::Template;
+    /// 
+    /// let template = Template::from_files([
+    ///     ("main.cpp".to_string(), b"// {{PROJECT_NAME}}\n".to_vec()),
+    ///     ("data/blob.bin".to_string(), vec![0, 1, 2]),
+    /// ])
+    /// .unwrap();
+    /// assert!(template.files.contains_key("main.cpp"));
+    /// assert!(template.binary_files.contains_key("data/blob.bin"));
     /// ```
-    pub fn from_embedded_content(_name: &str, main_cpp_content: &str, cmake_content: &str) -> Self {
+    pub fn from_files(entries: impl IntoIterator<Item = (String, Vec<u8>)>) -> Result<Self> {
         let mut files = HashMap::new();
-        files.insert("main.cpp".to_string(), main_cpp_content.to_string());
-        files.insert("CMakeLists.txt".to_string(), cmake_content.to_string());
-        
-        Self { files }
+        let mut binary_files = HashMap::new();
+        let mut manifest = None;
+        let mut yaml_manifest = None;
+
+        for (path, bytes) in entries {
+            validate_file_name(&path)?;
+
+            if path == MANIFEST_FILE {
+                let content = String::from_utf8_lossy(&bytes);
+                manifest = Some(toml::from_str(&content)?);
+                continue;
+            }
+            if YAML_MANIFEST_FILES.contains(&path.as_str()) {
+                let content = String::from_utf8_lossy(&bytes);
+                yaml_manifest = Some(serde_yaml::from_str(&content)?);
+                continue;
+            }
+
+            if is_binary(&bytes) {
+                binary_files.insert(path, bytes);
+                continue;
+            }
+            // Content that isn't valid UTF-8 can't be substituted, so keep it verbatim
+            match String::from_utf8(bytes) {
+                Ok(text) => {
+                    files.insert(path, text);
+                }
+                Err(e) => {
+                    binary_files.insert(path, e.into_bytes());
+                }
+            }
+        }
+
+        Ok(Self {
+            files,
+            binary_files,
+            symlinks: HashMap::new(),
+            manifest: manifest.or(yaml_manifest),
+            warnings: Vec::new(),
+        })
     }
 
     /// Creates a template from built-in embedded templates.
@@ -92,6 +499,7 @@ impl Template {
     /// # Supported Templates
     /// 
     /// * `"default"` - Basic C++ competitive programming template
+    /// * `"advanced"` - Adds a `lib/debug.hpp` debug macro and a `tests/` sample scaffold
     /// 
     /// # Examples
     /// 
@@ -102,7 +510,8 @@ impl Template {
     /// ```
     pub fn from_builtin(template_name: &str) -> Result<Self> {
         match template_name {
-            "default" => Ok(Self::from_embedded_content("default", DEFAULT_MAIN_CPP, DEFAULT_CMAKE)),
+            "default" => Ok(Self::from_embedded_content(DEFAULT_FILES)),
+            "advanced" => Ok(Self::from_embedded_content(ADVANCED_FILES)),
             _ => Err(ProconError::TemplateNotFound(template_name.to_string()))
         }
     }
@@ -110,10 +519,15 @@ impl Template {
     /// Loads a template from the specified directory path with dynamic file detection.
     /// 
     /// This method implements a comprehensive template loading system that:
-    /// 1. Validates that required files (main.cpp, CMakeLists.txt) are present
-    /// 2. Dynamically discovers and loads all additional files in the template directory
+    /// 1. Reads the optional `template.toml` manifest
+    /// 2. Dynamically discovers and loads all files in the template directory
     /// 3. Recursively processes subdirectories to maintain project structure
     /// 4. Preserves relative paths for proper project hierarchy recreation
+    /// 5. Validates that the required files are present: those the manifest declares
+    ///    in `required_files`, or main.cpp and CMakeLists.txt by default
+    /// 
+    /// When the manifest `extends` another template, that template's files are loaded
+    /// first and this template's files override or add to them.
     /// 
     /// The dynamic detection allows templates to include any additional files without
     /// requiring explicit configuration, making the template system flexible and extensible.
@@ -129,7 +543,10 @@ impl Template {
     /// 
     /// # Errors
     /// 
-    /// * `TemplateNotFound` - Required files (main.cpp, CMakeLists.txt) are missing
+    /// * `TemplateNotFound` - Required files (main.cpp, CMakeLists.txt) are missing, or
+    ///   the base template named by `extends` doesn't exist
+    /// * `ConfigError` - Templates extend each other in a cycle
+    /// * `TomlParse` - The manifest exists but is not valid TOML
     /// * `Io` - Filesystem errors during directory traversal or file reading
     /// 
     /// # Examples
@@ -142,24 +559,274 @@ impl Template {
     /// // Template now contains all files from the directory, including subdirectories
     /// ```
     pub fn load_from_path(path: &Path) -> Result<Self> {
+        Self::load(path, true)
+    }
+
+    /// Loads a template directory like [`Template::load_from_path`], but without
+    /// requiring `main.cpp` and `CMakeLists.txt`.
+    /// 
+    /// This supports iterating on unfinished templates. Callers should warn about the
+    /// missing files, which [`Template::missing_required_files`] reports.
+    /// 
+    /// # Errors
+    /// 
+    /// * `Io` - Filesystem errors during directory traversal or file reading
+    /// * `TomlParse` - The manifest exists but is not valid TOML
+    pub fn load_incomplete_from_path(path: &Path) -> Result<Self> {
+        Self::load(path, false)
+    }
+
+    /// Returns the files the template must contain, as declared by its manifest's
+    /// `required_files`, or [`REQUIRED_FILES`] when it declares none.
+    pub fn required_files(&self) -> Vec<String> {
+        match self.manifest.as_ref().and_then(|manifest| manifest.required_files.as_ref()) {
+            Some(declared) => declared.clone(),
+            None => REQUIRED_FILES.iter().map(|file_name| file_name.to_string()).collect(),
+        }
+    }
+
+    /// Returns the [required files](Template::required_files) the template lacks.
+    pub fn missing_required_files(&self) -> Vec<String> {
+        self.required_files()
+            .into_iter()
+            .filter(|file_name| {
+                !self.files.contains_key(file_name)
+                    && !self.binary_files.contains_key(file_name)
+                    && !self.symlinks.contains_key(file_name)
+            })
+            .collect()
+    }
+
+    fn load(path: &Path, require_files: bool) -> Result<Self> {
+        let mut visited = vec![fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())];
+        let template = Self::load_extending(path, &mut visited)?;
+
+        // Validate the required files, as declared by the manifest or the defaults
+        if require_files && let Some(missing) = template.missing_required_files().first() {
+            return Err(ProconError::TemplateNotFound(format!(
+                "{} not found in template",
+                missing
+            )));
+        }
+
+        Ok(template)
+    }
+
+    /// Loads a template directory and, recursively, the templates it extends.
+    ///
+    /// `visited` holds the canonical directories on the current inheritance chain.
+    fn load_extending(path: &Path, visited: &mut Vec<PathBuf>) -> Result<Self> {
+        let mut warnings = Vec::new();
+        let manifest = Self::load_manifest(path, &mut warnings)?;
+
+        // Discover and load every file in the template directory
         let mut files = HashMap::new();
+        let mut binary_files = HashMap::new();
+        let mut symlinks = HashMap::new();
+        let ignore_patterns = Self::load_ignore_patterns(path)?;
+        let mut found = LoadedEntries {
+            files: &mut files,
+            binary_files: &mut binary_files,
+            symlinks: &mut symlinks,
+            warnings: &mut warnings,
+        };
+        Self::load_directory_recursively(path, "", &ignore_patterns, &mut found)?;
+
+        let mut template = Self {
+            files,
+            binary_files,
+            symlinks,
+            manifest,
+            warnings,
+        };
 
-        // Validate and load required files first
-        let required_files = ["main.cpp", "CMakeLists.txt"];
-        for &file_name in &required_files {
-            let file_path = path.join(file_name);
-            if !file_path.exists() {
-                return Err(ProconError::TemplateNotFound(
-                    format!("{} not found in template", file_name),
+        if let Some(base_name) = template.manifest.as_ref().and_then(|manifest| manifest.extends.clone()) {
+            let base = Self::load_base(&base_name, path, visited)?;
+            template.inherit(base);
+        }
+
+        Ok(template)
+    }
+
+    /// Resolves a base template named by `extends`.
+    ///
+    /// A directory of that name next to the child wins, so user templates can extend
+    /// each other; otherwise the builtin is used. A template never resolves to itself,
+    /// which lets a user `default` extend the builtin `default`.
+    fn load_base(name: &str, child_dir: &Path, visited: &mut Vec<PathBuf>) -> Result<Self> {
+        let child = visited.last().cloned();
+        let sibling = child_dir
+            .parent()
+            .map(|templates_dir| templates_dir.join(name))
+            .filter(|dir| dir.is_dir())
+            .map(|dir| fs::canonicalize(&dir).unwrap_or(dir))
+            .filter(|dir| Some(dir) != child.as_ref());
+
+        let Some(base_dir) = sibling else {
+            return Self::from_builtin(name).map_err(|_| {
+                ProconError::TemplateNotFound(format!("{} (extended by {})", name, child_dir.display()))
+            });
+        };
+
+        if visited.contains(&base_dir) {
+            let chain: Vec<String> = visited
+                .iter()
+                .chain([&base_dir])
+                .map(|dir| dir.file_name().unwrap_or_default().to_string_lossy().into_owned())
+                .collect();
+            return Err(ProconError::ConfigError(format!(
+                "Template inheritance cycle: {}",
+                chain.join(" -> ")
+            )));
+        }
+
+        visited.push(base_dir.clone());
+        let base = Self::load_extending(&base_dir, visited);
+        visited.pop();
+        base
+    }
+
+    /// Inserts `other`'s text and binary files and symlinks into this template.
+    ///
+    /// A path this template already has, as either kind of file, is replaced only when
+    /// `overwrite` is set. Manifests are left alone. Returns how many files were added
+    /// or overwritten.
+    pub fn merge(&mut self, other: &Template, overwrite: bool) -> usize {
+        let mut merged = 0;
+        for (path, content) in &other.files {
+            if self.take_for_merge(path, overwrite) {
+                self.files.insert(path.clone(), content.clone());
+                merged += 1;
+            }
+        }
+        for (path, bytes) in &other.binary_files {
+            if self.take_for_merge(path, overwrite) {
+                self.binary_files.insert(path.clone(), bytes.clone());
+                merged += 1;
+            }
+        }
+        for (path, target) in &other.symlinks {
+            if self.take_for_merge(path, overwrite) {
+                self.symlinks.insert(path.clone(), target.clone());
+                merged += 1;
+            }
+        }
+        merged
+    }
+
+    /// Returns whether [`Template::merge`] may write `path`, removing this template's
+    /// existing file there when it is to be overwritten.
+    fn take_for_merge(&mut self, path: &str, overwrite: bool) -> bool {
+        let exists = self.files.contains_key(path)
+            || self.binary_files.contains_key(path)
+            || self.symlinks.contains_key(path);
+        if exists && !overwrite {
+            return false;
+        }
+        self.files.remove(path);
+        self.binary_files.remove(path);
+        self.symlinks.remove(path);
+        true
+    }
+
+    /// Adds a base template's files that this template doesn't override.
+    ///
+    /// Manifest variables, conditional files, samples and hooks are combined, with
+    /// this template's entries winning and its hooks running after the base's; other manifest fields fall back to the base's.
+    fn inherit(&mut self, mut base: Template) {
+        self.merge(&base, false);
+        self.warnings.append(&mut base.warnings);
+
+        if let Some(base_manifest) = base.manifest {
+            let manifest = self.manifest.get_or_insert_with(TemplateManifest::default);
+            let mut variables = base_manifest.variables;
+            variables.append(&mut manifest.variables);
+            manifest.variables = variables;
+            let mut conditional_files = base_manifest.conditional_files;
+            conditional_files.append(&mut manifest.conditional_files);
+            manifest.conditional_files = conditional_files;
+            let mut post_create = base_manifest.hooks.post_create;
+            post_create.append(&mut manifest.hooks.post_create);
+            manifest.hooks.post_create = post_create;
+            let mut samples = base_manifest.samples;
+            samples.append(&mut manifest.samples);
+            manifest.samples = samples;
+            let mut no_substitute = base_manifest.no_substitute;
+            no_substitute.append(&mut manifest.no_substitute);
+            manifest.no_substitute = no_substitute;
+            manifest.post_message = manifest.post_message.take().or(base_manifest.post_message);
+            manifest.description = manifest.description.take().or(base_manifest.description);
+            manifest.required_files = manifest.required_files.take().or(base_manifest.required_files);
+        }
+    }
+
+    /// Checks that a template directory can be loaded, without keeping the result.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Template::load_from_path`].
+    pub fn validate(path: &Path) -> Result<()> {
+        Self::load_from_path(path).map(|_| ())
+    }
+
+    /// Reads the optional manifest from a template directory: `template.toml`, or else
+    /// `template.yaml` or `template.yml`.
+    ///
+    /// Returns `Ok(None)` when the template does not declare a manifest, so templates
+    /// without one keep behaving exactly as before. When there is both a TOML and a
+    /// YAML manifest, the TOML one is used and a warning is added to `warnings`.
+    ///
+    /// # Errors
+    ///
+    /// * `TomlParse` - The TOML manifest is not valid TOML
+    /// * `YamlParse` - The YAML manifest is not valid YAML
+    /// * `Io` - The manifest exists but could not be read
+    fn load_manifest(path: &Path, warnings: &mut Vec<String>) -> Result<Option<TemplateManifest>> {
+        let yaml_path = YAML_MANIFEST_FILES
+            .iter()
+            .map(|file_name| path.join(file_name))
+            .find(|yaml_path| yaml_path.is_file());
+        let manifest_path = path.join(MANIFEST_FILE);
+
+        if manifest_path.is_file() {
+            if let Some(yaml_path) = yaml_path {
+                warnings.push(format!(
+                    "Template {} has both {} and {}; ignoring the latter",
+                    path.display(),
+                    MANIFEST_FILE,
+                    yaml_path.file_name().unwrap_or_default().to_string_lossy()
                 ));
             }
-            files.insert(file_name.to_string(), fs::read_to_string(&file_path)?);
+            let content = fs::read_to_string(&manifest_path)?;
+            return Ok(Some(toml::from_str(&content)?));
+        }
+
+        match yaml_path {
+            Some(yaml_path) => {
+                let content = fs::read_to_string(&yaml_path)?;
+                Ok(Some(serde_yaml::from_str(&content)?))
+            }
+            None => Ok(None),
         }
+    }
 
-        // Dynamically discover and load all other files in the template directory
-        Self::load_directory_recursively(path, "", &mut files)?;
+    /// Reads the patterns of the optional [`IGNORE_FILE`] at a template's root.
+    ///
+    /// # Errors
+    ///
+    /// * `Io` - The ignore file exists but could not be read
+    fn load_ignore_patterns(path: &Path) -> Result<Vec<String>> {
+        let ignore_path = path.join(IGNORE_FILE);
+        if !ignore_path.is_file() {
+            return Ok(Vec::new());
+        }
 
-        Ok(Self { files })
+        Ok(fs::read_to_string(&ignore_path)?
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_string)
+            .collect())
     }
 
     /// Recursively loads all files from a directory and its subdirectories.
@@ -167,7 +834,7 @@ impl Template {
     /// This private helper method implements the core dynamic file detection logic:
     /// - Traverses the directory tree recursively
     /// - Maintains relative path structure using path prefixes
-    /// - Skips required files that are already loaded to avoid duplication
+    /// - Skips the root manifest, which is loaded separately
     /// - Handles both files and subdirectories appropriately
     /// - Preserves the original directory hierarchy for accurate project recreation
     /// 
@@ -179,7 +846,13 @@ impl Template {
     /// 
     /// * `dir` - The directory to scan for files
     /// * `prefix` - The relative path prefix for files in this directory (empty for root)
-    /// * `files` - Mutable reference to the HashMap where discovered files are stored
+    /// * `ignore_patterns` - Patterns from the template's [`IGNORE_FILE`]; matching
+    ///   entries are skipped, except [`REQUIRED_FILES`], which are kept with a warning
+    /// * `found` - Where discovered text files, files that look binary (see
+    ///   [`is_binary`]), symlinks and warnings are stored
+    ///
+    /// Symlinks are recorded with their target rather than followed, so a link to a
+    /// directory can't make the traversal loop.
     /// 
     /// # Returns
     /// 
@@ -198,7 +871,8 @@ impl Template {
     fn load_directory_recursively(
         dir: &Path,
         prefix: &str,
-        files: &mut HashMap<String, String>,
+        ignore_patterns: &[String],
+        found: &mut LoadedEntries,
     ) -> Result<()> {
         for entry in fs::read_dir(dir)? {
             let entry = entry?;
@@ -215,27 +889,248 @@ impl Template {
                 format!("{}/{}", prefix, name)
             };
 
-            if path.is_dir() {
+            if is_ignored(ignore_patterns, &relative_path, path.is_dir()) {
+                if !REQUIRED_FILES.contains(&relative_path.as_str()) {
+                    log::debug!("Skipping {} (matched by {})", relative_path, IGNORE_FILE);
+                    continue;
+                }
+                found.warnings.push(format!(
+                    "{} would ignore required file {}; keeping it",
+                    IGNORE_FILE, relative_path
+                ));
+            }
+
+            let file_type = entry.file_type()?;
+            if file_type.is_symlink() {
+                let target = fs::read_link(&path)?;
+                log::debug!("Loaded {} as a symlink to {}", relative_path, target.display());
+                found.symlinks.insert(relative_path, target);
+            } else if file_type.is_dir() {
                 // Recursively process subdirectories to maintain hierarchy
-                Self::load_directory_recursively(&path, &relative_path, files)?;
-            } else if path.is_file() {
-                // Skip required files that are already loaded to prevent duplication
-                let required_files = ["main.cpp", "CMakeLists.txt"];
-                if prefix.is_empty() && required_files.contains(&name.as_str()) {
+                Self::load_directory_recursively(&path, &relative_path, ignore_patterns, found)?;
+            } else if file_type.is_file() {
+                // Skip the manifest and ignore list, which describe the template rather
+                // than belonging to it
+                if prefix.is_empty() && (is_manifest_file(&name) || name == IGNORE_FILE) {
                     continue;
                 }
 
-                // Load file content and store with relative path as key
-                if let Ok(content) = fs::read_to_string(&path) {
-                    files.insert(relative_path, content);
+                // Load file content and store with relative path as key. Files that look
+                // binary or aren't UTF-8 are kept as bytes so substitution can never
+                // corrupt them.
+                let bytes = fs::read(&path)?;
+                if is_binary(&bytes) {
+                    log::debug!("Loaded {} as binary ({} bytes)", relative_path, bytes.len());
+                    found.binary_files.insert(relative_path, bytes);
+                } else {
+                    match String::from_utf8(bytes) {
+                        Ok(content) => {
+                            log::debug!("Loaded {} ({} bytes)", relative_path, content.len());
+                            found.files.insert(relative_path, content);
+                        }
+                        Err(e) => {
+                            log::debug!("Loaded {} as binary (not UTF-8)", relative_path);
+                            found.binary_files.insert(relative_path, e.into_bytes());
+                        }
+                    }
                 }
-                // Note: We silently skip files that cannot be read (e.g., binary files)
-                // This allows templates to include various file types without breaking
             }
         }
         Ok(())
     }
 
+    /// Computes a stable hash of the template's files for cache and change detection.
+    ///
+    /// Text and binary files are hashed together in sorted path order, each as its length-prefixed path followed
+    /// by its length-prefixed content, using 64-bit FNV-1a. The result is independent of
+    /// `HashMap` iteration order and stays the same across runs, platforms, and Rust
+    /// releases, unlike `std`'s `DefaultHasher`. The manifest is not part of the hash.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use procon_rs::template::Template;
+    ///
+    /// let a = Template::from_main_and_cmake("int main() {}", "project(x)");
+    /// let b = Template::from_main_and_cmake("int main() {}", "project(x)");
+    /// assert_eq!(a.content_hash(), b.content_hash());
+    /// ```
+    pub fn content_hash(&self) -> String {
+        let mut hash = Fnv1a::default();
+
+        let mut entries: Vec<(&String, &[u8])> = self
+            .files
+            .iter()
+            .map(|(path, content)| (path, content.as_bytes()))
+            .chain(self.binary_files.iter().map(|(path, bytes)| (path, bytes.as_slice())))
+            .collect();
+        entries.sort();
+
+        for (path, content) in entries {
+            // Length prefixes keep ("ab", "c") and ("a", "bc") from colliding
+            hash.feed(&(path.len() as u64).to_le_bytes());
+            hash.feed(path.as_bytes());
+            hash.feed(&(content.len() as u64).to_le_bytes());
+            hash.feed(content);
+        }
+
+        // Symlinks follow every file, each marked so it can't pass for a file holding
+        // its target
+        let mut symlinks: Vec<(&String, &PathBuf)> = self.symlinks.iter().collect();
+        symlinks.sort();
+        for (path, target) in symlinks {
+            let target = target.to_string_lossy();
+            hash.feed(b"symlink");
+            hash.feed(&(path.len() as u64).to_le_bytes());
+            hash.feed(path.as_bytes());
+            hash.feed(&(target.len() as u64).to_le_bytes());
+            hash.feed(target.as_bytes());
+        }
+
+        hash.hex()
+    }
+
+    /// Returns the relative paths of all text and binary files and symlinks, sorted.
+    pub fn file_paths(&self) -> Vec<String> {
+        let mut paths: Vec<String> = self
+            .files
+            .keys()
+            .chain(self.binary_files.keys())
+            .chain(self.symlinks.keys())
+            .cloned()
+            .collect();
+        paths.sort();
+        paths
+    }
+
+    /// Returns the number of text and binary files and symlinks.
+    pub fn file_count(&self) -> usize {
+        self.files.len() + self.binary_files.len() + self.symlinks.len()
+    }
+
+    /// Returns the combined size of all text and binary file contents, in bytes.
+    pub fn total_bytes(&self) -> usize {
+        self.files.values().map(String::len).sum::<usize>()
+            + self.binary_files.values().map(Vec::len).sum::<usize>()
+    }
+
+    /// Returns the names of all `{{NAME}}` placeholders in file contents and paths,
+    /// including those written with a fallback as `{{NAME:fallback}}`.
+    ///
+    /// Only identifiers count as placeholders, so `{{ }}` or `{{1}}` are ignored.
+    /// Binary files are not scanned since they are copied verbatim.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use procon_rs::template::Template;
+    ///
+    /// let template = Template::from_embedded_content(&[("{{DATE}}.md", "by {{AUTHOR}} {{ }}")]);
+    /// let names: Vec<String> = template.variables().into_iter().collect();
+    /// assert_eq!(names, ["AUTHOR", "DATE"]);
+    /// ```
+    pub fn variables(&self) -> BTreeSet<String> {
+        self.placeholder_captures()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    /// Returns the fallback of every variable written as `{{NAME:fallback}}`.
+    ///
+    /// Should a variable be given different fallbacks, the first one found in sorted
+    /// path order is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use procon_rs::template::Template;
+    ///
+    /// let template = Template::from_embedded_content(&[("main.cpp", "// {{AUTHOR:Anonymous}} {{DATE}}")]);
+    /// assert_eq!(template.inline_defaults()["AUTHOR"], "Anonymous");
+    /// assert!(!template.inline_defaults().contains_key("DATE"));
+    /// ```
+    pub fn inline_defaults(&self) -> BTreeMap<String, String> {
+        let mut defaults = BTreeMap::new();
+        for (name, fallback) in self.placeholder_captures() {
+            if let Some(fallback) = fallback {
+                defaults.entry(name).or_insert(fallback);
+            }
+        }
+        defaults
+    }
+
+    /// Returns whether a file's content is copied without substitution because the
+    /// manifest's `no_substitute` matches it. Required files never are.
+    pub fn is_verbatim(&self, path: &str) -> bool {
+        let Some(manifest) = &self.manifest else {
+            return false;
+        };
+        manifest.no_substitute.iter().any(|pattern| glob_match(pattern, path))
+            && !self.required_files().iter().any(|required| required == path)
+    }
+
+    /// Scans file contents and paths, in sorted path order, for placeholders and
+    /// returns each one's name and fallback, if it has one. Verbatim file contents
+    /// are skipped.
+    fn placeholder_captures(&self) -> Vec<(String, Option<String>)> {
+        let mut texts: Vec<(&str, Option<&str>)> = self
+            .files
+            .iter()
+            .map(|(path, content)| (path.as_str(), (!self.is_verbatim(path)).then_some(content.as_str())))
+            .chain(self.binary_files.keys().map(|path| (path.as_str(), None)))
+            .collect();
+        texts.sort();
+
+        texts
+            .into_iter()
+            .flat_map(|(path, content)| [Some(path), content].into_iter().flatten())
+            .flat_map(|text| PLACEHOLDER.captures_iter(text))
+            .map(|captures| (captures[1].to_string(), captures.get(2).map(|m| m.as_str().to_string())))
+            .collect()
+    }
+
+    /// Removes the files the manifest marks as samples.
+    ///
+    /// Returns how many files were removed. Templates without a manifest, or whose
+    /// manifest declares no samples, are left unchanged.
+    pub fn remove_samples(&mut self) -> usize {
+        let Some(manifest) = &self.manifest else {
+            return 0;
+        };
+        let is_sample =
+            |path: &String| manifest.samples.iter().any(|pattern| glob_match(pattern, path));
+
+        let before = self.files.len() + self.binary_files.len();
+        self.files.retain(|path, _| !is_sample(path));
+        self.binary_files.retain(|path, _| !is_sample(path));
+        before - self.files.len() - self.binary_files.len()
+    }
+
+    /// Removes the template's root `.gitignore`, whichever kind of file it is.
+    ///
+    /// Returns whether there was one.
+    pub fn remove_gitignore(&mut self) -> bool {
+        let text = self.files.remove(GITIGNORE_FILE).is_some();
+        let binary = self.binary_files.remove(GITIGNORE_FILE).is_some();
+        let symlink = self.symlinks.remove(GITIGNORE_FILE).is_some();
+        text || binary || symlink
+    }
+
+    /// Adds extra rules to the template's root `.gitignore`, creating it if needed.
+    ///
+    /// Rules already present in the template's `.gitignore`, and repeated extras, are
+    /// only kept once (see [`merge_gitignore`]).
+    pub fn extend_gitignore(&mut self, extra_rules: &[String]) {
+        if extra_rules.is_empty() {
+            return;
+        }
+
+        let base = self.files.get(GITIGNORE_FILE).map(String::as_str).unwrap_or("");
+        let merged = merge_gitignore(base, &extra_rules.join("\n"));
+        self.files.insert(GITIGNORE_FILE.to_string(), merged);
+    }
+
     pub fn apply_variables(&self, project_name: &str) -> Self {
         let mut processed_files = HashMap::new();
 
@@ -244,8 +1139,20 @@ impl Template {
             processed_files.insert(filename.clone(), processed_content);
         }
 
+        let manifest = self.manifest.as_ref().map(|manifest| TemplateManifest {
+            post_message: manifest
+                .post_message
+                .as_ref()
+                .map(|message| message.replace("{{PROJECT_NAME}}", project_name)),
+            ..manifest.clone()
+        });
+
         Self {
             files: processed_files,
+            binary_files: self.binary_files.clone(),
+            symlinks: self.symlinks.clone(),
+            manifest,
+            warnings: self.warnings.clone(),
         }
     }
 
@@ -273,8 +1180,15 @@ impl Template {
     /// 
     /// # Errors
     /// 
+    /// * `UnsafeTemplatePath` - A file path is absolute or resolves outside `dest_dir`;
+    ///   nothing is written in that case
+    /// * `UnsafeSymlinkTarget` - A symlink points to an absolute path or outside
+    ///   `dest_dir`; nothing is written in that case either
     /// * `Io` - Filesystem errors such as permission issues, disk space, or invalid paths
     /// 
+    /// A `.gitignore` at the template root is merged into an existing `.gitignore`
+    /// in the destination (see [`merge_gitignore`]) rather than replacing it.
+    /// 
     /// # Directory Structure Handling
     /// 
     /// The method automatically creates subdirectories based on file paths:
@@ -293,22 +1207,282 @@ impl Template {
     /// // Creates my_project/ with full directory structure from template
     /// ```
     pub fn copy_to(&self, dest_dir: &Path) -> Result<()> {
+        // Reject escaping paths up front so a bad entry cannot leave partial output behind
+        // Sorted so errors and write order don't depend on HashMap iteration order
+        let mut entries: Vec<(&String, &[u8])> = self
+            .files
+            .iter()
+            .map(|(path, content)| (path, content.as_bytes()))
+            .chain(self.binary_files.iter().map(|(path, bytes)| (path, bytes.as_slice())))
+            .collect();
+        entries.sort_by_key(|(path, _)| *path);
+
+        let mut targets: Vec<(PathBuf, &[u8])> = Vec::with_capacity(entries.len());
+        for (relative_path, content) in entries {
+            targets.push((Self::normalize_relative_path(relative_path)?, content));
+        }
+        let mut symlinks: Vec<(PathBuf, &PathBuf)> = Vec::with_capacity(self.symlinks.len());
+        for (relative_path, target) in &self.symlinks {
+            let link = Self::normalize_relative_path(relative_path)?;
+            Self::check_symlink_target(&link, target)?;
+            symlinks.push((link, target));
+        }
+        symlinks.sort();
+
         // Ensure the destination directory exists
         fs::create_dir_all(dest_dir)?;
 
-        for (relative_path, content) in &self.files {
-            let dest_file = dest_dir.join(relative_path);
+        for (relative_path, content) in targets {
+            let dest_file = dest_dir.join(&relative_path);
             
             // Create parent directories if the file is in a subdirectory
             if let Some(parent_dir) = dest_file.parent() {
                 fs::create_dir_all(parent_dir)?;
             }
             
+            // Merge into an existing .gitignore instead of discarding the user's rules
+            if relative_path == Path::new(GITIGNORE_FILE)
+                && dest_file.is_file()
+                && let Ok(rules) = std::str::from_utf8(content)
+            {
+                let existing = fs::read_to_string(&dest_file)?;
+                fs::write(&dest_file, merge_gitignore(&existing, rules))?;
+                continue;
+            }
+
             // Write the file content to the destination
             fs::write(&dest_file, content)?;
         }
 
+        // Links come last so no file is ever written through one
+        for (relative_path, target) in symlinks {
+            let dest_link = dest_dir.join(&relative_path);
+            if let Some(parent_dir) = dest_link.parent() {
+                fs::create_dir_all(parent_dir)?;
+            }
+            Self::create_symlink(target, &dest_link)?;
+        }
+
         Ok(())
     }
+
+    #[cfg(unix)]
+    fn create_symlink(target: &Path, link: &Path) -> Result<()> {
+        std::os::unix::fs::symlink(target, link)?;
+        Ok(())
+    }
+
+    /// Symlinks can't be created reliably elsewhere, so they are left out with a warning.
+    #[cfg(not(unix))]
+    fn create_symlink(target: &Path, link: &Path) -> Result<()> {
+        log::warn!("Skipping symlink {} -> {}", link.display(), target.display());
+        Ok(())
+    }
+
+    /// Checks that a symlink at the normalized relative path `link` points inside the
+    /// destination, resolving its target lexically from the link's directory.
+    ///
+    /// Without this, a template could plant a link to `/etc` or `../../somewhere` that
+    /// later writes into the project would follow.
+    ///
+    /// # Errors
+    ///
+    /// * `UnsafeSymlinkTarget` - The target is absolute or climbs above the destination
+    fn check_symlink_target(link: &Path, target: &Path) -> Result<()> {
+        let unsafe_target = || {
+            ProconError::UnsafeSymlinkTarget(link.display().to_string(), target.display().to_string())
+        };
+
+        let mut resolved = link.parent().map(Path::to_path_buf).unwrap_or_default();
+        for component in target.components() {
+            match component {
+                Component::Normal(part) => resolved.push(part),
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    if !resolved.pop() {
+                        return Err(unsafe_target());
+                    }
+                }
+                Component::RootDir | Component::Prefix(_) => return Err(unsafe_target()),
+            }
+        }
+        Ok(())
+    }
+
+    /// Normalizes a template file key into a path that stays inside the destination.
+    ///
+    /// `.` components are dropped and `..` components are resolved lexically. Keys that
+    /// are absolute, or whose `..` components climb above the destination root, are
+    /// rejected. This matters for templates coming from untrusted sources, where a
+    /// crafted key like `../../evil` would otherwise be written outside the project.
+    ///
+    /// # Errors
+    ///
+    /// * `UnsafeTemplatePath` - The key is absolute or escapes the destination
+    fn normalize_relative_path(relative_path: &str) -> Result<PathBuf> {
+        let mut normalized = PathBuf::new();
+
+        for component in Path::new(relative_path).components() {
+            match component {
+                Component::Normal(part) => normalized.push(part),
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    if !normalized.pop() {
+                        return Err(ProconError::UnsafeTemplatePath(relative_path.to_string()));
+                    }
+                }
+                Component::RootDir | Component::Prefix(_) => {
+                    return Err(ProconError::UnsafeTemplatePath(relative_path.to_string()));
+                }
+            }
+        }
+
+        if normalized.as_os_str().is_empty() {
+            return Err(ProconError::UnsafeTemplatePath(relative_path.to_string()));
+        }
+
+        Ok(normalized)
+    }
+}
+
+/// Checks that a relative template path only contains portable file names.
+///
+/// Every `/`-separated segment must be non-empty and free of control characters and
+/// of the characters Windows forbids in file names (`<>:"|?*`). This catches names
+/// that variable substitution turned into something no filesystem will accept.
+///
+/// # Errors
+///
+/// * `InvalidTemplateFileName` - A segment is empty or contains a forbidden character
+pub fn validate_file_name(relative_path: &str) -> Result<()> {
+    let is_forbidden = |c: char| c.is_control() || "<>:\"|?*".contains(c);
+
+    for segment in relative_path.split('/') {
+        if segment.is_empty() || segment.chars().any(is_forbidden) {
+            return Err(ProconError::InvalidTemplateFileName(relative_path.to_string()));
+        }
+    }
+
+    Ok(())
 }
 
+/// Replaces every `{{NAME:fallback}}` placeholder in `content` with the value `lookup`
+/// returns for `NAME`, or with `fallback` when it returns `None`.
+///
+/// Placeholders without a fallback are left for the caller to substitute.
+///
+/// # Examples
+///
+/// ```
+/// use procon_rs::template::fill_defaulted_placeholders;
+///
+/// let filled = fill_defaulted_placeholders("{{A:x}} {{B:https://a.b}} {{C}}", |name| {
+///     (name == "A").then(|| "a".to_string())
+/// });
+/// assert_eq!(filled, "a https://a.b {{C}}");
+/// ```
+pub fn fill_defaulted_placeholders(content: &str, lookup: impl Fn(&str) -> Option<String>) -> String {
+    PLACEHOLDER
+        .replace_all(content, |captures: &regex::Captures| match captures.get(2) {
+            Some(fallback) => lookup(&captures[1]).unwrap_or_else(|| fallback.as_str().to_string()),
+            None => captures[0].to_string(),
+        })
+        .into_owned()
+}
+
+/// Decides whether a template entry matches any pattern of an [`IGNORE_FILE`].
+///
+/// Directories are also tried with a trailing `/`, so `build/` skips the whole
+/// directory, and patterns without a `/` are tried against the entry's name alone.
+fn is_ignored(patterns: &[String], relative_path: &str, is_dir: bool) -> bool {
+    let name = relative_path.rsplit('/').next().unwrap_or(relative_path);
+    let dir_path = format!("{}/", relative_path);
+    patterns.iter().any(|pattern| {
+        let anchored = pattern.trim_end_matches('/').contains('/');
+        let candidates: &[&str] = match (is_dir, anchored) {
+            (true, true) => &[relative_path, &dir_path],
+            (true, false) => &[name, &format!("{}/", name)],
+            (false, true) => &[relative_path],
+            (false, false) => &[name],
+        };
+        candidates.iter().any(|candidate| glob_match(pattern, candidate))
+    })
+}
+
+/// Heuristically decides whether file content is binary.
+///
+/// Text files essentially never contain NUL bytes, while most binary formats (fonts,
+/// images, compiled objects) have one early on, so the first KB is checked for a NUL.
+/// Such files are copied verbatim instead of being treated as text.
+pub fn is_binary(bytes: &[u8]) -> bool {
+    bytes.iter().take(BINARY_SNIFF_LEN).any(|&byte| byte == 0)
+}
+
+/// Merges template `.gitignore` rules into an existing `.gitignore`.
+///
+/// The user's content is kept verbatim. Template rules that are not already present
+/// (compared after trimming whitespace) are appended under a marker comment; blank
+/// lines and comments from the template are not carried over. When every rule is
+/// already present the existing content is returned unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use procon_rs::template::merge_gitignore;
+///
+/// let merged = merge_gitignore("build/\n", "build/\n*.o\n");
+/// assert_eq!(merged, "build/\n\n# Added by procon_rs\n*.o\n");
+/// ```
+pub fn merge_gitignore(existing: &str, template: &str) -> String {
+    let present: std::collections::HashSet<&str> = existing.lines().map(str::trim).collect();
+
+    let mut missing: Vec<&str> = Vec::new();
+    for rule in template.lines().map(str::trim) {
+        if rule.is_empty() || rule.starts_with('#') || present.contains(rule) || missing.contains(&rule) {
+            continue;
+        }
+        missing.push(rule);
+    }
+
+    if missing.is_empty() {
+        return existing.to_string();
+    }
+
+    let mut merged = existing.to_string();
+    if !merged.is_empty() && !merged.ends_with('\n') {
+        merged.push('\n');
+    }
+    if !merged.is_empty() {
+        merged.push('\n');
+    }
+    merged.push_str("# Added by procon_rs\n");
+    for rule in missing {
+        merged.push_str(rule);
+        merged.push('\n');
+    }
+    merged
+}
+
+/// 64-bit FNV-1a hasher, whose output stays the same across runs, platforms, and
+/// Rust releases.
+pub(crate) struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Fnv1a {
+    pub(crate) fn feed(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    /// Returns the hash as 16 lowercase hex digits.
+    pub(crate) fn hex(&self) -> String {
+        format!("{:016x}", self.0)
+    }
+}
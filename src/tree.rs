@@ -0,0 +1,55 @@
+use std::collections::BTreeMap;
+
+/// A directory level of the tree, keyed by entry name so siblings come out sorted.
+#[derive(Default)]
+struct Node {
+    children: BTreeMap<String, Node>,
+}
+
+/// Renders `/`-separated relative file paths as an ASCII directory tree.
+///
+/// Paths sharing a directory are grouped under it, and siblings are sorted by name.
+/// The first line is `root`.
+///
+/// # Examples
+///
+/// ```
+/// use procon_rs::tree::render_tree;
+///
+/// let paths = ["main.cpp".to_string(), "lib/io.hpp".to_string()];
+/// assert_eq!(
+///     render_tree("demo", &paths),
+///     "demo\n├── lib\n│   └── io.hpp\n└── main.cpp\n"
+/// );
+/// ```
+pub fn render_tree(root: &str, paths: &[String]) -> String {
+    let mut tree = Node::default();
+    for path in paths {
+        let mut node = &mut tree;
+        for segment in path.split('/').filter(|segment| !segment.is_empty()) {
+            node = node.children.entry(segment.to_string()).or_default();
+        }
+    }
+
+    let mut output = format!("{}\n", root);
+    render_children(&tree, "", &mut output);
+    output
+}
+
+fn render_children(node: &Node, indent: &str, output: &mut String) {
+    let count = node.children.len();
+    for (index, (name, child)) in node.children.iter().enumerate() {
+        let is_last = index + 1 == count;
+        let (connector, continuation) = if is_last {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+
+        output.push_str(indent);
+        output.push_str(connector);
+        output.push_str(name);
+        output.push('\n');
+        render_children(child, &format!("{}{}", indent, continuation), output);
+    }
+}
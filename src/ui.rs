@@ -0,0 +1,103 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Environment variable that disables emoji markers when set to a non-empty value.
+pub const NO_EMOJI_ENV: &str = "PROCON_NO_EMOJI";
+
+static EMOJI_ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Prefix printed in front of a status line.
+///
+/// Each marker has an emoji form and a plain ASCII form for terminals that render
+/// emoji as boxes. Color is applied by the caller and is unaffected by the choice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Marker {
+    Create,
+    Success,
+    Error,
+    Warning,
+    Config,
+    Info,
+    Skip,
+    Remove,
+    Prune,
+}
+
+impl Marker {
+    pub fn emoji(self) -> &'static str {
+        match self {
+            Marker::Create => "✨",
+            Marker::Success => "✅",
+            Marker::Error => "❌",
+            Marker::Warning => "⚠️",
+            Marker::Config => "⚙️",
+            Marker::Info => "📦",
+            Marker::Skip => "⏭️",
+            Marker::Remove => "🗑️",
+            Marker::Prune => "🧹",
+        }
+    }
+
+    pub fn ascii(self) -> &'static str {
+        match self {
+            Marker::Create => "[+]",
+            Marker::Success => "[ok]",
+            Marker::Error => "[err]",
+            Marker::Warning => "[warn]",
+            Marker::Config => "[cfg]",
+            Marker::Info => "[i]",
+            Marker::Skip => "[skip]",
+            Marker::Remove => "[rm]",
+            Marker::Prune => "[-]",
+        }
+    }
+
+    /// Returns the form selected by [`set_emoji`].
+    pub fn text(self) -> &'static str {
+        if EMOJI_ENABLED.load(Ordering::Relaxed) {
+            self.emoji()
+        } else {
+            self.ascii()
+        }
+    }
+}
+
+/// Selects emoji or ASCII markers for all subsequent output.
+pub fn set_emoji(enabled: bool) {
+    EMOJI_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns whether `PROCON_NO_EMOJI` asks for ASCII markers.
+pub fn emoji_disabled_by_env() -> bool {
+    std::env::var_os(NO_EMOJI_ENV).is_some_and(|value| !value.is_empty())
+}
+
+/// How much status output the CLI prints, chosen with `--quiet` and `--verbose`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Verbosity {
+    /// Only errors, on stderr, plus output that is the command's actual result.
+    Quiet,
+    #[default]
+    Normal,
+    /// Additionally lists each file written.
+    Verbose,
+}
+
+impl Verbosity {
+    pub fn from_flags(quiet: bool, verbose: bool) -> Self {
+        match (quiet, verbose) {
+            (true, _) => Verbosity::Quiet,
+            (false, true) => Verbosity::Verbose,
+            (false, false) => Verbosity::Normal,
+        }
+    }
+
+    /// Returns whether status lines such as "created successfully" should be printed.
+    pub fn shows_status(self) -> bool {
+        self != Verbosity::Quiet
+    }
+
+    /// Returns whether per-file details should be printed.
+    pub fn shows_details(self) -> bool {
+        self == Verbosity::Verbose
+    }
+}
@@ -0,0 +1,17 @@
+#pragma once
+
+// debug(x, y) prints "[x, y] = 1 2" to stderr in Debug builds and compiles to nothing otherwise
+#ifdef LOCAL
+#include <iostream>
+
+template <class... Args>
+void debug_print(const char* names, const Args&... args) {
+    std::cerr << "[" << names << "] =";
+    ((std::cerr << ' ' << args), ...);
+    std::cerr << std::endl;
+}
+
+#define debug(...) debug_print(#__VA_ARGS__, __VA_ARGS__)
+#else
+#define debug(...) ((void)0)
+#endif
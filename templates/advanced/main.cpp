@@ -0,0 +1,17 @@
+{{PRAGMAS}}
+#include <bits/stdc++.h>
+#include "debug.hpp"
+using namespace std;
+
+void solve() {
+    // {{PROJECT_NAME}} - solve the problem here
+}
+
+int main() {
+    ios_base::sync_with_stdio(false);
+    cin.tie(nullptr);
+
+    solve();
+
+    return 0;
+}
\ No newline at end of file
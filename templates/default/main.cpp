@@ -1,3 +1,4 @@
+{{PRAGMAS}}
 #include <bits/stdc++.h>
 using namespace std;
 
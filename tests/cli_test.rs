@@ -0,0 +1,2028 @@
+#[cfg(test)]
+mod cli_tests {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::io::Write;
+    use std::process::{Command, Output, Stdio};
+    use tempfile::TempDir;
+
+    /// Runs the procon_rs binary with an isolated home and config directory.
+    ///
+    /// Pointing HOME and XDG_CONFIG_HOME into the temporary directory keeps the
+    /// user's real templates and configuration out of the tests.
+    fn run_procon(home: &Path, cwd: &Path, args: &[&str]) -> Output {
+        run_procon_with_env(home, cwd, args, &[])
+    }
+
+    /// Like `run_procon`, with extra environment variables set.
+    fn run_procon_with_env(home: &Path, cwd: &Path, args: &[&str], envs: &[(&str, &str)]) -> Output {
+        Command::new(env!("CARGO_BIN_EXE_procon_rs"))
+            .args(args)
+            .current_dir(cwd)
+            .env("HOME", home)
+            .env("XDG_CONFIG_HOME", home.join(".config"))
+            .envs(envs.iter().copied())
+            .output()
+            .unwrap()
+    }
+
+    /// Creates a user template under the isolated config directory.
+    fn create_user_template(home: &Path, name: &str, extra_files: &[(&str, &str)]) {
+        let template_dir = home
+            .join(".config")
+            .join("procon_rs")
+            .join("templates")
+            .join(name);
+        fs::create_dir_all(&template_dir).unwrap();
+        fs::write(template_dir.join("main.cpp"), "int main() { return 0; }\n").unwrap();
+        fs::write(
+            template_dir.join("CMakeLists.txt"),
+            "project({{PROJECT_NAME}})\n",
+        )
+        .unwrap();
+        for (path, content) in extra_files {
+            fs::write(template_dir.join(path), content).unwrap();
+        }
+    }
+
+    /// Tests that `new` prints the template's substituted post message after creation.
+    ///
+    /// Templates use the post message to tell users their next steps, so it must
+    /// appear on stdout with the project name filled in.
+    #[test]
+    fn test_new_prints_post_message() {
+        // Arrange: Create a user template declaring a post message
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        create_user_template(
+            home.path(),
+            "with_message",
+            &[(
+                "template.toml",
+                "post_message = \"Next: cd {{PROJECT_NAME}} && cmake -B build\"\n",
+            )],
+        );
+
+        // Act: Create a project from the template
+        let output = run_procon(
+            home.path(),
+            work.path(),
+            &["new", "abc300_a", "--template", "with_message"],
+        );
+
+        // Assert: Verify the substituted post message is emitted
+        assert!(output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("Next: cd abc300_a && cmake -B build"));
+        assert!(!work.path().join("abc300_a/template.toml").exists());
+    }
+
+    /// Tests that the post message is part of `new`'s JSON output and printed by `init`.
+    #[test]
+    fn test_post_message_in_json_and_init() {
+        // Arrange: A template with a post message, configured as the default for init
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        let message = ("template.toml", "post_message = \"Next: cd {{PROJECT_NAME}}\"\n");
+        create_user_template(home.path(), "with_message", &[message]);
+        run_procon(home.path(), work.path(), &["config", "template.default", "with_message"]);
+        let existing = work.path().join("existing");
+        fs::create_dir(&existing).unwrap();
+
+        // Act: Create a project with JSON output, and initialize the existing directory
+        let json = run_procon(
+            home.path(),
+            work.path(),
+            &["--format", "json", "new", "abc300_a", "-t", "with_message"],
+        );
+        let init = run_procon(home.path(), &existing, &["init"]);
+
+        // Assert: Verify both report the substituted message
+        assert!(json.status.success(), "{}", String::from_utf8_lossy(&json.stderr));
+        let result: serde_json::Value = serde_json::from_slice(&json.stdout).unwrap();
+        assert_eq!(result["post_message"], "Next: cd abc300_a");
+        assert!(init.status.success(), "{}", String::from_utf8_lossy(&init.stderr));
+        assert!(String::from_utf8_lossy(&init.stdout).contains("Next: cd existing"));
+    }
+
+    /// Tests that a mistyped template name gets a "did you mean" suggestion.
+    ///
+    /// Users commonly transpose letters; pointing at the intended template saves a lookup.
+    #[test]
+    fn test_new_suggests_template_on_typo() {
+        // Arrange: An isolated environment with only builtin templates
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+
+        // Act: Create a project with a misspelled template
+        let output = run_procon(home.path(), work.path(), &["new", "foo", "-t", "defualt"]);
+
+        // Assert: Verify the command fails and suggests the builtin
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("Template 'defualt' not found"));
+        assert!(stderr.contains("Did you mean 'default'?"));
+    }
+
+    /// Tests that `config <key> --json` prints the typed value as a JSON object.
+    #[test]
+    fn test_config_get_json() {
+        // Arrange: An isolated environment with the default configuration
+        let home = TempDir::new().unwrap();
+
+        // Act: Read a key as JSON
+        let output = run_procon(
+            home.path(),
+            home.path(),
+            &["config", "project.cpp_standard", "--json"],
+        );
+
+        // Assert: Verify the output parses and carries a string value
+        assert!(output.status.success());
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(json["project.cpp_standard"], serde_json::json!("17"));
+    }
+
+    /// Tests that the global `--format json` reads a config key as JSON like `--json`.
+    #[test]
+    fn test_config_get_format_json() {
+        // Arrange: An isolated environment with the default configuration
+        let home = TempDir::new().unwrap();
+
+        // Act: Read a key with the global JSON format
+        let output = run_procon(home.path(), home.path(), &["--format", "json", "config", "project.cpp_standard"]);
+
+        // Assert: Verify the same object as with --json is printed
+        assert!(output.status.success());
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(json["project.cpp_standard"], serde_json::json!("17"));
+    }
+
+    /// Tests that an unknown key in JSON mode yields a JSON error object and a failure exit.
+    #[test]
+    fn test_config_get_json_unknown_key() {
+        // Arrange: An isolated environment with the default configuration
+        let home = TempDir::new().unwrap();
+
+        // Act: Read an unknown key as JSON
+        let output = run_procon(home.path(), home.path(), &["config", "nope", "--json"]);
+
+        // Assert: Verify a JSON error is printed to stderr
+        assert!(!output.status.success());
+        let json: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+        assert!(json["error"].as_str().unwrap().contains("Unknown configuration key"));
+    }
+
+    /// Tests that a malformed config file is reported instead of falling back to defaults.
+    #[test]
+    fn test_malformed_config_is_reported() {
+        // Arrange: A config file that isn't valid TOML
+        let home = TempDir::new().unwrap();
+        let config_dir = home.path().join(".config/procon_rs");
+        fs::create_dir_all(&config_dir).unwrap();
+        fs::write(config_dir.join("config.toml"), "[project\n").unwrap();
+
+        // Act: Read a key as JSON and ask for the template directory
+        let json = run_procon(home.path(), home.path(), &["config", "project.cpp_standard", "--json"]);
+        let dir = run_procon(home.path(), home.path(), &["template", "dir"]);
+
+        // Assert: Verify both fail, the first with a JSON error object
+        assert!(!json.status.success());
+        assert!(json.stdout.is_empty());
+        let error: serde_json::Value = serde_json::from_slice(&json.stderr).unwrap();
+        assert!(error["error"].is_string());
+        assert!(!dir.status.success());
+        assert!(dir.stdout.is_empty());
+    }
+
+    /// Tests that `new --git-branch --git-commit` creates a repository with an initial commit.
+    ///
+    /// Skipped when git is not installed, since the feature shells out to it.
+    #[test]
+    fn test_new_with_git_branch_and_commit() {
+        if !procon_rs::git::is_available() {
+            return;
+        }
+
+        // Arrange: An isolated environment with a git identity provided via env vars
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+
+        // Act: Create a project with a named branch and an initial commit
+        let output = Command::new(env!("CARGO_BIN_EXE_procon_rs"))
+            .args(["new", "gitted", "--git-branch", "main", "--git-commit", "scaffold"])
+            .current_dir(work.path())
+            .env("HOME", home.path())
+            .env("XDG_CONFIG_HOME", home.path().join(".config"))
+            .env("GIT_CONFIG_NOSYSTEM", "1")
+            .env("GIT_AUTHOR_NAME", "Test")
+            .env("GIT_AUTHOR_EMAIL", "test@example.com")
+            .env("GIT_COMMITTER_NAME", "Test")
+            .env("GIT_COMMITTER_EMAIL", "test@example.com")
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+
+        // Assert: Verify the commit exists on the requested branch
+        let project = work.path().join("gitted");
+        let log = Command::new("git")
+            .args(["log", "--format=%s", "main"])
+            .current_dir(&project)
+            .output()
+            .unwrap();
+        assert!(log.status.success());
+        assert_eq!(String::from_utf8_lossy(&log.stdout).trim(), "scaffold");
+
+        // Assert: Verify the scaffolded files are tracked
+        let tracked = Command::new("git")
+            .args(["ls-files"])
+            .current_dir(&project)
+            .output()
+            .unwrap();
+        assert!(String::from_utf8_lossy(&tracked.stdout).contains("main.cpp"));
+    }
+
+    /// Tests that `templates dir` prints only the user template directory and creates nothing.
+    ///
+    /// The output is meant for shell substitution, so it must be the bare path.
+    #[test]
+    fn test_templates_dir_prints_template_directory() {
+        // Arrange: An isolated environment with no template directory yet
+        let home = TempDir::new().unwrap();
+        let expected = home.path().join(".config/procon_rs/templates");
+
+        // Act: Print the template and cache directories
+        let output = run_procon(home.path(), home.path(), &["templates", "dir"]);
+        let cache = run_procon(home.path(), home.path(), &["template", "dir", "--cache"]);
+
+        // Assert: Verify the bare paths were printed and neither directory was created
+        assert!(output.status.success());
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), expected.to_string_lossy());
+        assert_eq!(
+            String::from_utf8_lossy(&cache.stdout).trim(),
+            home.path().join(".config/procon_rs/cache").to_string_lossy()
+        );
+        assert!(!expected.exists());
+    }
+
+    /// Tests that `{{DATE}}` in a template file name produces a dated file name.
+    ///
+    /// The same date must be used in file names and contents of one project.
+    #[test]
+    fn test_new_substitutes_date_in_file_names() {
+        // Arrange: A user template with a dated notes file
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        create_user_template(
+            home.path(),
+            "dated",
+            &[("{{DATE}}_notes.md", "# {{PROJECT_NAME}} ({{DATE}})\n")],
+        );
+
+        // Act: Create a project from the template
+        let output = run_procon(home.path(), work.path(), &["new", "daily", "-t", "dated"]);
+        assert!(output.status.success());
+
+        // Assert: Verify exactly one YYYY-MM-DD_notes.md exists, dated like its content
+        let project = work.path().join("daily");
+        let notes: Vec<String> = fs::read_dir(&project)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .filter(|name| name.ends_with("_notes.md"))
+            .collect();
+        assert_eq!(notes.len(), 1);
+        let date = notes[0].trim_end_matches("_notes.md");
+        let date_pattern = regex::Regex::new(r"^\d{4}-\d{2}-\d{2}$").unwrap();
+        assert!(date_pattern.is_match(date));
+        let content = fs::read_to_string(project.join(&notes[0])).unwrap();
+        assert_eq!(content, format!("# daily ({})\n", date));
+    }
+
+    /// Tests that a file name made illegal by substitution is rejected.
+    ///
+    /// Unix-only because the fixture itself needs a name Windows can't store.
+    #[cfg(unix)]
+    #[test]
+    fn test_new_rejects_illegal_substituted_file_name() {
+        // Arrange: A user template whose file name contains a forbidden character
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        create_user_template(home.path(), "bad_name", &[("what?.md", "")]);
+
+        // Act: Create a project from the template
+        let output = run_procon(home.path(), work.path(), &["new", "p", "-t", "bad_name"]);
+
+        // Assert: Verify the invalid name is reported
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains("is not a valid file name"));
+    }
+
+    /// Tests that manifest-declared samples are copied by default and omitted with `--no-samples`.
+    ///
+    /// Judge-specific templates ship example cases; users who don't want them opt out.
+    #[test]
+    fn test_new_no_samples_omits_declared_samples() {
+        // Arrange: A user template shipping sample cases declared in its manifest
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        create_user_template(
+            home.path(),
+            "with_samples",
+            &[("template.toml", "samples = [\"tests/*\"]\n"), ("notes.md", "notes\n")],
+        );
+        let tests_dir = home.path().join(".config/procon_rs/templates/with_samples/tests");
+        fs::create_dir_all(&tests_dir).unwrap();
+        fs::write(tests_dir.join("01.in"), "1 2\n").unwrap();
+        fs::write(tests_dir.join("01.out"), "3\n").unwrap();
+
+        // Act: Create one project with samples and one without
+        let with = run_procon(home.path(), work.path(), &["new", "with", "-t", "with_samples"]);
+        let without = run_procon(
+            home.path(),
+            work.path(),
+            &["new", "without", "-t", "with_samples", "--no-samples"],
+        );
+
+        // Assert: Verify samples are only present in the default project
+        assert!(with.status.success() && without.status.success());
+        assert!(work.path().join("with/tests/01.in").exists());
+        assert!(work.path().join("with/tests/01.out").exists());
+        assert!(!work.path().join("without/tests/01.in").exists());
+        assert!(!work.path().join("without/tests/01.out").exists());
+        assert!(work.path().join("without/notes.md").exists());
+    }
+
+    /// Tests that `config --validate` exits non-zero and lists every problem in the file.
+    #[test]
+    fn test_config_validate_reports_all_problems() {
+        // Arrange: A hand-edited config with two invalid fields
+        let home = TempDir::new().unwrap();
+        let config_file = home.path().join("edited.toml");
+        fs::write(
+            &config_file,
+            "[template]\ndefault = \"default\"\npath = \"templates\"\n\n\
+             [project]\ncpp_standard = \"98\"\ncmake_minimum_version = \"3.x\"\n",
+        )
+        .unwrap();
+
+        // Act: Validate the file
+        let output = run_procon(
+            home.path(),
+            home.path(),
+            &["config", "--validate", config_file.to_str().unwrap()],
+        );
+
+        // Assert: Verify failure with both problems reported
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(!output.status.success());
+        assert!(stderr.contains("Unsupported C++ standard '98'"));
+        assert!(stderr.contains("Invalid CMake version '3.x'"));
+    }
+
+    /// Tests that `config --validate` succeeds with default settings when no config file exists.
+    #[test]
+    fn test_config_validate_without_config_file() {
+        // Arrange: A home with no config file
+        let home = TempDir::new().unwrap();
+
+        // Act: Validate the user's config
+        let output = run_procon(home.path(), home.path(), &["config", "--validate"]);
+
+        // Assert: Verify the defaults are reported valid
+        assert!(output.status.success());
+        assert!(String::from_utf8_lossy(&output.stdout).contains("Configuration is valid"));
+    }
+
+    /// Tests that `--print-template-source` reports a user-dir template as `user` with its path.
+    ///
+    /// Users rely on this to confirm their customized template shadowed the builtin.
+    #[test]
+    fn test_new_print_template_source_reports_user() {
+        // Arrange: A user template shadowing the builtin default
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        create_user_template(home.path(), "default", &[]);
+
+        // Act: Create a project asking for the template source
+        let output = run_procon(
+            home.path(),
+            work.path(),
+            &["new", "sourced", "--print-template-source"],
+        );
+
+        // Assert: Verify the user template and its directory are reported
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(output.status.success());
+        assert!(stdout.contains("Template source: user ("));
+        assert!(stdout.contains("procon_rs/templates/default"));
+    }
+
+    /// Tests that the template source is part of the JSON output and shown by `-v`.
+    #[test]
+    fn test_new_template_source_in_json_and_verbose() {
+        // Arrange: A user template shadowing the builtin default
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        create_user_template(home.path(), "default", &[]);
+
+        // Act: Create one project with JSON output and one verbosely
+        let json = run_procon(home.path(), work.path(), &["--format", "json", "new", "as_json"]);
+        let verbose = run_procon(home.path(), work.path(), &["new", "as_text", "-v"]);
+
+        // Assert: Verify both report the user template and its directory
+        assert!(json.status.success(), "{}", String::from_utf8_lossy(&json.stderr));
+        let result: serde_json::Value = serde_json::from_slice(&json.stdout).unwrap();
+        assert_eq!(result["template_source"], "user");
+        assert!(result["template_path"].as_str().unwrap().ends_with("procon_rs/templates/default"));
+        assert!(String::from_utf8_lossy(&verbose.stdout).contains("Template source: user ("));
+    }
+
+    /// Tests that `init` keeps an existing CMakeLists and fills in the missing template files.
+    #[test]
+    fn test_init_keeps_existing_cmake_lists() {
+        // Arrange: A directory with a hand-written CMakeLists
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        let cmake = "project(my_solver)\nset(CMAKE_CXX_STANDARD 20)\n";
+        fs::write(work.path().join("CMakeLists.txt"), cmake).unwrap();
+
+        // Act: Initialize the directory
+        let output = run_procon(home.path(), work.path(), &["init"]);
+
+        // Assert: Verify the CMakeLists survived and its settings were picked up
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(output.status.success());
+        assert_eq!(fs::read_to_string(work.path().join("CMakeLists.txt")).unwrap(), cmake);
+        assert!(work.path().join("main.cpp").exists());
+        assert!(stdout.contains("my_solver"));
+        assert!(stdout.contains("C++20"));
+    }
+
+    /// Tests that `remove` deletes scaffolded files but keeps files the user added.
+    ///
+    /// The ledger makes removal a safe undo: only what procon_rs wrote is touched.
+    #[test]
+    fn test_remove_keeps_user_files() {
+        // Arrange: A project with an extra user file
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        let project = work.path().join("undo_me");
+        assert!(run_procon(home.path(), work.path(), &["new", "undo_me"]).status.success());
+        fs::write(project.join("notes.txt"), "mine\n").unwrap();
+
+        // Act: Remove the project without prompting
+        let output = run_procon(home.path(), work.path(), &["remove", "undo_me", "--yes"]);
+
+        // Assert: Verify the scaffold is gone and the user file survived
+        assert!(output.status.success());
+        assert!(!project.join("main.cpp").exists());
+        assert!(!project.join("CMakeLists.txt").exists());
+        assert!(!project.join(".procon").exists());
+        assert_eq!(fs::read_to_string(project.join("notes.txt")).unwrap(), "mine\n");
+    }
+
+    /// Tests that `init` inside a project made by `new` keeps its files in the ledger.
+    #[test]
+    fn test_remove_after_new_and_init() {
+        // Arrange: A project created by new, then initialized again
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        let project = work.path().join("layered");
+        assert!(run_procon(home.path(), work.path(), &["new", "layered"]).status.success());
+        assert!(run_procon(home.path(), &project, &["init"]).status.success());
+
+        // Act: Remove the project without prompting
+        let output = run_procon(home.path(), work.path(), &["remove", "layered", "--yes"]);
+
+        // Assert: Verify the files new wrote were removed as well
+        assert!(output.status.success());
+        assert!(!project.join("main.cpp").exists());
+        assert!(!project.join("CMakeLists.txt").exists());
+        assert!(!project.join(".procon").exists());
+    }
+
+    /// Tests that `remove` aborts without deleting anything when not confirmed.
+    #[test]
+    fn test_remove_requires_confirmation() {
+        // Arrange: A freshly created project
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        assert!(run_procon(home.path(), work.path(), &["new", "kept"]).status.success());
+
+        // Act: Run remove with no answer on stdin
+        let output = run_procon(home.path(), work.path(), &["remove", "kept"]);
+
+        // Assert: Verify nothing was removed
+        assert!(output.status.success());
+        assert!(work.path().join("kept/main.cpp").exists());
+    }
+
+    /// Tests that `--no-emoji` and `PROCON_NO_EMOJI` replace emoji with ASCII markers.
+    ///
+    /// Terminals that render emoji as boxes still need readable status lines.
+    #[test]
+    fn test_no_emoji_uses_ascii_markers() {
+        // Arrange: An isolated home and working directory
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+
+        // Act: Create projects with the flag and with the environment variable
+        let flag = run_procon(home.path(), work.path(), &["new", "plain", "--no-emoji"]);
+        let env = run_procon_with_env(
+            home.path(),
+            work.path(),
+            &["new", "plain_env"],
+            &[("PROCON_NO_EMOJI", "1")],
+        );
+
+        // Assert: Verify ASCII markers and no emoji in either run
+        for output in [flag, env] {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            assert!(output.status.success());
+            assert!(stdout.contains("[+] Creating project"));
+            assert!(stdout.contains("[ok] Project"));
+            assert!(!stdout.contains('✨') && !stdout.contains('✅'));
+        }
+    }
+
+    /// Tests that `--check-name-available` reports free and taken names without creating anything.
+    ///
+    /// Scripts rely on the exit code to decide whether to create a project.
+    #[test]
+    fn test_new_check_name_available() {
+        // Arrange: One existing project directory
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        fs::create_dir(work.path().join("taken")).unwrap();
+
+        // Act: Check a free and a taken name
+        let free = run_procon(home.path(), work.path(), &["new", "free", "--check-name-available"]);
+        let taken = run_procon(home.path(), work.path(), &["new", "taken", "--check-name-available"]);
+
+        // Assert: Verify exit codes, JSON and that nothing was created
+        let free_json: serde_json::Value = serde_json::from_slice(&free.stdout).unwrap();
+        let taken_json: serde_json::Value = serde_json::from_slice(&taken.stdout).unwrap();
+        assert_eq!(free.status.code(), Some(0));
+        assert_eq!(taken.status.code(), Some(1));
+        assert_eq!(free_json["available"], true);
+        assert_eq!(taken_json["available"], false);
+        assert!(!work.path().join("free").exists());
+    }
+
+    /// Tests that `--canonicalize` prints the real path when creating under a symlinked base.
+    ///
+    /// Downstream tooling compares paths, so symlinks in the output cause mismatches.
+    #[cfg(unix)]
+    #[test]
+    fn test_new_canonicalize_resolves_symlinked_base() {
+        // Arrange: A symlink pointing at the real working directory
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        let real = work.path().join("real");
+        let link = work.path().join("link");
+        fs::create_dir(&real).unwrap();
+        std::os::unix::fs::symlink(&real, &link).unwrap();
+
+        // Act: Create a project through the symlink
+        let output = run_procon(
+            home.path(),
+            work.path(),
+            &["new", "canon", "-p", link.to_str().unwrap(), "--canonicalize"],
+        );
+
+        // Assert: Verify the printed path is the resolved one
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let expected = fs::canonicalize(real.join("canon")).unwrap();
+        assert!(output.status.success());
+        assert!(stdout.contains(&format!("Path: {}", expected.display())));
+        assert!(!stdout.contains("link/canon"));
+    }
+
+    /// Tests that setting template.path warns only when the directory doesn't exist.
+    ///
+    /// The value is still accepted, but a typo should be caught right away.
+    #[test]
+    fn test_config_set_template_path_warns_when_missing() {
+        // Arrange: One existing and one missing directory
+        let home = TempDir::new().unwrap();
+        let existing = home.path().join("templates");
+        fs::create_dir(&existing).unwrap();
+        let missing = home.path().join("tempaltes");
+
+        // Act: Set each as the template path
+        let warned = run_procon(
+            home.path(),
+            home.path(),
+            &["config", "template.path", missing.to_str().unwrap()],
+        );
+        let quiet = run_procon(
+            home.path(),
+            home.path(),
+            &["config", "template.path", existing.to_str().unwrap()],
+        );
+
+        // Assert: Verify both succeed and only the missing path warns
+        assert!(warned.status.success() && quiet.status.success());
+        assert!(String::from_utf8_lossy(&warned.stderr).contains("won't be found there"));
+        assert!(!String::from_utf8_lossy(&quiet.stderr).contains("won't be found there"));
+    }
+
+    /// Tests that two runs with `SOURCE_DATE_EPOCH` set produce byte-identical projects.
+    ///
+    /// Packaging and caching need reproducible output, including dated files and names.
+    #[test]
+    fn test_new_reproducible_with_source_date_epoch() {
+        // Arrange: A template using the date in a file name and in content
+        let home = TempDir::new().unwrap();
+        let first = TempDir::new().unwrap();
+        let second = TempDir::new().unwrap();
+        create_user_template(home.path(), "dated", &[("{{DATE}}_notes.md", "Created {{DATE}}\n")]);
+        let epoch = [("SOURCE_DATE_EPOCH", "1700000000")];
+
+        // Act: Create the same project twice in different directories
+        let args = ["new", "repro", "-t", "dated"];
+        assert!(run_procon_with_env(home.path(), first.path(), &args, &epoch).status.success());
+        assert!(run_procon_with_env(home.path(), second.path(), &args, &epoch).status.success());
+
+        // Assert: Verify the epoch's date was used and every scaffolded file matches
+        let notes = "repro/2023-11-14_notes.md";
+        assert_eq!(
+            fs::read_to_string(first.path().join(notes)).unwrap(),
+            "Created 2023-11-14\n"
+        );
+        for file in [notes, "repro/main.cpp", "repro/CMakeLists.txt"] {
+            assert_eq!(
+                fs::read(first.path().join(file)).unwrap(),
+                fs::read(second.path().join(file)).unwrap()
+            );
+        }
+    }
+
+    /// Tests that an incomplete template loads under `--allow-incomplete` and fails without it.
+    ///
+    /// The bypass must stay opt-in, and it must warn about what is missing.
+    #[test]
+    fn test_new_allow_incomplete_template() {
+        // Arrange: A user template without CMakeLists.txt
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        let template_dir = home.path().join(".config/procon_rs/templates/draft");
+        fs::create_dir_all(&template_dir).unwrap();
+        fs::write(template_dir.join("main.cpp"), "// {{PROJECT_NAME}}\n").unwrap();
+
+        // Act: Create a project with and without the flag
+        let strict = run_procon(home.path(), work.path(), &["new", "strict", "-t", "draft"]);
+        let lenient = run_procon(
+            home.path(),
+            work.path(),
+            &["new", "lenient", "-t", "draft", "--allow-incomplete"],
+        );
+
+        // Assert: Verify only the flagged run succeeds, with a warning
+        assert!(!strict.status.success());
+        assert!(!work.path().join("strict").exists());
+        assert!(lenient.status.success());
+        assert_eq!(
+            fs::read_to_string(work.path().join("lenient/main.cpp")).unwrap(),
+            "// lenient\n"
+        );
+        assert!(String::from_utf8_lossy(&lenient.stderr).contains("missing CMakeLists.txt"));
+    }
+
+    /// Tests that `template show --tree` renders a user template's nested files.
+    #[test]
+    fn test_template_show_tree() {
+        // Arrange: A user template with a nested header
+        let home = TempDir::new().unwrap();
+        create_user_template(home.path(), "nested", &[]);
+        let lib_dir = home.path().join(".config/procon_rs/templates/nested/lib");
+        fs::create_dir_all(&lib_dir).unwrap();
+        fs::write(lib_dir.join("io.hpp"), "#pragma once\n").unwrap();
+
+        // Act: Show the template as a tree
+        let output = run_procon(home.path(), home.path(), &["template", "show", "nested", "--tree"]);
+
+        // Assert: Verify the tree output
+        assert!(output.status.success());
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout),
+            "nested\n├── CMakeLists.txt\n├── lib\n│   └── io.hpp\n└── main.cpp\n"
+        );
+    }
+
+    /// Tests that a value set by one invocation is read back by the next.
+    ///
+    /// This exercises the full set, save, load and get path through the CLI.
+    #[test]
+    fn test_config_set_then_get_across_invocations() {
+        // Arrange: An isolated home without a config file
+        let home = TempDir::new().unwrap();
+
+        // Act: Set a key, then read it back in a separate process
+        let set = run_procon(home.path(), home.path(), &["config", "project.cpp_standard", "20"]);
+        let get = run_procon(home.path(), home.path(), &["config", "project.cpp_standard"]);
+
+        // Assert: Verify the saved value is reported and the file was written
+        assert!(set.status.success() && get.status.success());
+        assert!(String::from_utf8_lossy(&get.stdout).contains("project.cpp_standard = 20"));
+        assert!(home.path().join(".config/procon_rs/config.toml").is_file());
+    }
+
+    /// Tests that `config` can fix an unsupported standard in a hand-edited file.
+    ///
+    /// Every other command refuses such a file, so this must not be one of them.
+    #[test]
+    fn test_config_set_fixes_unsupported_cpp_standard() {
+        // Arrange: A config file with a typo in the standard
+        let home = TempDir::new().unwrap();
+        run_procon(home.path(), home.path(), &["config", "project.cpp_standard", "20"]);
+        let config_file = home.path().join(".config/procon_rs/config.toml");
+        let content = fs::read_to_string(&config_file).unwrap();
+        fs::write(&config_file, content.replace("\"20\"", "\"cpp18\"")).unwrap();
+        assert!(!run_procon(home.path(), home.path(), &["config", "project.cpp_standard"]).status.success());
+
+        // Act: Set a supported standard, then read it back
+        let set = run_procon(home.path(), home.path(), &["config", "project.cpp_standard", "17"]);
+        let get = run_procon(home.path(), home.path(), &["config", "project.cpp_standard"]);
+
+        // Assert: Verify the value was replaced and the file loads again
+        assert!(set.status.success(), "{}", String::from_utf8_lossy(&set.stderr));
+        assert!(get.status.success(), "{}", String::from_utf8_lossy(&get.stderr));
+        assert!(String::from_utf8_lossy(&get.stdout).contains("project.cpp_standard = 17"));
+    }
+
+    /// Tests that getting an unknown key fails instead of printing a placeholder.
+    #[test]
+    fn test_config_get_unknown_key_fails() {
+        // Arrange: An isolated home
+        let home = TempDir::new().unwrap();
+
+        // Act: Get a key that doesn't exist
+        let output = run_procon(home.path(), home.path(), &["config", "project.nope"]);
+
+        // Assert: Verify a non-zero exit naming the key
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains("Unknown configuration key: project.nope"));
+    }
+
+    /// Tests that a configured C++ standard is used by a later `new`.
+    #[test]
+    fn test_config_set_affects_new() {
+        // Arrange: Configure C++20
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        run_procon(home.path(), work.path(), &["config", "project.cpp_standard", "20"]);
+
+        // Act: Create a project
+        let output = run_procon(home.path(), work.path(), &["new", "configured"]);
+
+        // Assert: Verify the generated CMakeLists uses the configured standard
+        assert!(output.status.success());
+        let cmake = fs::read_to_string(work.path().join("configured/CMakeLists.txt")).unwrap();
+        assert!(cmake.contains("set(CMAKE_CXX_STANDARD 20)"));
+    }
+
+    /// Tests that `list-templates` shows builtin and user templates, sorted and deduplicated.
+    #[test]
+    fn test_list_templates_shows_user_templates() {
+        // Arrange: Two user templates, one shadowing the builtin, and one incomplete dir
+        let home = TempDir::new().unwrap();
+        create_user_template(home.path(), "zeta", &[]);
+        create_user_template(home.path(), "alpha", &[]);
+        create_user_template(home.path(), "default", &[]);
+        fs::create_dir_all(home.path().join(".config/procon_rs/templates/broken")).unwrap();
+
+        // Act: List the templates
+        let output = run_procon(home.path(), home.path(), &["list-templates"]);
+
+        // Assert: Verify order, sources and that the incomplete dir is omitted
+        assert!(output.status.success());
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout),
+            "advanced (builtin)\nalpha (user)\ndefault (user, overrides builtin)\nzeta (user)\n"
+        );
+    }
+
+    /// Tests that manifest variables are substituted with their declared defaults.
+    #[test]
+    fn test_new_substitutes_manifest_variable_defaults() {
+        // Arrange: A template declaring an AUTHOR variable
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        create_user_template(
+            home.path(),
+            "authored",
+            &[
+                ("template.toml", "[variables]\nAUTHOR = \"anonymous\"\n"),
+                ("NOTES.md", "by {{AUTHOR}}, {{UNDECLARED}}\n"),
+            ],
+        );
+
+        // Act: Create a project from it
+        let output = run_procon(home.path(), work.path(), &["new", "authored", "-t", "authored"]);
+
+        // Assert: Verify the default was used and unknown placeholders were kept
+        assert!(output.status.success());
+        assert_eq!(
+            fs::read_to_string(work.path().join("authored/NOTES.md")).unwrap(),
+            "by anonymous, {{UNDECLARED}}\n"
+        );
+    }
+
+    /// Tests that two `--define` variables are substituted in main.cpp.
+    ///
+    /// Definitions on the command line also override manifest defaults.
+    #[test]
+    fn test_new_define_substitutes_variables() {
+        // Arrange: A template using two custom variables, one with a manifest default
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        create_user_template(
+            home.path(),
+            "defined",
+            &[("template.toml", "[variables]\nJUDGE = \"none\"\n")],
+        );
+        fs::write(
+            home.path().join(".config/procon_rs/templates/defined/main.cpp"),
+            "// {{AUTHOR}} @ {{JUDGE}}\n",
+        )
+        .unwrap();
+
+        // Act: Create a project defining both variables
+        let output = run_procon(
+            home.path(),
+            work.path(),
+            &["new", "defs", "-t", "defined", "-D", "AUTHOR=alice", "--define", "JUDGE=atcoder"],
+        );
+
+        // Assert: Verify both were substituted
+        assert!(output.status.success());
+        assert_eq!(
+            fs::read_to_string(work.path().join("defs/main.cpp")).unwrap(),
+            "// alice @ atcoder\n"
+        );
+    }
+
+    /// Tests that a malformed `--define` is rejected before anything is created.
+    #[test]
+    fn test_new_define_rejects_malformed() {
+        // Arrange: An isolated home and working directory
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+
+        // Act: Pass a definition without '='
+        let output = run_procon(home.path(), work.path(), &["new", "bad", "-D", "AUTHOR"]);
+
+        // Assert: Verify the argument error
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains("expected KEY=VALUE"));
+        assert!(!work.path().join("bad").exists());
+    }
+
+    /// Tests that `new --git` creates a repository with the generated files committed.
+    ///
+    /// Skipped when git is not installed, since the feature shells out to it.
+    #[test]
+    fn test_new_git_commits_generated_files() {
+        if !procon_rs::git::is_available() {
+            return;
+        }
+
+        // Arrange: An isolated environment with a git identity provided via env vars
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        let identity = [
+            ("GIT_CONFIG_NOSYSTEM", "1"),
+            ("GIT_AUTHOR_NAME", "Test"),
+            ("GIT_AUTHOR_EMAIL", "test@example.com"),
+            ("GIT_COMMITTER_NAME", "Test"),
+            ("GIT_COMMITTER_EMAIL", "test@example.com"),
+        ];
+
+        // Act: Create a project with --git only
+        let output = run_procon_with_env(home.path(), work.path(), &["new", "versioned", "--git"], &identity);
+
+        // Assert: Verify the repository exists and .gitignore is committed
+        let project = work.path().join("versioned");
+        assert!(output.status.success());
+        assert!(project.join(".git").is_dir());
+        let tracked = Command::new("git")
+            .args(["ls-files"])
+            .current_dir(&project)
+            .output()
+            .unwrap();
+        let tracked = String::from_utf8_lossy(&tracked.stdout);
+        assert!(tracked.contains(".gitignore"));
+        assert!(tracked.contains("main.cpp"));
+    }
+
+    /// Tests that `new --git` still succeeds, with a warning, when git is not on PATH.
+    #[test]
+    fn test_new_git_without_git_warns() {
+        // Arrange: An environment whose PATH contains no git
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        let empty_path = home.path().to_str().unwrap();
+
+        // Act: Create a project with --git
+        let output = run_procon_with_env(
+            home.path(),
+            work.path(),
+            &["new", "no_git", "--git"],
+            &[("PATH", empty_path)],
+        );
+
+        // Assert: Verify the project exists and the problem was reported
+        assert!(output.status.success());
+        assert!(work.path().join("no_git/main.cpp").exists());
+        assert!(String::from_utf8_lossy(&output.stderr).contains("git not found on PATH"));
+    }
+
+    /// Tests that `{{DATE}}` and `{{YEAR}}` render as a date and its year.
+    ///
+    /// Both come from one timestamp, so a header can never mix two different days.
+    #[test]
+    fn test_new_substitutes_date_and_year() {
+        // Arrange: A template with a dated header
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        create_user_template(home.path(), "header", &[("HEADER.txt", "{{DATE}} (c) {{YEAR}}\n")]);
+
+        // Act: Create a project
+        let output = run_procon(home.path(), work.path(), &["new", "dated", "-t", "header"]);
+
+        // Assert: Verify the date format and that the year matches the date
+        assert!(output.status.success());
+        let header = fs::read_to_string(work.path().join("dated/HEADER.txt")).unwrap();
+        let pattern = regex::Regex::new(r"^(\d{4})-\d{2}-\d{2} \(c\) (\d{4})\n$").unwrap();
+        let captures = pattern.captures(&header).unwrap();
+        assert_eq!(&captures[1], &captures[2]);
+    }
+
+    /// Tests that `new --dry-run` lists the planned files without creating the project.
+    #[test]
+    fn test_new_dry_run_creates_nothing() {
+        // Arrange: An empty working directory
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+
+        // Act: Preview a project
+        let output = run_procon(home.path(), work.path(), &["new", "preview", "--dry-run"]);
+
+        // Assert: Verify the files were listed but the directory was never created
+        assert!(output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("main.cpp ("));
+        assert!(stdout.contains("bytes)"));
+        assert!(!work.path().join("preview").exists());
+    }
+
+    /// Tests that `new --dry-run` still fails when the project already exists.
+    #[test]
+    fn test_new_dry_run_fails_for_existing_project() {
+        // Arrange: A directory already taken by the project name
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        fs::create_dir(work.path().join("taken")).unwrap();
+
+        // Act: Preview a project with that name
+        let output = run_procon(home.path(), work.path(), &["new", "taken", "--dry-run"]);
+
+        // Assert: Verify the command failed
+        assert!(!output.status.success());
+    }
+
+    /// Creates a bare git repository holding `files`, returning its `file://` URL.
+    fn create_bare_template_repo(root: &Path, files: &[(&str, &str)]) -> String {
+        let work = root.join("work");
+        let bare = root.join("template.git");
+        fs::create_dir_all(&work).unwrap();
+        for (path, content) in files {
+            fs::write(work.join(path), content).unwrap();
+        }
+        let git = |args: &[&str], dir: &Path| {
+            let status = Command::new("git")
+                .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .current_dir(dir)
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {:?} failed", args);
+        };
+        git(&["init", "--quiet"], &work);
+        git(&["add", "-A"], &work);
+        git(&["commit", "--quiet", "-m", "template"], &work);
+        git(&["clone", "--quiet", "--bare", "work", "template.git"], root);
+        format!("file://{}", bare.display())
+    }
+
+    /// Tests that `new` can use a template fetched from a `git+` URL.
+    ///
+    /// Uses a local bare repository so the test needs no network access.
+    #[test]
+    fn test_new_with_git_template() {
+        if !procon_rs::git::is_available() {
+            return;
+        }
+
+        // Arrange: A bare repository containing a template
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        let repos = TempDir::new().unwrap();
+        let url = create_bare_template_repo(
+            repos.path(),
+            &[
+                ("main.cpp", "// remote\nint main() {}\n"),
+                ("CMakeLists.txt", "project({{PROJECT_NAME}})\n"),
+            ],
+        );
+        let template = format!("git+{}", url);
+
+        // Act: Create a project from the repository
+        let output = run_procon(home.path(), work.path(), &["new", "remote", "-t", &template]);
+
+        // Assert: Verify the files were substituted and git metadata was not copied
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        let project = work.path().join("remote");
+        assert_eq!(fs::read_to_string(project.join("CMakeLists.txt")).unwrap(), "project(remote)\n");
+        assert!(fs::read_to_string(project.join("main.cpp")).unwrap().starts_with("// remote"));
+        assert!(!project.join(".git").exists());
+    }
+
+    /// Tests that a `git+` URL to a repository that isn't a template fails descriptively.
+    #[test]
+    fn test_new_with_git_template_rejects_non_template_repo() {
+        if !procon_rs::git::is_available() {
+            return;
+        }
+
+        // Arrange: A bare repository without CMakeLists.txt
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        let repos = TempDir::new().unwrap();
+        let url = create_bare_template_repo(repos.path(), &[("README.md", "not a template\n")]);
+        let template = format!("git+{}", url);
+
+        // Act: Create a project from the repository
+        let output = run_procon(home.path(), work.path(), &["new", "remote", "-t", &template]);
+
+        // Assert: Verify the fetch error names the URL and no project was created
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("Failed to fetch template"));
+        assert!(stderr.contains("not a template"));
+        assert!(!work.path().join("remote").exists());
+    }
+
+    /// Tests that an unreachable `git+` URL fails with a fetch error.
+    #[test]
+    fn test_new_with_unreachable_git_template() {
+        if !procon_rs::git::is_available() {
+            return;
+        }
+
+        // Arrange: A URL pointing at a repository that doesn't exist
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        let template = format!("git+file://{}/missing.git", work.path().display());
+
+        // Act: Create a project from the URL
+        let output = run_procon(home.path(), work.path(), &["new", "remote", "-t", &template]);
+
+        // Assert: Verify the failure is reported as a fetch error
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains("Failed to fetch template"));
+    }
+
+    /// Tests that a project whose files fail to write midway is removed again.
+    ///
+    /// A leftover half-written directory would block a retry with "already exists".
+    /// A template file named `.procon` makes writing the creation record fail after
+    /// the template files were copied.
+    #[test]
+    fn test_new_rolls_back_failed_project() {
+        // Arrange: A template that breaks the .procon record, and an existing base directory
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        create_user_template(home.path(), "broken", &[(".procon", "not a directory\n")]);
+        let base = work.path().join("contests");
+        fs::create_dir(&base).unwrap();
+
+        // Act: Create a project inside the base directory
+        let output = run_procon(
+            home.path(),
+            work.path(),
+            &["new", "partial", "-t", "broken", "-p", base.to_str().unwrap()],
+        );
+
+        // Assert: Verify the project directory is gone but the base directory is kept
+        assert!(!output.status.success());
+        assert!(!base.join("partial").exists());
+        assert!(base.is_dir());
+    }
+
+    /// Tests that `new --list-vars` separates builtin variables from ones needing `--define`.
+    #[test]
+    fn test_new_list_vars() {
+        // Arrange: A template using a builtin and a custom variable
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        create_user_template(home.path(), "authored", &[("AUTHORS", "{{AUTHOR}}\n")]);
+
+        // Act: List the variables
+        let output = run_procon(home.path(), work.path(), &["new", "x", "-t", "authored", "--list-vars"]);
+
+        // Assert: Verify both variables are classified and nothing was created
+        assert!(output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("AUTHOR (requires --define)"));
+        assert!(stdout.contains("PROJECT_NAME (builtin)"));
+        assert!(!work.path().join("x").exists());
+    }
+
+    /// Tests that `completions bash` prints a script that knows the subcommands.
+    #[test]
+    fn test_completions_bash() {
+        // Arrange: An isolated environment
+        let home = TempDir::new().unwrap();
+
+        // Act: Generate bash completions
+        let output = run_procon(home.path(), home.path(), &["completions", "bash"]);
+
+        // Assert: Verify a completion function mentioning `new` was printed
+        assert!(output.status.success());
+        let script = String::from_utf8_lossy(&output.stdout);
+        assert!(script.contains("_procon_rs()"));
+        assert!(script.contains("new"));
+    }
+
+    /// Tests that `--quiet` keeps stdout empty when a project is created successfully.
+    #[test]
+    fn test_new_quiet_prints_nothing() {
+        // Arrange: An isolated environment
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+
+        // Act: Create a project quietly
+        let output = run_procon(home.path(), work.path(), &["--quiet", "new", "silent"]);
+
+        // Assert: Verify the project exists and nothing was printed
+        assert!(output.status.success());
+        assert!(work.path().join("silent/main.cpp").exists());
+        assert!(output.stdout.is_empty());
+        assert!(output.stderr.is_empty());
+    }
+
+    /// Tests that `--verbose` lists every file written.
+    #[test]
+    fn test_new_verbose_lists_files() {
+        // Arrange: An isolated environment
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+
+        // Act: Create a project verbosely
+        let output = run_procon(home.path(), work.path(), &["new", "chatty", "-v"]);
+
+        // Assert: Verify each template file was reported
+        assert!(output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("Wrote "));
+        assert!(stdout.contains("main.cpp"));
+        assert!(stdout.contains("CMakeLists.txt"));
+    }
+
+    /// Tests that `--format json` prints the created project as a single JSON object.
+    #[test]
+    fn test_new_format_json() {
+        // Arrange: An isolated environment
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+
+        // Act: Create a project with JSON output
+        let output = run_procon(home.path(), work.path(), &["--format", "json", "new", "structured"]);
+
+        // Assert: Verify stdout is one object describing the project
+        assert!(output.status.success());
+        let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(result["name"], "structured");
+        assert_eq!(result["template"], "default");
+        assert!(result["path"].as_str().unwrap().ends_with("structured"));
+        let files = result["files"].as_array().unwrap();
+        assert!(files.iter().any(|file| file == "main.cpp"));
+    }
+
+    /// Tests that `--format json` reports errors as a JSON object on stderr.
+    #[test]
+    fn test_new_format_json_error() {
+        // Arrange: An isolated environment
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+
+        // Act: Create a project from a missing template
+        let output = run_procon(
+            home.path(),
+            work.path(),
+            &["new", "broken", "-t", "missing", "--format", "json"],
+        );
+
+        // Assert: Verify a non-zero exit with only a JSON error
+        assert!(!output.status.success());
+        assert!(output.stdout.is_empty());
+        let error: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+        assert!(error["error"].as_str().unwrap().contains("Template 'missing' not found"));
+    }
+
+    /// Tests that `--format json` keeps stderr empty, reporting `--open`'s hint as a warning.
+    #[test]
+    fn test_new_format_json_open_hint_is_a_warning() {
+        // Arrange: An environment without any editor to find
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+
+        // Act: Create a project with JSON output and --open
+        let output = run_procon_with_env(
+            home.path(),
+            work.path(),
+            &["--format", "json", "new", "unopened", "--open"],
+            &[("EDITOR", ""), ("VISUAL", ""), ("PATH", "")],
+        );
+
+        // Assert: Verify the hint is among the warnings and stderr stayed empty
+        assert!(output.status.success());
+        assert!(output.stderr.is_empty(), "{}", String::from_utf8_lossy(&output.stderr));
+        let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        let warnings = result["warnings"].as_array().unwrap();
+        assert!(warnings.iter().any(|warning| warning.as_str().unwrap().contains("No editor found")));
+    }
+
+    /// Tests that `contest`, `init` and `template new` print a JSON object with `--format json`.
+    #[test]
+    fn test_creating_commands_format_json() {
+        // Arrange: An isolated environment with an empty directory to initialize
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        let existing = work.path().join("existing");
+        fs::create_dir(&existing).unwrap();
+
+        // Act: Run each creating command with JSON output
+        let contest = run_procon(
+            home.path(),
+            work.path(),
+            &["--format", "json", "contest", "abc300", "--count", "2"],
+        );
+        let init = run_procon(home.path(), &existing, &["--format", "json", "init"]);
+        let template = run_procon(home.path(), work.path(), &["--format", "json", "template", "new", "mine"]);
+
+        // Assert: Verify each printed only one object describing what it created
+        for output in [&contest, &init, &template] {
+            assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+            assert!(output.stderr.is_empty());
+        }
+        let contest: serde_json::Value = serde_json::from_slice(&contest.stdout).unwrap();
+        assert_eq!(contest["name"], "abc300");
+        assert_eq!(contest["problems"][1]["id"], "B");
+        assert!(contest["problems"][1]["path"].as_str().unwrap().ends_with("abc300/B"));
+        let init: serde_json::Value = serde_json::from_slice(&init.stdout).unwrap();
+        assert_eq!(init["name"], "existing");
+        assert!(init["created"].as_array().unwrap().iter().any(|file| file == "main.cpp"));
+        let template: serde_json::Value = serde_json::from_slice(&template.stdout).unwrap();
+        assert_eq!(template["name"], "mine");
+        assert!(template["path"].as_str().unwrap().ends_with("templates/mine"));
+    }
+
+    /// Creates a template whose `tests/` folder is only included with `WITH_TESTS`.
+    fn create_conditional_template(home: &Path) {
+        create_user_template(
+            home,
+            "harness",
+            &[("template.toml", "[conditional_files]\n\"tests/\" = \"WITH_TESTS\"\n")],
+        );
+        let tests_dir = home.join(".config/procon_rs/templates/harness/tests");
+        fs::create_dir_all(&tests_dir).unwrap();
+        fs::write(tests_dir.join("test_main.cpp"), "// tests for {{PROJECT_NAME}}\n").unwrap();
+    }
+
+    /// Tests that conditional files are left out when their variable is not defined.
+    #[test]
+    fn test_new_omits_conditional_files_without_define() {
+        // Arrange: A template with a conditional test harness
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        create_conditional_template(home.path());
+
+        // Act: Create a project without WITH_TESTS, and one with it switched off
+        let plain = run_procon(home.path(), work.path(), &["new", "plain", "-t", "harness"]);
+        let off = run_procon(home.path(), work.path(), &["new", "off", "-t", "harness", "-D", "WITH_TESTS=0"]);
+
+        // Assert: Verify neither project has the test harness
+        assert!(plain.status.success());
+        assert!(off.status.success());
+        assert!(work.path().join("plain/main.cpp").exists());
+        assert!(!work.path().join("plain/tests/test_main.cpp").exists());
+        assert!(!work.path().join("off/tests").exists());
+    }
+
+    /// Tests that conditional files are included when their variable is defined.
+    #[test]
+    fn test_new_includes_conditional_files_with_define() {
+        // Arrange: A template with a conditional test harness
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        create_conditional_template(home.path());
+
+        // Act: Create a project with WITH_TESTS
+        let output = run_procon(home.path(), work.path(), &["new", "tested", "-t", "harness", "-D", "WITH_TESTS=1"]);
+
+        // Assert: Verify the harness was created and substituted
+        assert!(output.status.success());
+        let harness = fs::read_to_string(work.path().join("tested/tests/test_main.cpp")).unwrap();
+        assert_eq!(harness, "// tests for tested\n");
+    }
+
+    /// Tests that a template's post_create hooks run inside the new project.
+    ///
+    /// Skipped on Windows, where the hook command would need `cmd` syntax.
+    #[test]
+    fn test_new_runs_template_hooks() {
+        if cfg!(windows) {
+            return;
+        }
+
+        // Arrange: A template declaring a harmless hook
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        let manifest = "[hooks]\npost_create = [\"touch hookran\"]\n";
+        create_user_template(home.path(), "hooked", &[("template.toml", manifest)]);
+
+        // Act: Create a project, and another with --no-hooks
+        let output = run_procon(home.path(), work.path(), &["new", "with_hook", "-t", "hooked"]);
+        let skipped = run_procon(home.path(), work.path(), &["new", "no_hook", "-t", "hooked", "--no-hooks"]);
+
+        // Assert: Verify the hook ran only when hooks were allowed
+        assert!(output.status.success());
+        assert!(skipped.status.success());
+        assert!(work.path().join("with_hook/hookran").exists());
+        assert!(!work.path().join("no_hook/hookran").exists());
+    }
+
+    /// Tests that a failing template hook fails creation, naming the command.
+    #[test]
+    fn test_new_failing_template_hook() {
+        if cfg!(windows) {
+            return;
+        }
+
+        // Arrange: A template whose hook exits with an error
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        let manifest = "[hooks]\npost_create = [\"echo boom >&2; exit 3\"]\n";
+        create_user_template(home.path(), "failing", &[("template.toml", manifest)]);
+
+        // Act: Create a project
+        let output = run_procon(home.path(), work.path(), &["new", "doomed", "-t", "failing"]);
+
+        // Assert: Verify the error names the command and its stderr, and nothing is left
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("Failed to create project: template hook"));
+        assert!(stderr.contains("exit 3"));
+        assert!(stderr.contains("boom"));
+        assert!(!work.path().join("doomed").exists());
+    }
+
+    /// Tests that a template path escaping the project fails creation, naming the path.
+    #[test]
+    fn test_new_rejects_escaping_template_path() {
+        // Arrange: A template whose file path is built from a variable
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        create_user_template(home.path(), "sneaky", &[("{{DIR}}_evil.txt", "pwned\n")]);
+
+        // Act: Define the variable so the path leaves the project directory
+        let output = run_procon(
+            home.path(),
+            work.path(),
+            &["new", "victim", "-t", "sneaky", "--define", "DIR=../x"],
+        );
+
+        // Assert: Verify creation failed, naming the path, and nothing was written
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("Failed to create project"), "{}", stderr);
+        assert!(stderr.contains("../x_evil.txt"), "{}", stderr);
+        assert_eq!(fs::read_dir(work.path()).unwrap().count(), 0);
+    }
+
+    /// Tests that a failing template hook under `--force` restores the replaced project.
+    #[test]
+    fn test_new_force_failing_template_hook_keeps_existing_project() {
+        if cfg!(windows) {
+            return;
+        }
+
+        // Arrange: An existing project with user edits, and a template whose hook fails
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        let manifest = "[hooks]\npost_create = [\"exit 3\"]\n";
+        create_user_template(home.path(), "failing", &[("template.toml", manifest)]);
+        assert!(run_procon(home.path(), work.path(), &["new", "kept"]).status.success());
+        fs::write(work.path().join("kept/main.cpp"), "// my solution\n").unwrap();
+
+        // Act: Replace the project with --force from the failing template
+        let output = run_procon(home.path(), work.path(), &["new", "kept", "-t", "failing", "--force"]);
+
+        // Assert: Verify the old project is back and nothing else was left behind
+        assert!(!output.status.success());
+        let main = fs::read_to_string(work.path().join("kept/main.cpp")).unwrap();
+        assert_eq!(main, "// my solution\n");
+        assert_eq!(fs::read_dir(work.path()).unwrap().count(), 1);
+    }
+
+    /// Tests that templates are found in directories listed in `PROCON_TEMPLATE_PATH`.
+    ///
+    /// Teams use this to share a templates directory, e.g. on a network drive.
+    #[test]
+    fn test_new_finds_template_in_template_path_env() {
+        // Arrange: A shared template directory outside the config directory
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        let shared = TempDir::new().unwrap();
+        let template_dir = shared.path().join("team");
+        fs::create_dir_all(&template_dir).unwrap();
+        fs::write(template_dir.join("main.cpp"), "// team template\n").unwrap();
+        fs::write(template_dir.join("CMakeLists.txt"), "project({{PROJECT_NAME}})\n").unwrap();
+        let search_path = shared.path().to_str().unwrap();
+
+        // Act: Create a project from the shared template
+        let output = run_procon_with_env(
+            home.path(),
+            work.path(),
+            &["new", "shared_project", "-t", "team"],
+            &[("PROCON_TEMPLATE_PATH", search_path)],
+        );
+
+        // Assert: Verify the shared template was used
+        assert!(output.status.success());
+        let main = fs::read_to_string(work.path().join("shared_project/main.cpp")).unwrap();
+        assert_eq!(main, "// team template\n");
+    }
+
+    /// Tests that a failed write leaves neither the project nor its staging directory.
+    ///
+    /// Projects are built in a hidden sibling directory and only moved into place when
+    /// complete, so the base directory must be exactly as it was before.
+    #[test]
+    fn test_new_failed_write_leaves_base_untouched() {
+        // Arrange: A template that breaks the .procon record, and an empty base directory
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        create_user_template(home.path(), "broken", &[(".procon", "not a directory\n")]);
+
+        // Act: Create a project in the base directory
+        let output = run_procon(home.path(), work.path(), &["new", "partial", "-t", "broken"]);
+
+        // Assert: Verify the base directory is still empty
+        assert!(!output.status.success());
+        assert_eq!(fs::read_dir(work.path()).unwrap().count(), 0);
+    }
+
+    /// Tests that a failed `new --force` keeps the project it would have replaced.
+    ///
+    /// The old project is only removed once its replacement is completely in place.
+    #[test]
+    fn test_new_failed_force_keeps_existing_project() {
+        // Arrange: An existing project with user edits, and a template that fails to write
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        create_user_template(home.path(), "broken", &[(".procon", "not a directory\n")]);
+        assert!(run_procon(home.path(), work.path(), &["new", "kept"]).status.success());
+        fs::write(work.path().join("kept/main.cpp"), "// my solution\n").unwrap();
+
+        // Act: Replace the project with --force from the broken template
+        let output = run_procon(home.path(), work.path(), &["new", "kept", "-t", "broken", "--force"]);
+
+        // Assert: Verify the old project is intact and nothing else was left behind
+        assert!(!output.status.success());
+        let main = fs::read_to_string(work.path().join("kept/main.cpp")).unwrap();
+        assert_eq!(main, "// my solution\n");
+        assert_eq!(fs::read_dir(work.path()).unwrap().count(), 1);
+    }
+
+    /// Tests that `new --open` starts `$EDITOR` with the project path as its argument.
+    ///
+    /// The editor is detached, so the stub's output is polled for. Skipped on
+    /// Windows, where the stub would need `cmd` syntax.
+    #[test]
+    fn test_new_open_starts_editor_with_project_path() {
+        if cfg!(windows) {
+            return;
+        }
+
+        // Arrange: A stub editor script that records its first argument
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        let script = work.path().join("editor.sh");
+        let received = work.path().join("received.txt");
+        fs::write(&script, format!("printf '%s' \"$1\" > '{}'\n", received.display())).unwrap();
+        let editor = format!("sh '{}'", script.display());
+
+        // Act: Create a project with --open
+        let output = run_procon_with_env(
+            home.path(),
+            work.path(),
+            &["new", "opened", "--open"],
+            &[("EDITOR", editor.as_str())],
+        );
+
+        // Assert: Verify the editor received the project path
+        assert!(output.status.success());
+        for _ in 0..50 {
+            if received.exists() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+        let argument = fs::read_to_string(&received).unwrap();
+        assert!(argument.ends_with("opened"));
+        assert!(Path::new(&argument).join("main.cpp").exists());
+    }
+
+    /// Tests that a variable default set with `config defaults.<NAME>` is substituted by `new`.
+    #[test]
+    fn test_config_defaults_substituted_by_new() {
+        // Arrange: A template using {{AUTHOR}} and a configured default for it
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        create_user_template(home.path(), "signed", &[("main.cpp", "// Author: {{AUTHOR}}\n")]);
+        let set = run_procon(home.path(), work.path(), &["config", "defaults.AUTHOR", "tourist"]);
+
+        // Act: Create a project without --define, and read the value back
+        let output = run_procon(home.path(), work.path(), &["new", "signed_app", "-t", "signed"]);
+        let get = run_procon(home.path(), work.path(), &["config", "defaults.AUTHOR"]);
+
+        // Assert: Verify the configured value was substituted
+        assert!(set.status.success());
+        assert!(output.status.success());
+        assert!(String::from_utf8_lossy(&get.stdout).contains("defaults.AUTHOR = tourist"));
+        let main = fs::read_to_string(work.path().join("signed_app/main.cpp")).unwrap();
+        assert_eq!(main, "// Author: tourist\n");
+    }
+
+    /// Tests that `config --all` lists every key, sorted, including variable defaults.
+    #[test]
+    fn test_config_all_lists_every_key() {
+        // Arrange: An isolated home with one variable default configured
+        let home = TempDir::new().unwrap();
+        run_procon(home.path(), home.path(), &["config", "defaults.AUTHOR", "tourist"]);
+
+        // Act: List the configuration as text and as JSON
+        let output = run_procon(home.path(), home.path(), &["config", "--all"]);
+        let json = run_procon(home.path(), home.path(), &["--format", "json", "config", "--all"]);
+
+        // Assert: Verify sorted key = value lines and a matching JSON object
+        assert!(output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let lines: Vec<&str> = stdout.lines().collect();
+        assert!(lines.contains(&"project.cpp_standard = 17"));
+        assert!(lines.contains(&"defaults.AUTHOR = tourist"));
+        let mut sorted = lines.clone();
+        sorted.sort();
+        assert_eq!(lines, sorted);
+        let value: serde_json::Value = serde_json::from_slice(&json.stdout).unwrap();
+        assert_eq!(value["project.cpp_standard"], "17");
+        assert_eq!(value["defaults.AUTHOR"], "tourist");
+    }
+
+    /// Tests that `--config` reads and writes the given file instead of the user's config.
+    #[test]
+    fn test_config_option_round_trips_through_given_file() {
+        // Arrange: A config path outside the isolated config directory
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        let config_path = work.path().join("profile.toml");
+        let config_arg = config_path.to_str().unwrap();
+
+        // Act: Set a value through --config, read it back, and create a project with it
+        let set = run_procon(home.path(), work.path(), &["--config", config_arg, "config", "project.cpp_standard", "20"]);
+        let get = run_procon(home.path(), work.path(), &["config", "project.cpp_standard", "--config", config_arg]);
+        let default = run_procon(home.path(), work.path(), &["config", "project.cpp_standard"]);
+        let new = run_procon(home.path(), work.path(), &["new", "profiled", "--config", config_arg]);
+
+        // Assert: Verify only the given file was used
+        assert!(set.status.success() && get.status.success() && new.status.success());
+        assert!(config_path.is_file());
+        assert!(!home.path().join(".config/procon_rs/config.toml").exists());
+        assert!(String::from_utf8_lossy(&get.stdout).contains("project.cpp_standard = 20"));
+        assert!(String::from_utf8_lossy(&default.stdout).contains("project.cpp_standard = 17"));
+        let cmake = fs::read_to_string(work.path().join("profiled/CMakeLists.txt")).unwrap();
+        assert!(cmake.contains("set(CMAKE_CXX_STANDARD 20)"));
+    }
+
+    /// Tests that `--config` pointing at a directory fails with a clear message.
+    #[test]
+    fn test_config_option_rejects_directory() {
+        // Arrange: An isolated home whose path is passed as the config file
+        let home = TempDir::new().unwrap();
+        let dir_arg = home.path().to_str().unwrap();
+
+        // Act: Read a setting from the directory
+        let output = run_procon(home.path(), home.path(), &["--config", dir_arg, "config", "project.cpp_standard"]);
+
+        // Assert: Verify the error explains the path is a directory
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains("is a directory, not a file"));
+    }
+
+    /// Tests that `contest --count` creates problems A.. in a contest directory.
+    #[test]
+    fn test_contest_count_creates_lettered_problems() {
+        // Arrange: An isolated home and working directory
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+
+        // Act: Create a contest with three problems
+        let output = run_procon(home.path(), work.path(), &["contest", "arc150", "--count", "3"]);
+
+        // Assert: Verify problems A to C exist and nothing more
+        assert!(output.status.success());
+        for problem in ["A", "B", "C"] {
+            assert!(work.path().join("arc150").join(problem).join("main.cpp").is_file());
+        }
+        assert!(!work.path().join("arc150/D").exists());
+    }
+
+    /// Tests that `{{PROJECT_NAME_UPPER}}` is substituted, e.g. for include guards.
+    #[test]
+    fn test_new_substitutes_project_name_upper() {
+        // Arrange: A template with a header guarded by the upper-case name
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        let header = "#ifndef {{PROJECT_NAME_UPPER}}_H\n#define {{PROJECT_NAME_UPPER}}_H\n#endif\n";
+        create_user_template(home.path(), "guarded", &[("solver.hpp", header)]);
+
+        // Act: Create a project whose name has a dash and a space
+        let output = run_procon(home.path(), work.path(), &["new", "my-cool project", "-t", "guarded"]);
+
+        // Assert: Verify the guard uses the sanitized, uppercased name
+        assert!(output.status.success());
+        let content = fs::read_to_string(work.path().join("my-cool project/solver.hpp")).unwrap();
+        assert!(content.starts_with("#ifndef MY_COOL_PROJECT_H\n#define MY_COOL_PROJECT_H\n"));
+    }
+
+    /// Tests that `{{NAME:fallback}}` uses the variable's value when it has one and
+    /// the fallback otherwise, even when the fallback contains colons.
+    #[test]
+    fn test_new_substitutes_inline_fallbacks() {
+        // Arrange: A template with inline fallbacks, one of them a URL
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        let notes = "by {{AUTHOR:Anonymous}}\nsee {{CONTEST_URL:https://atcoder.jp/contests}}\nfor {{PROJECT_NAME:unnamed}}\n";
+        create_user_template(home.path(), "noted", &[("notes.md", notes)]);
+
+        // Act: Create one project defining AUTHOR and one defining nothing
+        let defined = run_procon(home.path(), work.path(), &["new", "defined", "-t", "noted", "-D", "AUTHOR=tourist"]);
+        let fallback = run_procon(home.path(), work.path(), &["new", "fallback", "-t", "noted"]);
+
+        // Assert: Verify defined values win and fallbacks fill the rest
+        assert!(defined.status.success() && fallback.status.success());
+        assert_eq!(
+            fs::read_to_string(work.path().join("defined/notes.md")).unwrap(),
+            "by tourist\nsee https://atcoder.jp/contests\nfor defined\n"
+        );
+        assert_eq!(
+            fs::read_to_string(work.path().join("fallback/notes.md")).unwrap(),
+            "by Anonymous\nsee https://atcoder.jp/contests\nfor fallback\n"
+        );
+    }
+
+    /// Tests that `--template-dir` instantiates a template from any directory.
+    #[test]
+    fn test_new_with_template_dir() {
+        // Arrange: A template outside the template search path
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        let template_dir = work.path().join("drafts").join("my-template");
+        fs::create_dir_all(&template_dir).unwrap();
+        fs::write(template_dir.join("main.cpp"), "// {{PROJECT_NAME}}\n").unwrap();
+        fs::write(template_dir.join("CMakeLists.txt"), "project({{PROJECT_NAME}})\n").unwrap();
+        let dir_arg = template_dir.to_str().unwrap();
+
+        // Act: Create a project from it, and try combining it with --template
+        let output = run_procon(home.path(), work.path(), &["new", "drafted", "--template-dir", dir_arg]);
+        let both = run_procon(home.path(), work.path(), &["new", "both", "--template-dir", dir_arg, "-t", "default"]);
+
+        // Assert: Verify the directory's files were used and the combination is refused
+        assert!(output.status.success());
+        assert_eq!(fs::read_to_string(work.path().join("drafted/main.cpp")).unwrap(), "// drafted\n");
+        assert!(!both.status.success());
+        assert!(String::from_utf8_lossy(&both.stderr).contains("cannot be used with"));
+        assert!(!work.path().join("both").exists());
+    }
+
+    /// Tests that `--log-level debug` traces the template roots that were searched,
+    /// on stderr only.
+    #[test]
+    fn test_log_level_debug_shows_searched_template_roots() {
+        // Arrange: A shared template directory in PROCON_TEMPLATE_PATH
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        let shared = TempDir::new().unwrap();
+        let shared_path = shared.path().to_str().unwrap();
+
+        // Act: Create the same project with and without debug logs
+        let quiet = run_procon_with_env(home.path(), work.path(), &["new", "silent"], &[]);
+        let traced = run_procon_with_env(
+            home.path(),
+            work.path(),
+            &["--log-level", "debug", "new", "plain"],
+            &[("PROCON_TEMPLATE_PATH", shared_path)],
+        );
+
+        // Assert: Verify the roots and the decision are logged, and stdout is unchanged
+        assert!(quiet.status.success() && traced.status.success());
+        let stderr = String::from_utf8_lossy(&traced.stderr);
+        assert!(stderr.contains(&format!("Template 'default' not in {}", shared_path)));
+        assert!(stderr.contains("Using builtin template 'default'"));
+        assert!(String::from_utf8_lossy(&quiet.stderr).is_empty());
+        let stdout = String::from_utf8_lossy(&traced.stdout);
+        assert!(!stdout.contains("DEBUG"));
+    }
+
+    /// Tests that `doctor` reports on cmake and fails exactly when a required tool is missing.
+    #[test]
+    fn test_doctor_reports_tools() {
+        // Arrange: An isolated home
+        let home = TempDir::new().unwrap();
+
+        // Act: Run the checks
+        let output = run_procon(home.path(), home.path(), &["doctor"]);
+
+        // Assert: Verify cmake, git and the config directory are reported
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("cmake: "));
+        assert!(stdout.contains("git: "));
+        assert!(stdout.contains("config directory: "));
+        if stdout.contains("cmake: not found") {
+            assert!(!output.status.success());
+            assert!(String::from_utf8_lossy(&output.stderr).contains("cmake not found on PATH"));
+        }
+    }
+
+    /// Tests that files matched by `no_substitute` are copied verbatim.
+    ///
+    /// Scripts may use `{{...}}` themselves, so their contents must be left untouched
+    /// while the rest of the template is still substituted.
+    #[test]
+    fn test_new_copies_no_substitute_files_verbatim() {
+        // Arrange: Create a template whose script contains a placeholder
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        let template_dir = home.path().join(".config/procon_rs/templates/scripted");
+        fs::create_dir_all(template_dir.join("scripts")).unwrap();
+        create_user_template(
+            home.path(),
+            "scripted",
+            &[
+                ("template.toml", "no_substitute = [\"scripts/*.sh\"]\n"),
+                ("main.cpp", "// {{PROJECT_NAME}}\nint main() { return 0; }\n"),
+                ("scripts/gen.sh", "echo {{PROJECT_NAME}}\n"),
+            ],
+        );
+
+        // Act: Create a project from the template
+        let output = run_procon(home.path(), work.path(), &["new", "abc300_a", "-t", "scripted"]);
+
+        // Assert: Verify the script is verbatim and main.cpp is substituted
+        assert!(output.status.success());
+        let project = work.path().join("abc300_a");
+        let script = fs::read_to_string(project.join("scripts/gen.sh")).unwrap();
+        assert_eq!(script, "echo {{PROJECT_NAME}}\n");
+        let main = fs::read_to_string(project.join("main.cpp")).unwrap();
+        assert!(main.starts_with("// abc300_a\n"));
+    }
+
+    /// Tests that a relative `--path` is resolved to an absolute path.
+    ///
+    /// Users running from subfolders need to see exactly where the project landed,
+    /// so `./` and `..` segments must not leak into the reported path.
+    #[test]
+    fn test_new_resolves_relative_path() {
+        // Arrange: A subdirectory of the working directory
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        fs::create_dir(work.path().join("sub")).unwrap();
+
+        // Act: Create a project with a relative, roundabout --path
+        let output = run_procon(
+            home.path(),
+            work.path(),
+            &["new", "abc300_a", "-p", "./sub/../sub"],
+        );
+
+        // Assert: Verify the project landed under the resolved absolute path
+        assert!(output.status.success());
+        let expected = fs::canonicalize(work.path()).unwrap().join("sub").join("abc300_a");
+        assert!(expected.join("main.cpp").is_file());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains(&format!("Path: {}", expected.display())));
+    }
+
+    /// Tests that a `git+` template is cloned once and reused until `--refresh`.
+    ///
+    /// Cloning the same URL on every `new` is slow, so later invocations must use the
+    /// cached clone. A marker file planted in the cache shows whether it was reused.
+    #[test]
+    fn test_new_reuses_cached_git_template() {
+        if !procon_rs::git::is_available() {
+            return;
+        }
+
+        // Arrange: Create a first project from a bare repository, then mark its cached clone
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        let repos = TempDir::new().unwrap();
+        let url = create_bare_template_repo(
+            repos.path(),
+            &[
+                ("main.cpp", "int main() {}\n"),
+                ("CMakeLists.txt", "project({{PROJECT_NAME}})\n"),
+            ],
+        );
+        let template = format!("git+{}", url);
+        let first = run_procon(home.path(), work.path(), &["new", "first", "-t", &template]);
+        assert!(first.status.success(), "{}", String::from_utf8_lossy(&first.stderr));
+        let cache = home.path().join(".config/procon_rs/cache");
+        let clones: Vec<PathBuf> = fs::read_dir(&cache).unwrap().map(|entry| entry.unwrap().path()).collect();
+        assert_eq!(clones.len(), 1);
+        fs::write(clones[0].join("cached.txt"), "marker\n").unwrap();
+
+        // Act: Create a second project normally and a third with --refresh
+        let second = run_procon(home.path(), work.path(), &["new", "second", "-t", &template]);
+        let third = run_procon(
+            home.path(),
+            work.path(),
+            &["new", "third", "-t", &template, "--refresh"],
+        );
+
+        // Assert: Verify only the refreshed project was made from a fresh clone
+        assert!(second.status.success());
+        assert!(third.status.success());
+        assert!(work.path().join("second/cached.txt").exists());
+        assert!(!work.path().join("third/cached.txt").exists());
+        assert!(!clones[0].join("cached.txt").exists());
+    }
+
+    /// Tests that `--stdin-template` builds the project from a piped `main.cpp`.
+    ///
+    /// One-off solutions skip templates on disk entirely, so the piped content must be
+    /// substituted and paired with a CMakeLists.txt generated from the config.
+    #[test]
+    fn test_new_with_stdin_template() {
+        // Arrange: An isolated environment and a main.cpp to pipe in
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        let mut child = Command::new(env!("CARGO_BIN_EXE_procon_rs"))
+            .args(["new", "foo", "--stdin-template"])
+            .current_dir(work.path())
+            .env("HOME", home.path())
+            .env("XDG_CONFIG_HOME", home.path().join(".config"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        // Act: Pipe the template into `new`
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(b"// {{PROJECT_NAME}}\nint main() {}\n")
+            .unwrap();
+        let output = child.wait_with_output().unwrap();
+
+        // Assert: Verify main.cpp was substituted and CMakeLists.txt generated
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        let project = work.path().join("foo");
+        assert_eq!(fs::read_to_string(project.join("main.cpp")).unwrap(), "// foo\nint main() {}\n");
+        let cmake = fs::read_to_string(project.join("CMakeLists.txt")).unwrap();
+        assert!(cmake.contains("project(foo)"));
+        assert!(cmake.contains("set(CMAKE_CXX_STANDARD 17)"));
+    }
+
+    /// Tests that `template info` summarizes a builtin template.
+    ///
+    /// Builtins have no manifest, so a description is generated and the files and
+    /// required files are listed.
+    #[test]
+    fn test_template_info_builtin() {
+        // Arrange: An isolated environment with only builtin templates
+        let home = TempDir::new().unwrap();
+
+        // Act: Describe the default template
+        let output = run_procon(home.path(), home.path(), &["template", "info", "default"]);
+
+        // Assert: Verify the description and files are printed
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("template 'default' with"));
+        assert!(stdout.contains("Required files: main.cpp, CMakeLists.txt"));
+        assert!(stdout.contains("   main.cpp\n"));
+        assert!(stdout.contains("   CMakeLists.txt\n"));
+    }
+
+    /// Tests that `--no-gitignore` leaves the template's `.gitignore` out.
+    ///
+    /// Users with a global gitignore don't want one per project, while everyone else
+    /// keeps getting it by default.
+    #[test]
+    fn test_new_no_gitignore() {
+        // Arrange: An isolated environment using the builtin default template
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+
+        // Act: Create one project with the flag and one without
+        let without = run_procon(home.path(), work.path(), &["new", "bare", "--no-gitignore"]);
+        let with = run_procon(home.path(), work.path(), &["new", "ignored"]);
+
+        // Assert: Verify only the project created without the flag has a .gitignore
+        assert!(without.status.success());
+        assert!(with.status.success());
+        assert!(work.path().join("bare/main.cpp").exists());
+        assert!(!work.path().join("bare/.gitignore").exists());
+        assert!(work.path().join("ignored/.gitignore").exists());
+    }
+
+    /// Tests that `new --force` merges the replaced project's `.gitignore` rules.
+    #[test]
+    fn test_new_force_keeps_gitignore_rules() {
+        // Arrange: A project whose .gitignore the user extended
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        let gitignore = work.path().join("ruled/.gitignore");
+        assert!(run_procon(home.path(), work.path(), &["new", "ruled"]).status.success());
+        let mut rules = fs::read_to_string(&gitignore).unwrap();
+        rules.push_str("\nsecrets.txt\n");
+        fs::write(&gitignore, rules).unwrap();
+
+        // Act: Replace the project with --force
+        let output = run_procon(home.path(), work.path(), &["new", "ruled", "--force"]);
+
+        // Assert: Verify the user rule survived alongside the template's
+        assert!(output.status.success());
+        let merged = fs::read_to_string(&gitignore).unwrap();
+        assert!(merged.lines().any(|line| line == "secrets.txt"));
+        assert!(merged.lines().any(|line| line == "build/"));
+    }
+
+    /// Tests that `undo` deletes the project created last, but not once it was modified.
+    ///
+    /// Undo exists for mistyped names; it must never throw away work done since.
+    #[test]
+    fn test_undo_removes_last_project() {
+        // Arrange: Create a project, then one more that gets modified
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        assert!(run_procon(home.path(), work.path(), &["new", "kept"]).status.success());
+        assert!(run_procon(home.path(), work.path(), &["new", "typo"]).status.success());
+        fs::write(work.path().join("typo/notes.txt"), "work in progress\n").unwrap();
+
+        // Act: Undo while modified, then again after reverting the change
+        let refused = run_procon(home.path(), work.path(), &["undo", "--yes"]);
+        fs::remove_file(work.path().join("typo/notes.txt")).unwrap();
+        let undone = run_procon(home.path(), work.path(), &["undo", "--yes"]);
+        let nothing_left = run_procon(home.path(), work.path(), &["undo", "--yes"]);
+
+        // Assert: Verify only the unmodified last project was deleted, and only once
+        assert!(!refused.status.success());
+        assert!(String::from_utf8_lossy(&refused.stderr).contains("notes.txt"));
+        assert!(undone.status.success(), "{}", String::from_utf8_lossy(&undone.stderr));
+        assert!(!work.path().join("typo").exists());
+        assert!(work.path().join("kept/main.cpp").exists());
+        assert!(!nothing_left.status.success());
+        assert!(String::from_utf8_lossy(&nothing_left.stderr).contains("Nothing to undo"));
+    }
+
+    /// Tests that `rename` moves the project and renames it in CMakeLists.txt.
+    ///
+    /// A renamed directory whose build still produced the old target would be confusing.
+    #[test]
+    fn test_rename_project() {
+        // Arrange: A project named foo
+        let home = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        assert!(run_procon(home.path(), work.path(), &["new", "foo"]).status.success());
+
+        // Act: Rename it to bar
+        let output = run_procon(home.path(), work.path(), &["rename", "foo", "bar"]);
+
+        // Assert: Verify the directory moved and CMakeLists.txt uses the new name
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        assert!(!work.path().join("foo").exists());
+        let cmake = fs::read_to_string(work.path().join("bar/CMakeLists.txt")).unwrap();
+        assert!(cmake.contains("project(bar)"), "{}", cmake);
+        assert!(cmake.contains("add_executable(bar"), "{}", cmake);
+        assert!(!cmake.contains("foo"), "{}", cmake);
+    }
+}
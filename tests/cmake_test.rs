@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod cmake_tests {
+    use procon_rs::cmake::parse_cmake_lists;
+
+    /// Tests that the project name and standard are read from a typical CMakeLists.
+    #[test]
+    fn test_parse_cmake_lists_reads_name_and_standard() {
+        // Arrange: A CMakeLists as generated by the default template
+        let content = "cmake_minimum_required(VERSION 3.16)\n\
+                       project(abc300_a)\n\
+                       \n\
+                       set(CMAKE_CXX_STANDARD 20)\n\
+                       set(CMAKE_CXX_STANDARD_REQUIRED ON)\n";
+
+        // Act: Parse it
+        let settings = parse_cmake_lists(content);
+
+        // Assert: Verify both values were extracted
+        assert_eq!(settings.project_name.as_deref(), Some("abc300_a"));
+        assert_eq!(settings.cpp_standard.as_deref(), Some("20"));
+    }
+
+    /// Tests that commented-out settings are ignored in favor of the active ones.
+    ///
+    /// Users often keep old values around in comments; those must not win.
+    #[test]
+    fn test_parse_cmake_lists_ignores_comments() {
+        // Arrange: Line and bracket comments containing stale settings
+        let content = "# project(old_name)\n\
+                       #[[ set(CMAKE_CXX_STANDARD 11) ]]\n\
+                       PROJECT(\"solver\" LANGUAGES CXX) # project(trailing)\n\
+                       set(CMAKE_CXX_STANDARD 17) # set(CMAKE_CXX_STANDARD 14)\n";
+
+        // Act: Parse it
+        let settings = parse_cmake_lists(content);
+
+        // Assert: Verify only the active settings are used
+        assert_eq!(settings.project_name.as_deref(), Some("solver"));
+        assert_eq!(settings.cpp_standard.as_deref(), Some("17"));
+    }
+
+    /// Tests that a `cxx_std_NN` compile feature is used when CMAKE_CXX_STANDARD is absent.
+    #[test]
+    fn test_parse_cmake_lists_compile_feature_standard() {
+        // Arrange: A target-based CMakeLists
+        let content = "project(main)\ntarget_compile_features(main PRIVATE cxx_std_23)\n";
+
+        // Act & Assert: Verify the feature's standard is reported
+        assert_eq!(parse_cmake_lists(content).cpp_standard.as_deref(), Some("23"));
+    }
+
+    /// Tests that unparseable or variable-based values are reported as missing.
+    #[test]
+    fn test_parse_cmake_lists_missing_values() {
+        // Arrange: A CMakeLists without literal settings
+        let content = "project(${NAME})\nset(CMAKE_CXX_STANDARD ${STD})\n";
+
+        // Act: Parse it
+        let settings = parse_cmake_lists(content);
+
+        // Assert: Verify nothing was guessed
+        assert_eq!(settings.project_name, None);
+        assert_eq!(settings.cpp_standard, None);
+    }
+}
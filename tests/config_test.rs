@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod config_tests {
-    use procon_rs::config::{Config, ProjectConfig, TemplateConfig};
+    use procon_rs::config::{expand_tilde, Config, ProjectConfig, TemplateConfig, GCC_PRAGMAS};
+    use procon_rs::error::ProconError;
     use std::fs;
     use std::path::PathBuf;
     use tempfile::TempDir;
@@ -147,7 +148,9 @@ mod config_tests {
             project: ProjectConfig {
                 cpp_standard: "23".to_string(),
                 cmake_minimum_version: "3.25".to_string(),
+                ..Default::default()
             },
+            ..Default::default()
         };
 
         // Act: Serialize the configuration to TOML
@@ -174,4 +177,374 @@ mod config_tests {
             config.project.cmake_minimum_version
         );
     }
+
+    /// Tests that project.default_path_layout is opt-in and can be set and cleared.
+    ///
+    /// Nesting projects by contest changes where files land, so it must stay off
+    /// until the user explicitly configures a layout.
+    #[test]
+    fn test_config_default_path_layout() {
+        // Arrange: Create a mutable default configuration
+        let mut config = Config::default();
+
+        // Assert: Verify the layout is unset by default
+        assert_eq!(config.project.default_path_layout, None);
+        assert_eq!(config.get("project.default_path_layout"), Some(String::new()));
+
+        // Act: Configure a contest layout
+        config.set("project.default_path_layout", "{contest}/{problem}").unwrap();
+        // Assert: Verify the layout was stored
+        assert_eq!(
+            config.project.default_path_layout.as_deref(),
+            Some("{contest}/{problem}")
+        );
+
+        // Act: Clear the layout with an empty value
+        config.set("project.default_path_layout", "").unwrap();
+        // Assert: Verify projects are flat again
+        assert_eq!(config.project.default_path_layout, None);
+    }
+
+    /// Tests that Config::to_json() reports every key with its JSON type.
+    ///
+    /// Editor integrations parse this output, so strings must stay strings and unset
+    /// optional values must be null rather than empty strings.
+    #[test]
+    fn test_config_to_json_types() {
+        // Arrange: Create a default configuration
+        let config = Config::default();
+
+        // Act: Render the configuration as JSON
+        let json = config.to_json();
+
+        // Assert: Verify values carry their JSON types
+        assert_eq!(json["project.cpp_standard"], serde_json::json!("17"));
+        assert!(json["template.path"].is_string());
+        assert!(json["project.default_path_layout"].is_null());
+        assert_eq!(json.as_object().unwrap().len(), Config::KEYS.len());
+    }
+
+    /// Tests that Config::get_json() returns None for unknown keys.
+    #[test]
+    fn test_config_get_json_unknown_key() {
+        // Arrange & Act & Assert: Unknown keys have no JSON value
+        assert_eq!(Config::default().get_json("unknown.key"), None);
+    }
+
+    /// Tests that project.gitignore_extra is set from a comma-separated value.
+    ///
+    /// A single `config` invocation must be able to carry several rules.
+    #[test]
+    fn test_config_gitignore_extra() {
+        // Arrange: Create a mutable default configuration
+        let mut config = Config::default();
+        assert!(config.project.gitignore_extra.is_empty());
+
+        // Act: Set two rules, with stray whitespace and an empty entry
+        config.set("project.gitignore_extra", "*.log, input.txt,").unwrap();
+
+        // Assert: Verify the rules were parsed and read back
+        assert_eq!(config.project.gitignore_extra, vec!["*.log", "input.txt"]);
+        assert_eq!(
+            config.get("project.gitignore_extra"),
+            Some("*.log,input.txt".to_string())
+        );
+        assert_eq!(
+            config.get_json("project.gitignore_extra"),
+            Some(serde_json::json!(["*.log", "input.txt"]))
+        );
+    }
+
+    /// Tests that the CMake version renders as a policy range once a maximum is configured.
+    ///
+    /// Without a maximum the generated `cmake_minimum_required` keeps a single version.
+    #[test]
+    fn test_config_cmake_policy_range_rendering() {
+        // Arrange: Create a mutable default configuration
+        let mut config = Config::default();
+
+        // Assert: Verify a single version is rendered by default
+        assert_eq!(config.project.cmake_version_spec().unwrap(), "3.16");
+
+        // Act: Configure a policy maximum
+        config.set("project.cmake_policy_max", "3.28").unwrap();
+
+        // Assert: Verify the range form is rendered
+        assert_eq!(config.project.cmake_version_spec().unwrap(), "3.16...3.28");
+    }
+
+    /// Tests that a policy maximum older than the minimum version is rejected.
+    #[test]
+    fn test_config_cmake_policy_range_rejects_min_above_max() {
+        // Arrange: Create a mutable default configuration (minimum 3.16)
+        let mut config = Config::default();
+
+        // Act: Attempt to set an older maximum
+        let result = config.set("project.cmake_policy_max", "3.10");
+
+        // Assert: Verify the range is rejected and nothing was stored
+        assert!(result.unwrap_err().to_string().contains("minimum is newer than maximum"));
+        assert_eq!(config.project.cmake_policy_max, None);
+    }
+
+    /// Tests that a malformed policy maximum is rejected, including when loaded from a file.
+    ///
+    /// A hand-edited config bypasses `set`, so rendering validates the range as well.
+    #[test]
+    fn test_config_cmake_policy_range_rejects_malformed_version() {
+        // Arrange: Create configurations with malformed maximums
+        let mut config = Config::default();
+        let mut edited = Config::default();
+        edited.project.cmake_policy_max = Some("latest".to_string());
+
+        // Act & Assert: Verify both paths report the bad version
+        assert!(config.set("project.cmake_policy_max", "3.x").is_err());
+        assert!(edited.project.cmake_version_spec().is_err());
+    }
+
+    /// Tests that expand_tilde() resolves `~` to the home directory and leaves other paths alone.
+    #[test]
+    fn test_expand_tilde() {
+        // Arrange: The current user's home directory
+        let home = dirs::home_dir().unwrap();
+
+        // Act & Assert: Verify only a leading `~` component is expanded
+        assert_eq!(expand_tilde(&PathBuf::from("~/templates")), home.join("templates"));
+        assert_eq!(expand_tilde(&PathBuf::from("~")), home);
+        assert_eq!(expand_tilde(&PathBuf::from("/abs/~x")), PathBuf::from("/abs/~x"));
+        assert_eq!(expand_tilde(&PathBuf::from("~other/x")), PathBuf::from("~other/x"));
+    }
+
+    /// Tests that a config referring to a renamed builtin is migrated to the new name.
+    ///
+    /// Without migration, saved configs would silently break when a builtin is renamed.
+    #[test]
+    fn test_config_migrates_renamed_builtin() {
+        // Arrange: A config whose default template was later renamed
+        let mut config = Config::default();
+        let renames = [("default", "basic")];
+
+        // Act: Apply the migration
+        let warnings = config.migrate_with(&renames);
+
+        // Assert: Verify the name was rewritten and the rename reported
+        assert_eq!(config.template.default, "basic");
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("'default' was renamed to 'basic'"));
+    }
+
+    /// Tests that migration leaves unrelated template names untouched.
+    #[test]
+    fn test_config_migration_ignores_other_names() {
+        // Arrange: A config using a custom template
+        let mut config = Config::default();
+        config.template.default = "mine".to_string();
+
+        // Act: Apply the migration
+        let warnings = config.migrate_with(&[("default", "basic")]);
+
+        // Assert: Verify nothing changed
+        assert_eq!(config.template.default, "mine");
+        assert!(warnings.is_empty());
+    }
+
+    /// Tests that validate() reports every invalid field, not just the first one.
+    ///
+    /// Users fixing a hand-edited config should see all problems in one run.
+    #[test]
+    fn test_config_validate_reports_all_problems() {
+        // Arrange: A config file with several invalid fields
+        let temp_dir = TempDir::new().unwrap();
+        let config_file = temp_dir.path().join("config.toml");
+        let mut config = Config::default();
+        config.project.cpp_standard = "98".to_string();
+        config.project.cmake_minimum_version = "three".to_string();
+        config.project.default_path_layout = Some("../{contest}".to_string());
+        fs::write(&config_file, toml::to_string_pretty(&config).unwrap()).unwrap();
+
+        // Act: Load and validate the file
+        let loaded = Config::from_file(&config_file).unwrap();
+        let error = loaded.validate().unwrap_err();
+
+        // Assert: Verify each problem is listed
+        let message = error.to_string();
+        assert!(message.contains("Unsupported C++ standard '98'"));
+        assert!(message.contains("Invalid CMake version 'three'"));
+        assert!(message.contains("must contain {problem}"));
+        assert!(message.contains("must stay inside the project directory"));
+    }
+
+    /// Tests that the default configuration passes validation.
+    #[test]
+    fn test_config_validate_accepts_defaults() {
+        // Act & Assert: Verify defaults are valid
+        assert!(Config::default().validate().is_ok());
+    }
+
+    /// Tests that ui.emoji accepts booleans and rejects anything else.
+    #[test]
+    fn test_config_ui_emoji() {
+        // Arrange: Create a mutable default configuration
+        let mut config = Config::default();
+
+        // Act: Disable emoji and try an invalid value
+        config.set("ui.emoji", "false").unwrap();
+        let invalid = config.set("ui.emoji", "off");
+
+        // Assert: Verify the stored value and typed JSON
+        assert!(invalid.is_err());
+        assert_eq!(config.get("ui.emoji").unwrap(), "false");
+        assert_eq!(config.get_json("ui.emoji").unwrap(), serde_json::json!(false));
+    }
+
+    /// Tests that GCC pragmas are rendered for g++ but not for clang.
+    ///
+    /// Clang ignores `#pragma GCC optimize`, so injecting it would only add noise.
+    #[test]
+    fn test_config_gcc_pragmas_depend_on_compiler() {
+        // Arrange: Enable pragmas for both compilers
+        let mut gcc = Config::default();
+        gcc.set("project.gcc_pragmas", "true").unwrap();
+        gcc.set("project.compiler", "/usr/bin/g++-13").unwrap();
+        let mut clang = Config::default();
+        clang.set("project.gcc_pragmas", "true").unwrap();
+        clang.set("project.compiler", "clang++").unwrap();
+
+        // Act & Assert: Verify only GCC gets the pragmas, and only when enabled
+        assert_eq!(gcc.project.pragmas(), GCC_PRAGMAS);
+        assert_eq!(clang.project.pragmas(), "");
+        assert_eq!(Config::default().project.pragmas(), "");
+    }
+
+    /// Tests that GCC is recognized behind a path, a target prefix or a wrapper.
+    #[test]
+    fn test_config_uses_gcc_recognizes_decorated_names() {
+        // Arrange: A helper configuring a compiler command
+        let with_compiler = |compiler: &str| {
+            let mut config = Config::default();
+            config.set("project.compiler", compiler).unwrap();
+            config
+        };
+
+        // Act & Assert: Verify every GCC spelling is detected, and Clang still isn't
+        assert!(with_compiler("/usr/bin/g++-13").project.uses_gcc());
+        assert!(with_compiler("x86_64-linux-gnu-g++").project.uses_gcc());
+        assert!(with_compiler("ccache g++").project.uses_gcc());
+        assert!(!with_compiler("ccache clang++").project.uses_gcc());
+        assert!(!with_compiler("/usr/bin/clang++-17").project.uses_gcc());
+    }
+
+    /// Tests that a saved configuration is picked up by a fresh load.
+    ///
+    /// Without persistence, `config` changes could never affect later `new` runs.
+    #[test]
+    fn test_config_save_then_load_round_trip() {
+        // Arrange: A custom standard saved into a not-yet-existing directory
+        let temp_dir = TempDir::new().unwrap();
+        let config_file = temp_dir.path().join("procon_rs").join("config.toml");
+        let mut config = Config::default();
+        config.set("project.cpp_standard", "20").unwrap();
+        config.save_to(&config_file).unwrap();
+
+        // Act: Load it back
+        let loaded = Config::load_from(&config_file).unwrap();
+
+        // Assert: Verify the custom value survived
+        assert_eq!(loaded.project.cpp_standard, "20");
+    }
+
+    /// Tests that a missing config file yields defaults and a malformed one is an error.
+    #[test]
+    fn test_config_load_missing_and_malformed() {
+        // Arrange: A missing path and a file with invalid TOML
+        let temp_dir = TempDir::new().unwrap();
+        let malformed = temp_dir.path().join("config.toml");
+        fs::write(&malformed, "[project\ncpp_standard = ").unwrap();
+
+        // Act: Load both
+        let missing = Config::load_from(&temp_dir.path().join("absent.toml"));
+        let broken = Config::load_from(&malformed);
+
+        // Assert: Verify defaults for missing and a TOML parse error for malformed
+        assert_eq!(missing.unwrap().project.cpp_standard, "17");
+        assert!(matches!(broken, Err(ProconError::TomlParse(_))));
+    }
+
+    /// Tests that `defaults.<NAME>` keys read and write the variable defaults table.
+    #[test]
+    fn test_config_set_and_get_variable_defaults() {
+        // Arrange: A default configuration
+        let mut config = Config::default();
+
+        // Act: Set a default, read it back, then clear it
+        config.set("defaults.AUTHOR", "tourist").unwrap();
+        let value = config.get("defaults.AUTHOR");
+        let invalid = config.set("defaults.1ST", "x");
+        config.set("defaults.AUTHOR", "").unwrap();
+
+        // Assert: Verify the map was updated and bad names or prefixes are rejected
+        assert_eq!(value.as_deref(), Some("tourist"));
+        assert!(invalid.unwrap_err().to_string().contains("Invalid variable name '1ST'"));
+        assert!(config.defaults.is_empty());
+        assert_eq!(config.get("defaults.MISSING").as_deref(), Some(""));
+        assert!(config.set("default.AUTHOR", "x").is_err());
+    }
+
+    /// Tests that `project.cpp_standard` only accepts the supported standards.
+    ///
+    /// A typo such as `cpp18` would otherwise end up in every generated CMakeLists.txt.
+    #[test]
+    fn test_config_set_cpp_standard_validation() {
+        // Arrange: A default configuration
+        let mut config = Config::default();
+
+        // Act: Set a supported standard, then an unsupported one
+        let accepted = config.set("project.cpp_standard", "23");
+        let rejected = config.set("project.cpp_standard", "cpp18");
+
+        // Assert: Verify the bad value is rejected with the allowed values listed
+        assert!(accepted.is_ok());
+        let message = rejected.unwrap_err().to_string();
+        assert!(message.contains("Unsupported C++ standard 'cpp18'"));
+        assert!(message.contains("11, 14, 17, 20, 23, 26"));
+        assert_eq!(config.project.cpp_standard, "23");
+    }
+
+    /// Tests that loading a hand-edited config with an unsupported standard fails.
+    #[test]
+    fn test_config_load_rejects_unsupported_cpp_standard() {
+        // Arrange: A config file with a typo in the standard
+        let temp_dir = TempDir::new().unwrap();
+        let config_file = temp_dir.path().join("config.toml");
+        let mut config = Config::default();
+        config.project.cpp_standard = "cpp18".to_string();
+        config.save_to(&config_file).unwrap();
+
+        // Act: Load it
+        let result = Config::load_from(&config_file);
+
+        // Assert: Verify a configuration error naming the value and the file
+        let message = result.unwrap_err().to_string();
+        assert!(message.contains("Unsupported C++ standard 'cpp18'"));
+        assert!(message.contains(&config_file.display().to_string()));
+    }
+
+    /// Tests that `project.cmake_minimum_version` must be `MAJOR.MINOR[.PATCH]`.
+    #[test]
+    fn test_config_set_cmake_minimum_version_validation() {
+        // Arrange: A default configuration
+        let mut config = Config::default();
+
+        // Act & Assert: Verify numeric versions are accepted
+        config.set("project.cmake_minimum_version", "3.16").unwrap();
+        assert_eq!(config.project.cmake_minimum_version, "3.16");
+        config.set("project.cmake_minimum_version", "3.16.3").unwrap();
+        assert_eq!(config.project.cmake_minimum_version, "3.16.3");
+
+        // Act & Assert: Verify malformed versions are rejected and the value is kept
+        let message = config.set("project.cmake_minimum_version", "3.x").unwrap_err().to_string();
+        assert!(message.contains("Invalid CMake version '3.x'"));
+        assert!(config.set("project.cmake_minimum_version", "latest").is_err());
+        assert_eq!(config.project.cmake_minimum_version, "3.16.3");
+    }
 }
@@ -0,0 +1,129 @@
+#[cfg(test)]
+mod contest_command_tests {
+    use procon_rs::commands::contest::{ContestCommand, ContestCommandArgs};
+    use procon_rs::config::Config;
+    use procon_rs::error::ProconError;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// Tests that a contest gets one project per problem, each named after its id.
+    #[test]
+    fn test_contest_creates_project_per_problem() {
+        // Arrange: A contest with three problems
+        let temp_dir = TempDir::new().unwrap();
+        let args = ContestCommandArgs {
+            name: "abc300".to_string(),
+            problems: vec!["A".to_string(), "B".to_string(), "C".to_string()],
+            template: "default".to_string(),
+            path: Some(temp_dir.path().to_path_buf()),
+            create_parents: false,
+        };
+
+        // Act: Create the contest
+        let output = ContestCommand::execute(&args, &Config::default()).unwrap();
+
+        // Assert: Verify every problem has a main.cpp and uses its id as project name
+        let contest_path = temp_dir.path().join("abc300");
+        assert_eq!(output.contest_path, contest_path);
+        assert_eq!(output.problems.len(), 3);
+        for problem in ["A", "B", "C"] {
+            assert!(contest_path.join(problem).join("main.cpp").is_file());
+            let cmake = fs::read_to_string(contest_path.join(problem).join("CMakeLists.txt")).unwrap();
+            assert!(cmake.contains(&format!("project({})", problem)));
+        }
+    }
+
+    /// Tests that an existing problem directory aborts the contest before anything is created.
+    #[test]
+    fn test_contest_is_all_or_nothing() {
+        // Arrange: A contest directory that already has problem C
+        let temp_dir = TempDir::new().unwrap();
+        let contest_path = temp_dir.path().join("abc301");
+        fs::create_dir_all(contest_path.join("C")).unwrap();
+        let args = ContestCommandArgs {
+            name: "abc301".to_string(),
+            problems: ContestCommand::problem_ids(4).unwrap(),
+            template: "default".to_string(),
+            path: Some(temp_dir.path().to_path_buf()),
+            create_parents: false,
+        };
+
+        // Act: Create the contest
+        let result = ContestCommand::execute(&args, &Config::default());
+
+        // Assert: Verify the conflict is reported and no other problem was created
+        assert!(matches!(result, Err(ProconError::ProjectExists(name)) if name == "abc301/C"));
+        assert!(!contest_path.join("A").exists());
+        assert!(!contest_path.join("B").exists());
+        assert!(!contest_path.join("D").exists());
+    }
+
+    /// Tests that a missing `--path` is an error unless parents may be created.
+    ///
+    /// A typo in the path must not scatter a contest's directories across the filesystem.
+    #[test]
+    fn test_contest_requires_create_parents_for_missing_path() {
+        // Arrange: A base path two levels below an existing directory
+        let temp_dir = TempDir::new().unwrap();
+        let base = temp_dir.path().join("deep").join("er");
+        let args = |create_parents| ContestCommandArgs {
+            name: "abc302".to_string(),
+            problems: vec!["A".to_string()],
+            template: "default".to_string(),
+            path: Some(base.clone()),
+            create_parents,
+        };
+
+        // Act: Create the contest without, then with create_parents
+        let refused = ContestCommand::execute(&args(false), &Config::default());
+        let refused_leaves_nothing = !temp_dir.path().join("deep").exists();
+        let created = ContestCommand::execute(&args(true), &Config::default());
+
+        // Assert: Verify only the second attempt created the chain
+        assert!(refused.unwrap_err().to_string().contains("--create-parents"));
+        assert!(refused_leaves_nothing);
+        assert!(created.is_ok());
+        assert!(base.join("abc302/A/main.cpp").is_file());
+    }
+
+    /// Tests that a failed contest also removes the parent directories it created.
+    ///
+    /// Skipped on Windows, where the failing hook would need `cmd` syntax.
+    #[test]
+    fn test_contest_rollback_removes_created_parents() {
+        if cfg!(windows) {
+            return;
+        }
+
+        // Arrange: A template whose hook fails for problem B, and a missing base path
+        let temp_dir = TempDir::new().unwrap();
+        let templates = temp_dir.path().join("templates");
+        let template_dir = templates.join("hooked");
+        fs::create_dir_all(&template_dir).unwrap();
+        fs::write(template_dir.join("main.cpp"), "int main() {}").unwrap();
+        fs::write(template_dir.join("CMakeLists.txt"), "project({{PROJECT_NAME}})").unwrap();
+        fs::write(
+            template_dir.join("template.toml"),
+            "[hooks]\npost_create = [\"test {{PROJECT_NAME}} != B\"]\n",
+        )
+        .unwrap();
+        let mut config = Config::default();
+        config.template.path = templates;
+        let work = temp_dir.path().join("work");
+        fs::create_dir(&work).unwrap();
+        let args = ContestCommandArgs {
+            name: "abc303".to_string(),
+            problems: vec!["A".to_string(), "B".to_string()],
+            template: "hooked".to_string(),
+            path: Some(work.join("deep").join("er")),
+            create_parents: true,
+        };
+
+        // Act: Create the contest
+        let result = ContestCommand::execute(&args, &config);
+
+        // Assert: Verify problem A and every directory above it were removed again
+        assert!(result.is_err());
+        assert_eq!(fs::read_dir(&work).unwrap().count(), 0);
+    }
+}
@@ -110,16 +110,18 @@ mod error_tests {
     /// including the exact path where the template should be placed.
     #[test]
     fn test_template_not_found_with_hint_error_display() {
-        // Arrange: Create a TemplateNotFoundWithHint error with a template name
+        // Arrange: Create a TemplateNotFoundWithHint error for a configured template directory
         let template_name = "custom";
-        let error = ProconError::TemplateNotFoundWithHint(template_name.to_string());
+        let template_dir = std::path::PathBuf::from("/opt/templates").join(template_name);
+        let error = ProconError::TemplateNotFoundWithHint(template_name.to_string(), template_dir.clone());
 
         // Act: Convert the error to its string representation
         let error_message = error.to_string();
 
         // Assert: Verify the error message includes both template name and helpful hint
         assert!(error_message.contains("Template 'custom' not found"));
-        assert!(error_message.contains("~/.config/procon_rs/templates/custom"));
+        assert!(error_message.contains(&template_dir.display().to_string()));
+        assert!(error_message.contains("procon_rs template new custom"));
     }
 
     /// Tests that ProconError implements Send and Sync traits for thread safety.
@@ -132,5 +134,136 @@ mod error_tests {
         fn assert_send_sync<T: Send + Sync>() {}
         assert_send_sync::<ProconError>();
     }
-}
 
+    /// Tests that UnsafeTemplatePath error names the offending path and the security reason.
+    ///
+    /// This tells template authors exactly which entry was refused and why.
+    #[test]
+    fn test_unsafe_template_path_error_display() {
+        // Arrange: Create an UnsafeTemplatePath error for an escaping key
+        let error = ProconError::UnsafeTemplatePath("../escape".to_string());
+
+        // Act: Convert the error to its string representation
+        let error_message = error.to_string();
+
+        // Assert: Verify the message names the path and explains the refusal
+        assert_eq!(
+            error_message,
+            "Refusing to write template file '../escape': path escapes the project directory"
+        );
+    }
+
+    /// Tests that InvalidConfig error lists every problem on its own line.
+    #[test]
+    fn test_invalid_config_error_display() {
+        // Arrange: Create an InvalidConfig error with two problems
+        let error = ProconError::InvalidConfig(vec!["first".to_string(), "second".to_string()]);
+
+        // Act: Convert the error to its string representation
+        let error_message = error.to_string();
+
+        // Assert: Verify both problems are listed
+        assert_eq!(error_message, "Invalid configuration:\n  - first\n  - second");
+    }
+
+    /// Tests that TemplateFetchFailed error names the URL and the reason.
+    #[test]
+    fn test_template_fetch_failed_error_display() {
+        // Arrange: Create a TemplateFetchFailed error for an unreachable repository
+        let error = ProconError::TemplateFetchFailed(
+            "https://example.com/tpl.git".to_string(),
+            "repository not found".to_string(),
+        );
+
+        // Act: Convert the error to its string representation
+        let error_message = error.to_string();
+
+        // Assert: Verify the URL and reason are included
+        assert_eq!(
+            error_message,
+            "Failed to fetch template 'https://example.com/tpl.git': repository not found"
+        );
+    }
+
+    /// Tests that InvalidProjectName error names the rejected name and the rules.
+    #[test]
+    fn test_invalid_project_name_error_display() {
+        // Arrange: Create an InvalidProjectName error for a traversal attempt
+        let error = ProconError::InvalidProjectName("../etc".to_string());
+
+        // Act: Convert the error to its string representation
+        let error_message = error.to_string();
+
+        // Assert: Verify the name and the path separator rule are mentioned
+        assert!(error_message.starts_with("Invalid project name '../etc'"));
+        assert!(error_message.contains("path separators"));
+    }
+
+    /// Tests that TemplateNotFoundIn error lists the searched directories.
+    #[test]
+    fn test_template_not_found_in_error_display() {
+        // Arrange: Create a TemplateNotFoundIn error with two searched roots
+        let roots = vec![std::path::PathBuf::from("/team"), std::path::PathBuf::from("/home/templates")];
+        let error = ProconError::TemplateNotFoundIn("missing".to_string(), roots);
+
+        // Act: Convert the error to its string representation
+        let error_message = error.to_string();
+
+        // Assert: Verify the template and both directories are named
+        assert_eq!(
+            error_message,
+            "Template 'missing' not found (searched: /team, /home/templates)"
+        );
+    }
+
+    /// Tests that InvalidTemplate error lists every problem on its own line.
+    #[test]
+    fn test_invalid_template_error_display() {
+        // Arrange: Create an InvalidTemplate error with two problems
+        let error = ProconError::InvalidTemplate(vec!["first".to_string(), "second".to_string()]);
+
+        // Act: Convert the error to its string representation
+        let error_message = error.to_string();
+
+        // Assert: Verify both problems are listed
+        assert_eq!(error_message, "Invalid template:\n  - first\n  - second");
+    }
+
+    /// Tests that EnvironmentProblems error lists every problem on its own line.
+    #[test]
+    fn test_environment_problems_error_display() {
+        // Arrange: Create an EnvironmentProblems error with two problems
+        let error = ProconError::EnvironmentProblems(vec![
+            "cmake not found on PATH".to_string(),
+            "no C++ compiler found on PATH (tried g++, clang++)".to_string(),
+        ]);
+
+        // Act: Convert the error to its string representation
+        let error_message = error.to_string();
+
+        // Assert: Verify both problems are listed
+        assert_eq!(
+            error_message,
+            "Environment check failed:\n  - cmake not found on PATH\n  - no C++ compiler found on PATH (tried g++, clang++)"
+        );
+    }
+
+    /// Tests that ProjectModified error names the project and lists each changed file.
+    #[test]
+    fn test_project_modified_error_display() {
+        // Arrange: Create a ProjectModified error with two changed files
+        let error = ProconError::ProjectModified(
+            "/work/abc300_a".to_string(),
+            vec!["main.cpp".to_string(), "notes.txt".to_string()],
+        );
+
+        // Act: Convert the error to its string representation
+        let error_message = error.to_string();
+
+        // Assert: Verify the project and both files are listed
+        assert_eq!(
+            error_message,
+            "Refusing to undo: /work/abc300_a changed since it was created:\n  - main.cpp\n  - notes.txt"
+        );
+    }
+}
@@ -0,0 +1,68 @@
+#[cfg(all(test, unix))]
+mod exec_tests {
+    use procon_rs::exec::{capture, capture_streaming};
+    use std::process::Command;
+
+    /// Tests that capture() keeps stdout and stderr in separate buffers.
+    ///
+    /// Callers such as build and doctor report compiler errors separately from
+    /// regular output, so the streams must not be merged.
+    #[test]
+    fn test_capture_separates_streams() {
+        // Arrange: A process printing to both streams
+        let mut command = Command::new("sh");
+        command.args(["-c", "echo out; echo err >&2; exit 3"]);
+
+        // Act: Capture its output
+        let output = capture(&mut command).unwrap();
+
+        // Assert: Verify each stream and the exit status were captured
+        assert_eq!(output.stdout, "out\n");
+        assert_eq!(output.stderr, "err\n");
+        assert!(!output.success());
+        assert_eq!(output.status.code(), Some(3));
+    }
+
+    /// Tests that non-UTF-8 output is decoded lossily instead of failing.
+    ///
+    /// Compilers in some locales emit legacy encodings; the capture must survive them.
+    #[test]
+    fn test_capture_decodes_non_utf8_lossily() {
+        // Arrange: A process emitting an invalid UTF-8 byte between valid text
+        let mut command = Command::new("sh");
+        command.args(["-c", "printf 'a\\377b'"]);
+
+        // Act: Capture its output
+        let output = capture(&mut command).unwrap();
+
+        // Assert: Verify the invalid byte became a replacement character
+        assert!(output.success());
+        assert_eq!(output.stdout, "a\u{FFFD}b");
+    }
+
+    /// Tests that capture_streaming() still returns both streams after echoing them.
+    #[test]
+    fn test_capture_streaming_returns_output() {
+        // Arrange: A process printing to both streams
+        let mut command = Command::new("sh");
+        command.args(["-c", "echo streamed; echo warned >&2"]);
+
+        // Act: Run it with passthrough to the terminal
+        let output = capture_streaming(&mut command).unwrap();
+
+        // Assert: Verify the output was captured as well
+        assert!(output.success());
+        assert_eq!(output.stdout, "streamed\n");
+        assert_eq!(output.stderr, "warned\n");
+    }
+
+    /// Tests that a missing executable surfaces as an error rather than a panic.
+    #[test]
+    fn test_capture_missing_program() {
+        // Act: Run a program that doesn't exist
+        let result = capture(&mut Command::new("procon-rs-definitely-missing"));
+
+        // Assert: Verify an IO error is returned
+        assert!(result.is_err());
+    }
+}
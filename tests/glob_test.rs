@@ -0,0 +1,43 @@
+#[cfg(test)]
+mod glob_tests {
+    use procon_rs::glob::glob_match;
+
+    /// Tests that `*` and `?` stay within a single path segment.
+    #[test]
+    fn test_glob_single_segment_wildcards() {
+        // Act & Assert: Verify segment-local matching
+        assert!(glob_match("*.md", "notes.md"));
+        assert!(!glob_match("*.md", "docs/notes.md"));
+        assert!(glob_match("tests/0?.in", "tests/01.in"));
+        assert!(!glob_match("tests/0?.in", "tests/010.in"));
+        assert!(!glob_match("a?b", "a/b"));
+    }
+
+    /// Tests that `**` crosses directories, including matching zero of them.
+    #[test]
+    fn test_glob_double_star() {
+        // Act & Assert: Verify recursive matching
+        assert!(glob_match("**/*.sh", "scripts/tools/gen.sh"));
+        assert!(glob_match("**/*.sh", "gen.sh"));
+        assert!(glob_match("src/**/mod.rs", "src/mod.rs"));
+        assert!(glob_match("src/**", "src/a/b/c.cpp"));
+        assert!(!glob_match("src/**", "lib/a.cpp"));
+    }
+
+    /// Tests that a trailing slash matches everything below a directory.
+    #[test]
+    fn test_glob_directory_pattern() {
+        // Act & Assert: Verify directory shorthand
+        assert!(glob_match("build/", "build/CMakeCache.txt"));
+        assert!(glob_match("build/", "build/sub/out.o"));
+        assert!(!glob_match("build/", "rebuild/out.o"));
+    }
+
+    /// Tests that literal patterns require an exact match.
+    #[test]
+    fn test_glob_literal() {
+        // Act & Assert: Verify exact matching
+        assert!(glob_match("main.cpp", "main.cpp"));
+        assert!(!glob_match("main.cpp", "main.cpp.bak"));
+    }
+}
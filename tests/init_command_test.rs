@@ -0,0 +1,45 @@
+#[cfg(test)]
+mod init_command_tests {
+    use procon_rs::commands::init::InitCommand;
+    use procon_rs::config::Config;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// Tests that an existing CMakeLists supplies the init defaults.
+    ///
+    /// Initializing must harmonize with the user's established settings rather
+    /// than replace them with the configured ones.
+    #[test]
+    fn test_init_defaults_from_existing_cmake_lists() {
+        // Arrange: A directory whose CMakeLists names the project and standard
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("CMakeLists.txt"),
+            "project(my_solver)\n# set(CMAKE_CXX_STANDARD 11)\nset(CMAKE_CXX_STANDARD 20)\n",
+        )
+        .unwrap();
+
+        // Act: Derive the defaults
+        let defaults = InitCommand::defaults(temp_dir.path(), &Config::default()).unwrap();
+
+        // Assert: Verify the CMakeLists values win over the configuration
+        assert_eq!(defaults.project_name, "my_solver");
+        assert_eq!(defaults.cpp_standard, "20");
+    }
+
+    /// Tests that the directory name and configured standard are used without a CMakeLists.
+    #[test]
+    fn test_init_defaults_without_cmake_lists() {
+        // Arrange: An empty directory with a known name
+        let temp_dir = TempDir::new().unwrap();
+        let project_dir = temp_dir.path().join("abc300_a");
+        fs::create_dir(&project_dir).unwrap();
+
+        // Act: Derive the defaults
+        let defaults = InitCommand::defaults(&project_dir, &Config::default()).unwrap();
+
+        // Assert: Verify the fallbacks
+        assert_eq!(defaults.project_name, "abc300_a");
+        assert_eq!(defaults.cpp_standard, Config::default().project.cpp_standard);
+    }
+}
@@ -1,7 +1,13 @@
 #[cfg(test)]
 mod new_command_tests {
-    use procon_rs::commands::new::{NewCommand, NewCommandArgs};
+    use procon_rs::commands::new::{
+        camel_case_name, create_project, snake_case_name, upper_case_name, validate_project_name, NewCommand,
+        NewCommandArgs,
+    };
+    use procon_rs::config::Config;
+    use procon_rs::prompt::Prompter;
     use std::fs;
+    use std::io::Cursor;
     use tempfile::TempDir;
 
     /// Tests that NewCommand creates a project with all required files using the default template.
@@ -20,10 +26,11 @@ mod new_command_tests {
             name: project_name.to_string(),
             template: "default".to_string(),
             path: Some(temp_dir.path().to_path_buf()),
+            ..Default::default()
         };
 
         // Act: Execute the new command to create the project
-        let result = NewCommand::execute(args);
+        let result = NewCommand::execute_with_config(&args, &Config::default());
 
         // Assert: Verify the project was created successfully
         assert!(result.is_ok());
@@ -51,18 +58,22 @@ mod new_command_tests {
             name: project_name.to_string(),
             template: "default".to_string(),
             path: Some(temp_dir.path().to_path_buf()),
+            ..Default::default()
         };
 
         // Act: Create the project with variable substitution
-        let result = NewCommand::execute(args);
+        let result = NewCommand::execute_with_config(&args, &Config::default());
         assert!(result.is_ok());
 
         // Act: Read the generated file contents
         let main_content = fs::read_to_string(project_path.join("main.cpp")).unwrap();
         let cmake_content = fs::read_to_string(project_path.join("CMakeLists.txt")).unwrap();
 
-        // Assert: Verify project name was substituted in main.cpp
+        // Assert: Verify project name was substituted in main.cpp and the disabled
+        // pragma marker vanished without leaving a blank line
         assert!(main_content.contains("my_awesome_project"));
+        assert!(!main_content.contains("{{PRAGMAS}}"));
+        assert!(main_content.starts_with("#include"));
 
         // Assert: Verify project name was substituted in CMakeLists.txt
         assert!(cmake_content.contains("project(my_awesome_project)"));
@@ -86,10 +97,11 @@ mod new_command_tests {
             name: project_name.to_string(),
             template: "default".to_string(),
             path: Some(temp_dir.path().to_path_buf()),
+            ..Default::default()
         };
 
         // Act: Attempt to create a project with an existing name
-        let result = NewCommand::execute(args);
+        let result = NewCommand::execute_with_config(&args, &Config::default());
 
         // Assert: Verify the operation fails with an appropriate error
         assert!(result.is_err());
@@ -115,10 +127,11 @@ mod new_command_tests {
             name: project_name.to_string(),
             template: "nonexistent".to_string(),
             path: Some(temp_dir.path().to_path_buf()),
+            ..Default::default()
         };
 
         // Act: Attempt to create a project with an invalid template
-        let result = NewCommand::execute(args);
+        let result = NewCommand::execute_with_config(&args, &Config::default());
 
         // Assert: Verify the operation fails with template-specific error
         assert!(result.is_err());
@@ -148,10 +161,11 @@ mod new_command_tests {
             name: project_name.to_string(),
             template: "default".to_string(),
             path: None, // Should use current directory
+            ..Default::default()
         };
 
         // Act: Create the project in the current directory
-        let result = NewCommand::execute(args);
+        let result = NewCommand::execute_with_config(&args, &Config::default());
 
         // Cleanup: Restore original working directory
         std::env::set_current_dir(original_dir).unwrap();
@@ -181,10 +195,11 @@ mod new_command_tests {
             name: project_name.to_string(),
             template: "default".to_string(),
             path: Some(temp_dir.path().to_path_buf()),
+            ..Default::default()
         };
 
         // Act: Create the project with CMake variable substitution
-        let result = NewCommand::execute(args);
+        let result = NewCommand::execute_with_config(&args, &Config::default());
         assert!(result.is_ok());
 
         // Act: Read the generated CMakeLists.txt content
@@ -198,4 +213,271 @@ mod new_command_tests {
         assert!(cmake_content.contains("VERSION"));
         assert!(cmake_content.contains("17")); // Default C++ standard from config
     }
-}
\ No newline at end of file
+
+    /// Tests that a path traversal attempt is rejected before anything is created.
+    #[test]
+    fn test_new_command_rejects_traversal_name() {
+        // Arrange: A name that would escape the base directory
+        let temp_dir = TempDir::new().unwrap();
+        let base = temp_dir.path().join("base");
+        fs::create_dir(&base).unwrap();
+
+        let args = NewCommandArgs {
+            name: "../escaped".to_string(),
+            template: "default".to_string(),
+            path: Some(base.clone()),
+            ..Default::default()
+        };
+
+        // Act: Attempt to create the project
+        let result = NewCommand::execute_with_config(&args, &Config::default());
+
+        // Assert: Verify the name was rejected and nothing was written outside the base
+        assert!(result.unwrap_err().to_string().contains("Invalid project name '../escaped'"));
+        assert!(!temp_dir.path().join("escaped").exists());
+    }
+
+    /// Tests that empty, hidden, separator-containing and non-portable names are rejected.
+    #[test]
+    fn test_validate_project_name_rejects_invalid_names() {
+        // Arrange: Names breaking each rule
+        let invalid = ["", ".hidden", "..", "a/b", "a\\b", "../etc", "what?", "a:b", "nul\0"];
+
+        // Act & Assert: Verify every name is rejected
+        for name in invalid {
+            assert!(validate_project_name(name).is_err(), "accepted {:?}", name);
+        }
+    }
+
+    /// Tests that typical contest problem names are accepted.
+    #[test]
+    fn test_validate_project_name_accepts_valid_names() {
+        // Act & Assert: Verify common names pass
+        for name in ["abc300_a", "cf-1234-B", "my.solution", "a..b", "問題A"] {
+            assert!(validate_project_name(name).is_ok(), "rejected {:?}", name);
+        }
+    }
+
+    /// Tests that `force` recreates an existing generated project from scratch.
+    #[test]
+    fn test_new_command_force_recreates_project() {
+        // Arrange: A previously generated project with a stray file
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path().join("again");
+        let args = || NewCommandArgs {
+            name: "again".to_string(),
+            path: Some(temp_dir.path().to_path_buf()),
+            force: true,
+            ..Default::default()
+        };
+        NewCommand::execute_with_config(&args(), &Config::default()).unwrap();
+        fs::write(project_path.join("main.cpp"), "// edited\n").unwrap();
+        fs::write(project_path.join("scratch.txt"), "old notes\n").unwrap();
+
+        // Act: Create the project again with force
+        let result = NewCommand::execute_with_config(&args(), &Config::default());
+
+        // Assert: Verify the project was regenerated without the old content
+        assert!(result.is_ok());
+        assert!(!project_path.join("scratch.txt").exists());
+        assert!(fs::read_to_string(project_path.join("main.cpp")).unwrap().contains("again"));
+    }
+
+    /// Tests that `force` refuses to delete a directory that isn't a generated project.
+    #[test]
+    fn test_new_command_force_refuses_unrelated_directory() {
+        // Arrange: An existing directory holding unrelated files
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path().join("documents");
+        fs::create_dir_all(&project_path).unwrap();
+        fs::write(project_path.join("thesis.tex"), "precious\n").unwrap();
+
+        let args = NewCommandArgs {
+            name: "documents".to_string(),
+            path: Some(temp_dir.path().to_path_buf()),
+            force: true,
+            ..Default::default()
+        };
+
+        // Act: Attempt to replace it with force
+        let result = NewCommand::execute_with_config(&args, &Config::default());
+
+        // Assert: Verify the command refused and the files are untouched
+        assert!(result.unwrap_err().to_string().contains("doesn't look like a generated project"));
+        assert_eq!(fs::read_to_string(project_path.join("thesis.tex")).unwrap(), "precious\n");
+    }
+
+    /// Tests that `create_project` returns the absolute paths of the files it wrote.
+    ///
+    /// Editor plugins call it directly with their own configuration instead of
+    /// shelling out to the CLI.
+    #[test]
+    fn test_create_project_returns_written_files() {
+        // Arrange: An explicit configuration with a non-default C++ standard
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.project.cpp_standard = "20".to_string();
+        let args = NewCommandArgs {
+            name: "library".to_string(),
+            path: Some(temp_dir.path().to_path_buf()),
+            ..Default::default()
+        };
+
+        // Act: Create the project through the library API
+        let files = create_project(&args, &config).unwrap();
+
+        // Assert: Verify every returned path exists and the config was applied
+        let project_path = temp_dir.path().join("library");
+        assert!(files.contains(&project_path.join("main.cpp")));
+        assert!(files.contains(&project_path.join("CMakeLists.txt")));
+        assert!(files.iter().all(|path| path.is_file()));
+        let cmake = fs::read_to_string(project_path.join("CMakeLists.txt")).unwrap();
+        assert!(cmake.contains("set(CMAKE_CXX_STANDARD 20)"));
+    }
+
+    /// Tests that a name differing only in case from an existing project is handled
+    /// the way the filesystem resolves it.
+    ///
+    /// Case-insensitive filesystems (macOS, Windows) report the existing entry's real
+    /// name; case-sensitive ones keep the two projects apart.
+    #[test]
+    fn test_execute_with_differently_cased_existing_project() {
+        // Arrange: An existing project named Foo
+        let temp_dir = TempDir::new().unwrap();
+        let args_for = |name: &str| NewCommandArgs {
+            name: name.to_string(),
+            path: Some(temp_dir.path().to_path_buf()),
+            ..Default::default()
+        };
+        create_project(&args_for("Foo"), &Config::default()).unwrap();
+        let case_insensitive = temp_dir.path().join("foo").exists();
+
+        // Act: Create a project named foo
+        let result = create_project(&args_for("foo"), &Config::default());
+
+        // Assert: Verify the conflict names Foo, or that foo is a separate project
+        if case_insensitive {
+            assert_eq!(result.unwrap_err().to_string(), "Project 'Foo' already exists");
+        } else {
+            assert!(result.is_ok());
+            assert!(temp_dir.path().join("Foo/main.cpp").exists());
+            assert!(temp_dir.path().join("foo/main.cpp").exists());
+        }
+    }
+
+    /// Tests the case transforms behind the `{{PROJECT_NAME_*}}` variables.
+    #[test]
+    fn test_project_name_case_transforms() {
+        // Arrange: Names with separators, spaces and camel case
+        let spaced = "my-cool project";
+        let camel = "myCoolProject";
+
+        // Act: Transform them
+        let upper = upper_case_name(spaced);
+        let snake = (snake_case_name(spaced), snake_case_name(camel));
+        let camelized = (camel_case_name(spaced), camel_case_name("abc300_a"));
+
+        // Assert: Verify each variant
+        assert_eq!(upper, "MY_COOL_PROJECT");
+        assert_eq!(upper_case_name("abc300_a"), "ABC300_A");
+        assert_eq!(snake, ("my_cool_project".to_string(), "my_cool_project".to_string()));
+        assert_eq!(camelized, ("myCoolProject".to_string(), "abc300A".to_string()));
+    }
+
+    /// Tests that a missing `path` directory is an error unless `create_parents` is set.
+    ///
+    /// A typo in `--path` must not silently create a chain of directories.
+    #[test]
+    fn test_execute_missing_path_requires_create_parents() {
+        // Arrange: A destination two levels below an existing directory
+        let temp_dir = TempDir::new().unwrap();
+        let base_path = temp_dir.path().join("contests").join("abc300");
+        let mut args = NewCommandArgs {
+            name: "a".to_string(),
+            path: Some(base_path.clone()),
+            ..Default::default()
+        };
+
+        // Act: Create the project without and then with create_parents
+        let refused = create_project(&args, &Config::default());
+        let refused_left_nothing = !temp_dir.path().join("contests").exists();
+        args.create_parents = true;
+        let created = create_project(&args, &Config::default());
+
+        // Assert: Verify the error names the directory and the flag, and the retry works
+        let message = refused.unwrap_err().to_string();
+        assert!(message.contains(&base_path.display().to_string()));
+        assert!(message.contains("--create-parents"));
+        assert!(refused_left_nothing);
+        assert!(created.is_ok());
+        assert!(base_path.join("a").join("main.cpp").is_file());
+    }
+
+    /// Tests that prompted answers become the project's name and C++ standard.
+    ///
+    /// `new` without a name asks for its arguments; an invalid name is asked for
+    /// again, and an empty answer keeps the default template.
+    #[test]
+    fn test_new_command_prompt_collects_args() {
+        // Arrange: Scripted answers, starting with an invalid name
+        let temp_dir = TempDir::new().unwrap();
+        let input = Cursor::new("bad/name\nabc300_a\n\n20\n");
+        let mut output = Vec::new();
+        let mut prompter = Prompter::new(input, &mut output);
+        let args = NewCommandArgs {
+            path: Some(temp_dir.path().to_path_buf()),
+            ..Default::default()
+        };
+
+        // Act: Prompt for the arguments and create the project
+        let args = args.prompt(&mut prompter, &Config::default()).unwrap();
+        create_project(&args, &Config::default()).unwrap();
+
+        // Assert: Verify the project uses the typed name and standard
+        assert_eq!(args.name, "abc300_a");
+        assert_eq!(args.template, "default");
+        let cmake = fs::read_to_string(temp_dir.path().join("abc300_a/CMakeLists.txt")).unwrap();
+        assert!(cmake.contains("project(abc300_a)"));
+        assert!(cmake.contains("set(CMAKE_CXX_STANDARD 20)"));
+        let transcript = String::from_utf8(output).unwrap();
+        assert_eq!(transcript.matches("Project name: ").count(), 2);
+    }
+
+    /// Tests that `project.compiler_flags` is substituted for `{{COMPILER_FLAGS}}`.
+    ///
+    /// Cleared flags must leave nothing behind rather than the raw placeholder.
+    #[test]
+    fn test_new_command_substitutes_compiler_flags() {
+        // Arrange: A template whose CMakeLists.txt uses the flags, and configs with and without flags
+        let temp_dir = TempDir::new().unwrap();
+        let template_dir = temp_dir.path().join("flags_template");
+        fs::create_dir_all(&template_dir).unwrap();
+        fs::write(template_dir.join("main.cpp"), "int main() {}\n").unwrap();
+        fs::write(
+            template_dir.join("CMakeLists.txt"),
+            "target_compile_options({{PROJECT_NAME}} PRIVATE {{COMPILER_FLAGS}})\n",
+        )
+        .unwrap();
+        let mut config = Config::default();
+        config.set("project.compiler_flags", "-O2  -Wall -DLOCAL").unwrap();
+        let mut cleared = Config::default();
+        cleared.set("project.compiler_flags", "").unwrap();
+        let args_for = |name: &str| NewCommandArgs {
+            name: name.to_string(),
+            path: Some(temp_dir.path().to_path_buf()),
+            template_dir: Some(template_dir.clone()),
+            ..Default::default()
+        };
+
+        // Act: Create one project with each config
+        create_project(&args_for("with_flags"), &config).unwrap();
+        create_project(&args_for("without_flags"), &cleared).unwrap();
+
+        // Assert: Verify the joined flags, or nothing, replaced the placeholder
+        let with_flags = fs::read_to_string(temp_dir.path().join("with_flags/CMakeLists.txt")).unwrap();
+        assert_eq!(with_flags, "target_compile_options(with_flags PRIVATE -O2 -Wall -DLOCAL)\n");
+        let without_flags =
+            fs::read_to_string(temp_dir.path().join("without_flags/CMakeLists.txt")).unwrap();
+        assert_eq!(without_flags, "target_compile_options(without_flags PRIVATE )\n");
+    }
+}
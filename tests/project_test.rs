@@ -0,0 +1,162 @@
+#[cfg(test)]
+mod project_tests {
+    use procon_rs::commands::new::{NewCommand, NewCommandArgs};
+    use procon_rs::config::Config;
+    use procon_rs::commands::remove::RemoveCommand;
+    use procon_rs::project::{nested_project_dir, run_on_create_hook, Ledger};
+    use std::fs;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    /// Tests that creating a project writes a creation record naming the template.
+    ///
+    /// The record lets later commands operate on the scaffold without the user
+    /// re-specifying the template they created it with.
+    #[test]
+    fn test_new_command_writes_created_record() {
+        // Arrange: Set up a temporary directory for the project
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path().join("recorded");
+
+        let args = NewCommandArgs {
+            name: "recorded".to_string(),
+            template: "default".to_string(),
+            path: Some(temp_dir.path().to_path_buf()),
+            ..Default::default()
+        };
+
+        // Act: Create the project
+        NewCommand::execute_with_config(&args, &Config::default()).unwrap();
+
+        // Assert: Verify the record exists and names the template
+        let record = fs::read_to_string(project_path.join(".procon/created.toml")).unwrap();
+        assert_eq!(record.trim(), "template = \"default\"");
+    }
+
+    /// Tests that a name matching the contest convention is nested by the layout.
+    ///
+    /// Power users configure `{contest}/{problem}` so `abc300_a` lands in `abc300/a`.
+    #[test]
+    fn test_nested_project_dir_matching_name() {
+        // Act: Expand contest-style names with a nested layout
+        let abc = nested_project_dir("{contest}/{problem}", "abc300_a");
+        let codeforces = nested_project_dir("{contest}/{problem}", "codeforces_1234_b");
+
+        // Assert: Verify the names were split at the last underscore and nested
+        assert_eq!(abc, Some(PathBuf::from("abc300").join("a")));
+        assert_eq!(codeforces, Some(PathBuf::from("codeforces_1234").join("b")));
+    }
+
+    /// Tests that names not following the contest convention stay flat.
+    ///
+    /// Ordinary project names must not be split apart just because they contain
+    /// an underscore.
+    #[test]
+    fn test_nested_project_dir_non_matching_name() {
+        // Act & Assert: Verify names without a contest id are not nested
+        assert_eq!(nested_project_dir("{contest}/{problem}", "test_project"), None);
+        assert_eq!(nested_project_dir("{contest}/{problem}", "scratch"), None);
+    }
+
+    /// Tests that the on_create hook runs in the project with the project path in its environment.
+    ///
+    /// Hooks drive follow-up automation, so they need to know where the project lives.
+    #[cfg(unix)]
+    #[test]
+    fn test_on_create_hook_runs_with_env() {
+        // Arrange: A project directory and a hook writing the env var to a file
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path().join("hooked");
+        fs::create_dir_all(&project_path).unwrap();
+
+        // Act: Run the hook
+        run_on_create_hook(
+            "printf '%s|%s' \"$PROCON_PROJECT_PATH\" \"${PROCON_JUDGE_URL-unset}\" > hook_ran",
+            &project_path,
+            "hooked",
+        )
+        .unwrap();
+
+        // Assert: Verify the hook ran inside the project with the expected env vars
+        let recorded = fs::read_to_string(project_path.join("hook_ran")).unwrap();
+        assert_eq!(recorded, format!("{}|", project_path.to_string_lossy()));
+    }
+
+    /// Tests that a failing on_create hook reports an error carrying the command's stderr.
+    ///
+    /// The caller downgrades this to a warning, so the message must be self-explanatory.
+    #[cfg(unix)]
+    #[test]
+    fn test_on_create_hook_failure() {
+        // Arrange: A project directory and a failing hook
+        let temp_dir = TempDir::new().unwrap();
+
+        // Act: Run the hook
+        let result = run_on_create_hook("echo boom >&2; exit 3", temp_dir.path(), "hooked");
+
+        // Assert: Verify the failure is reported with the hook's stderr
+        let message = result.unwrap_err().to_string();
+        assert!(message.starts_with("Hook failed:"));
+        assert!(message.contains("boom"));
+    }
+
+    /// Tests that the ledger lists exactly the scaffolded files.
+    #[test]
+    fn test_new_command_writes_ledger() {
+        // Arrange: Set up a temporary directory for the project
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path().join("ledgered");
+
+        let args = NewCommandArgs {
+            name: "ledgered".to_string(),
+            path: Some(temp_dir.path().to_path_buf()),
+            ..Default::default()
+        };
+
+        // Act: Create the project
+        NewCommand::execute_with_config(&args, &Config::default()).unwrap();
+
+        // Assert: Verify the ledger names the template files
+        let ledger = Ledger::read(&project_path).unwrap().unwrap();
+        assert_eq!(ledger.files, vec![".gitignore", "CMakeLists.txt", "main.cpp"]);
+    }
+
+    /// Tests that removal collapses directories emptied by it and skips unsafe entries.
+    ///
+    /// A hand-edited ledger must never make `remove` reach outside the project.
+    #[test]
+    fn test_remove_command_cleans_empty_dirs_and_ignores_escapes() {
+        // Arrange: A project with a nested scaffolded file and an escaping ledger entry
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path().join("nested");
+        fs::create_dir_all(project_path.join("lib/util")).unwrap();
+        fs::write(project_path.join("lib/util/io.hpp"), "").unwrap();
+        fs::write(temp_dir.path().join("outside.txt"), "").unwrap();
+        Ledger::new(vec!["lib/util/io.hpp".to_string(), "../outside.txt".to_string()])
+            .write(&project_path)
+            .unwrap();
+
+        // Act: Remove the project
+        let report = RemoveCommand::execute(&project_path).unwrap();
+
+        // Assert: Verify the nested dirs are gone and the outside file was left alone
+        assert_eq!(report.removed, vec!["lib/util/io.hpp"]);
+        assert_eq!(report.missing, vec!["../outside.txt"]);
+        assert!(report.removed_project_dir);
+        assert!(!project_path.exists());
+        assert!(temp_dir.path().join("outside.txt").exists());
+    }
+
+    /// Tests that removing a directory without a ledger is refused.
+    #[test]
+    fn test_remove_command_requires_ledger() {
+        // Arrange: A plain directory
+        let temp_dir = TempDir::new().unwrap();
+
+        // Act: Attempt to remove it
+        let result = RemoveCommand::execute(temp_dir.path());
+
+        // Assert: Verify the missing ledger is reported
+        assert!(result.unwrap_err().to_string().contains("No ledger found"));
+    }
+}
@@ -0,0 +1,22 @@
+#[cfg(test)]
+mod prompt_tests {
+    use procon_rs::prompt::Prompter;
+    use std::io::Cursor;
+
+    /// Tests that only an explicit yes confirms, and ended input counts as no.
+    ///
+    /// `remove` and `undo` delete files on a yes, so anything unclear must decline.
+    #[test]
+    fn test_prompter_confirm_defaults_to_no() {
+        // Arrange: Answers covering yes, an empty line, another word and ended input
+        let mut output = Vec::new();
+        let mut prompter = Prompter::new(Cursor::new("yes\n\nsure\n"), &mut output);
+
+        // Act: Ask four times
+        let answers: Vec<bool> = (0..4).map(|_| prompter.confirm("Delete it?").unwrap()).collect();
+
+        // Assert: Verify only the first answer confirmed and the question shows the default
+        assert_eq!(answers, vec![true, false, false, false]);
+        assert!(String::from_utf8(output).unwrap().starts_with("Delete it? [y/N] "));
+    }
+}
@@ -0,0 +1,59 @@
+#[cfg(test)]
+mod rename_command_tests {
+    use procon_rs::commands::rename::RenameCommand;
+    use procon_rs::error::ProconError;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// Tests that only exact matches in naming calls are replaced.
+    ///
+    /// Rewriting substrings or unrelated lines could silently break a hand-edited build.
+    #[test]
+    fn test_rename_in_cmake_replaces_exact_names_only() {
+        // Arrange: A CMakeLists mentioning the name in several ways
+        let cmake = "\
+project(foo CXX)
+add_executable(foo main.cpp foo.cpp)
+add_executable(foo_test test.cpp)
+target_compile_options(foo PRIVATE -Wall)
+set(OUTPUT_NAME foo)
+# build foo
+";
+
+        // Act: Rename foo to bar
+        let renamed = RenameCommand::rename_in_cmake(cmake, "foo", "bar");
+
+        // Assert: Verify only whole target and project names changed
+        assert_eq!(
+            renamed,
+            "\
+project(bar CXX)
+add_executable(bar main.cpp foo.cpp)
+add_executable(foo_test test.cpp)
+target_compile_options(bar PRIVATE -Wall)
+set(OUTPUT_NAME foo)
+# build foo
+"
+        );
+    }
+
+    /// Tests that renaming onto an existing directory fails and changes nothing.
+    ///
+    /// Moving a project over another one would lose work.
+    #[test]
+    fn test_rename_refuses_existing_target() {
+        // Arrange: Two sibling projects
+        let temp_dir = TempDir::new().unwrap();
+        let foo = temp_dir.path().join("foo");
+        fs::create_dir(&foo).unwrap();
+        fs::write(foo.join("CMakeLists.txt"), "project(foo)\n").unwrap();
+        fs::create_dir(temp_dir.path().join("bar")).unwrap();
+
+        // Act: Rename foo to the taken name
+        let result = RenameCommand::execute(&foo, "bar");
+
+        // Assert: Verify the error and that foo is untouched
+        assert!(matches!(result, Err(ProconError::ProjectExists(_))));
+        assert_eq!(fs::read_to_string(foo.join("CMakeLists.txt")).unwrap(), "project(foo)\n");
+    }
+}
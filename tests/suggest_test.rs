@@ -0,0 +1,40 @@
+#[cfg(test)]
+mod suggest_tests {
+    use procon_rs::suggest::{did_you_mean, levenshtein};
+
+    /// Tests that levenshtein() counts insertions, deletions, and substitutions.
+    #[test]
+    fn test_levenshtein_distances() {
+        // Act & Assert: Verify distances for common edit shapes
+        assert_eq!(levenshtein("default", "default"), 0);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("defualt", "default"), 2);
+        assert_eq!(levenshtein("advance", "advanced"), 1);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+    }
+
+    /// Tests that did_you_mean() suggests the closest template for a typo.
+    ///
+    /// This is what turns a bare "not found" error into an actionable hint.
+    #[test]
+    fn test_did_you_mean_suggests_close_match() {
+        // Arrange: The available template names
+        let templates = ["advanced", "default"];
+
+        // Act: Look up a suggestion for a transposition typo
+        let suggestion = did_you_mean("defualt", templates);
+
+        // Assert: Verify the intended template is suggested
+        assert_eq!(suggestion, Some("default"));
+    }
+
+    /// Tests that did_you_mean() stays quiet for names that are not near any candidate.
+    ///
+    /// Suggesting an unrelated template would be more confusing than no hint at all.
+    #[test]
+    fn test_did_you_mean_ignores_distant_names() {
+        // Act & Assert: Verify no suggestion for unrelated or very short names
+        assert_eq!(did_you_mean("competitive", ["advanced", "default"]), None);
+        assert_eq!(did_you_mean("x", ["ab"]), None);
+    }
+}
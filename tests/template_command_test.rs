@@ -0,0 +1,175 @@
+#[cfg(test)]
+mod template_command_tests {
+    use procon_rs::commands::template::TemplateCommand;
+    use procon_rs::template::{Template, TemplateLoader};
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    /// Writes a minimal valid template into `dir`.
+    fn write_valid_template(dir: &Path) {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(dir.join("main.cpp"), "int main() { return 0; }").unwrap();
+        fs::write(dir.join("CMakeLists.txt"), "project({{PROJECT_NAME}})").unwrap();
+    }
+
+    /// Tests that prune reports broken templates without deleting them by default.
+    ///
+    /// Reporting first lets users inspect what would go before opting into removal.
+    #[test]
+    fn test_prune_reports_invalid_template() {
+        // Arrange: One valid template and one missing CMakeLists.txt
+        let temp_dir = TempDir::new().unwrap();
+        write_valid_template(&temp_dir.path().join("good"));
+        let broken = temp_dir.path().join("broken");
+        fs::create_dir_all(&broken).unwrap();
+        fs::write(broken.join("main.cpp"), "int main() {}").unwrap();
+
+        // Act: Prune without removal
+        let report = TemplateCommand::prune(temp_dir.path(), false).unwrap();
+
+        // Assert: Verify the broken template is reported but kept on disk
+        assert_eq!(report.kept, vec!["good".to_string()]);
+        assert_eq!(report.invalid.len(), 1);
+        assert_eq!(report.invalid[0].name, "broken");
+        assert!(report.invalid[0].reason.contains("CMakeLists.txt"));
+        assert!(!report.invalid[0].removed);
+        assert!(broken.exists());
+    }
+
+    /// Tests that prune with removal deletes broken templates and leaves valid ones.
+    #[test]
+    fn test_prune_removes_invalid_template() {
+        // Arrange: One valid template and one empty template directory
+        let temp_dir = TempDir::new().unwrap();
+        write_valid_template(&temp_dir.path().join("good"));
+        let broken = temp_dir.path().join("broken");
+        fs::create_dir_all(&broken).unwrap();
+
+        // Act: Prune with removal
+        let report = TemplateCommand::prune(temp_dir.path(), true).unwrap();
+
+        // Assert: Verify only the broken template was removed
+        assert!(report.invalid[0].removed);
+        assert!(!broken.exists());
+        assert!(temp_dir.path().join("good").exists());
+    }
+
+    /// Tests that prune never removes a template named like a builtin.
+    ///
+    /// A broken override of a builtin is reported so the user can fix it by hand.
+    #[test]
+    fn test_prune_keeps_builtin_names() {
+        // Arrange: A broken user template shadowing the builtin default
+        let temp_dir = TempDir::new().unwrap();
+        let shadow = temp_dir.path().join("default");
+        fs::create_dir_all(&shadow).unwrap();
+
+        // Act: Prune with removal
+        let report = TemplateCommand::prune(temp_dir.path(), true).unwrap();
+
+        // Assert: Verify it is reported but not removed
+        assert_eq!(report.invalid[0].name, "default");
+        assert!(!report.invalid[0].removed);
+        assert!(shadow.exists());
+    }
+
+    /// Tests that a broken cached template clone is reported and removed.
+    ///
+    /// An interrupted clone must not linger in the cache forever.
+    #[test]
+    fn test_prune_cache_removes_broken_clone() {
+        // Arrange: A cache holding one valid clone and one missing its CMakeLists.txt
+        let temp_dir = TempDir::new().unwrap();
+        write_valid_template(&temp_dir.path().join("0123456789abcdef"));
+        let broken = temp_dir.path().join("fedcba9876543210");
+        fs::create_dir_all(&broken).unwrap();
+        fs::write(broken.join("main.cpp"), "int main() {}").unwrap();
+
+        // Act: Prune the cache without, then with removal
+        let reported = TemplateCommand::prune_cache(temp_dir.path(), false).unwrap();
+        let pruned = TemplateCommand::prune_cache(temp_dir.path(), true).unwrap();
+
+        // Assert: Verify the broken clone was reported first and only then removed
+        assert_eq!(reported.kept, vec!["cache/0123456789abcdef".to_string()]);
+        assert_eq!(reported.invalid[0].name, "cache/fedcba9876543210");
+        assert!(!reported.invalid[0].removed);
+        assert!(pruned.invalid[0].removed);
+        assert!(!broken.exists());
+        assert!(temp_dir.path().join("0123456789abcdef").exists());
+    }
+
+    /// Tests that `template new` creates a template that `new --template` accepts.
+    #[test]
+    fn test_create_template_is_found_and_valid() {
+        // Arrange: An empty templates directory
+        let temp_dir = TempDir::new().unwrap();
+
+        // Act: Create a template
+        let template_dir = TemplateCommand::create(temp_dir.path(), "foo", false).unwrap();
+
+        // Assert: Verify the loader finds it and it loads with its placeholders intact
+        let loader = TemplateLoader::with_dir(temp_dir.path().to_path_buf());
+        assert_eq!(loader.find_template("foo").unwrap(), template_dir);
+        let template = Template::load_from_path(&template_dir).unwrap();
+        assert!(template.files["CMakeLists.txt"].contains("{{PROJECT_NAME}}"));
+        assert!(template.manifest.unwrap().description.is_some());
+    }
+
+    /// Tests that `template new` refuses to replace an existing template unless forced.
+    #[test]
+    fn test_create_template_refuses_existing_without_force() {
+        // Arrange: An existing customized template
+        let temp_dir = TempDir::new().unwrap();
+        let template_dir = temp_dir.path().join("mine");
+        write_valid_template(&template_dir);
+        fs::write(template_dir.join("notes.md"), "keep me").unwrap();
+
+        // Act: Create it again, first without and then with force
+        let refused = TemplateCommand::create(temp_dir.path(), "mine", false);
+        let notes_after_refusal = template_dir.join("notes.md").exists();
+        let forced = TemplateCommand::create(temp_dir.path(), "mine", true);
+
+        // Assert: Verify the refusal kept the files and force replaced them
+        assert!(refused.unwrap_err().to_string().contains("already exists"));
+        assert!(notes_after_refusal);
+        assert!(forced.is_ok());
+        assert!(!template_dir.join("notes.md").exists());
+        assert!(template_dir.join("template.toml").exists());
+    }
+
+    /// Tests that a complete template whose placeholders are all known passes the check.
+    #[test]
+    fn test_check_valid_template() {
+        // Arrange: A template using a builtin and a declared variable
+        let temp_dir = TempDir::new().unwrap();
+        let template_dir = temp_dir.path().join("good");
+        write_valid_template(&template_dir);
+        fs::write(template_dir.join("AUTHORS"), "{{AUTHOR}}\n").unwrap();
+        fs::write(template_dir.join("template.toml"), "[variables]\nAUTHOR = \"me\"\n").unwrap();
+
+        // Act: Check the template
+        let result = TemplateCommand::check(&template_dir);
+
+        // Assert: Verify it passes
+        assert!(result.is_ok());
+    }
+
+    /// Tests that undeclared placeholders and missing files are all reported.
+    #[test]
+    fn test_check_reports_undeclared_variable() {
+        // Arrange: A template without CMakeLists.txt using an undeclared variable
+        let temp_dir = TempDir::new().unwrap();
+        let template_dir = temp_dir.path().join("bad");
+        fs::create_dir_all(&template_dir).unwrap();
+        fs::write(template_dir.join("main.cpp"), "// {{UNDECLARED}} {{PROJECT_NAME}}\n").unwrap();
+
+        // Act: Check the template
+        let message = TemplateCommand::check(&template_dir).unwrap_err().to_string();
+
+        // Assert: Verify both problems are listed and builtins are not
+        assert!(message.contains("{{UNDECLARED}}"));
+        assert!(message.contains("missing required file CMakeLists.txt"));
+        assert!(!message.contains("PROJECT_NAME"));
+    }
+}
@@ -1,6 +1,8 @@
 #[cfg(test)]
 mod template_tests {
-    use procon_rs::template::{Template, TemplateLoader};
+    use procon_rs::error::ProconError;
+    use procon_rs::template::{is_binary, merge_gitignore, Template, TemplateLoader};
+    use std::collections::HashMap;
     use std::fs;
     use tempfile::TempDir;
 
@@ -365,7 +367,7 @@ add_executable({{PROJECT_NAME}} main.cpp)
 "#;
 
         // Act: Create template from embedded content
-        let template = Template::from_embedded_content("default", main_cpp_content, cmake_content);
+        let template = Template::from_main_and_cmake(main_cpp_content, cmake_content);
 
         // Assert: Verify the template was created correctly
         assert!(template.files.contains_key("main.cpp"));
@@ -374,6 +376,30 @@ add_executable({{PROJECT_NAME}} main.cpp)
         assert!(template.files["CMakeLists.txt"].contains("{{PROJECT_NAME}}"));
     }
 
+    /// Tests that embedded content builds a template from any set of files.
+    ///
+    /// Builtin templates ship more than main.cpp and CMakeLists.txt, including files
+    /// in subdirectories.
+    #[test]
+    fn test_template_from_embedded_content_slice() {
+        // Arrange: Three files, one of them nested
+        let files = [
+            ("main.cpp", "int main() {}"),
+            ("CMakeLists.txt", "project({{PROJECT_NAME}})"),
+            ("lib/debug.hpp", "#pragma once"),
+        ];
+
+        // Act: Create the template from the slice
+        let template = Template::from_embedded_content(&files);
+
+        // Assert: Verify exactly those files were added with their content
+        let mut keys: Vec<&str> = template.files.keys().map(String::as_str).collect();
+        keys.sort();
+        assert_eq!(keys, vec!["CMakeLists.txt", "lib/debug.hpp", "main.cpp"]);
+        assert_eq!(template.files["lib/debug.hpp"], "#pragma once");
+        assert!(template.binary_files.is_empty());
+    }
+
     /// Tests that embedded template content is properly processed with variables.
     /// 
     /// This verifies that template variables in embedded content are correctly
@@ -385,7 +411,7 @@ add_executable({{PROJECT_NAME}} main.cpp)
         let main_cpp_content = "// Project: {{PROJECT_NAME}}\nint main() { return 0; }";
         let cmake_content = "project({{PROJECT_NAME}})";
         
-        let template = Template::from_embedded_content("test", main_cpp_content, cmake_content);
+        let template = Template::from_main_and_cmake(main_cpp_content, cmake_content);
 
         // Act: Apply variable substitution
         let processed = template.apply_variables("my_project");
@@ -414,6 +440,20 @@ add_executable({{PROJECT_NAME}} main.cpp)
         assert!(template.files["CMakeLists.txt"].contains("{{PROJECT_NAME}}"));
     }
 
+    /// Tests that the advanced built-in template ships its library and test scaffold.
+    #[test]
+    fn test_builtin_advanced_template_loading() {
+        // Act: Load the advanced built-in template
+        let template = Template::from_builtin("advanced").unwrap();
+
+        // Assert: Verify the sources, the debug header and the test folder are present
+        assert!(template.files.contains_key("main.cpp"));
+        assert!(template.files.contains_key("CMakeLists.txt"));
+        assert!(template.files.contains_key("lib/debug.hpp"));
+        assert!(template.files.keys().any(|path| path.starts_with("tests/")));
+        assert!(template.files["main.cpp"].contains("#include \"debug.hpp\""));
+    }
+
     /// Tests that built-in templates return errors for non-existent template names.
     /// 
     /// This ensures that invalid built-in template names are properly rejected
@@ -432,4 +472,800 @@ add_executable({{PROJECT_NAME}} main.cpp)
             assert!(e.to_string().contains("Template 'nonexistent' not found"));
         }
     }
-}
\ No newline at end of file
+
+    /// Tests that Template::load_from_path() reads the post_message from a template.toml manifest.
+    ///
+    /// This verifies that templates can declare next steps for the user, and that the
+    /// manifest itself describes the template rather than being copied into projects.
+    #[test]
+    fn test_template_manifest_post_message() {
+        // Arrange: Create a template declaring a post message in its manifest
+        let temp_dir = TempDir::new().unwrap();
+        let template_dir = temp_dir.path().join("with_manifest");
+        fs::create_dir_all(&template_dir).unwrap();
+        fs::write(template_dir.join("main.cpp"), "int main() { return 0; }").unwrap();
+        fs::write(template_dir.join("CMakeLists.txt"), "project({{PROJECT_NAME}})").unwrap();
+        fs::write(
+            template_dir.join("template.toml"),
+            "post_message = \"cd {{PROJECT_NAME}} && cmake --preset debug\"\n",
+        )
+        .unwrap();
+
+        // Act: Load the template and apply variables
+        let template = Template::load_from_path(&template_dir).unwrap();
+        let processed = template.apply_variables("abc300_a");
+
+        // Assert: Verify the manifest was parsed and substituted
+        let manifest = processed.manifest.unwrap();
+        assert_eq!(
+            manifest.post_message.as_deref(),
+            Some("cd abc300_a && cmake --preset debug")
+        );
+
+        // Assert: Verify the manifest is not treated as a template file
+        assert!(!processed.files.contains_key("template.toml"));
+    }
+
+    /// Tests that templates without a manifest load with no manifest attached.
+    ///
+    /// This ensures the manifest stays optional and existing templates behave as before.
+    #[test]
+    fn test_template_without_manifest() {
+        // Arrange: Create a template without template.toml
+        let temp_dir = TempDir::new().unwrap();
+        let template_dir = temp_dir.path().join("plain");
+        fs::create_dir_all(&template_dir).unwrap();
+        fs::write(template_dir.join("main.cpp"), "int main() { return 0; }").unwrap();
+        fs::write(template_dir.join("CMakeLists.txt"), "project({{PROJECT_NAME}})").unwrap();
+
+        // Act: Load the template
+        let template = Template::load_from_path(&template_dir).unwrap();
+
+        // Assert: Verify no manifest is present
+        assert!(template.manifest.is_none());
+    }
+
+    /// Tests that Template::copy_to() refuses file keys that escape the destination.
+    ///
+    /// Templates may come from untrusted sources, so a crafted key such as `../escape`
+    /// must be rejected before anything is written outside the project directory.
+    #[test]
+    fn test_template_copy_rejects_escaping_path() {
+        // Arrange: Create a template containing an escaping file key
+        let temp_dir = TempDir::new().unwrap();
+        let dest_dir = temp_dir.path().join("project");
+        let mut template = Template::from_main_and_cmake("int main() {}", "project(x)");
+        template
+            .files
+            .insert("../escape".to_string(), "evil".to_string());
+
+        // Act: Attempt to copy the template
+        let result = template.copy_to(&dest_dir);
+
+        // Assert: Verify the copy is refused and nothing escaped
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("escapes the project directory"));
+        assert!(!temp_dir.path().join("escape").exists());
+        assert!(!dest_dir.join("main.cpp").exists());
+    }
+
+    /// Tests that Template::copy_to() refuses absolute file keys.
+    ///
+    /// An absolute key would ignore the destination entirely when joined, so it is
+    /// treated the same as an escaping relative path.
+    #[test]
+    fn test_template_copy_rejects_absolute_path() {
+        // Arrange: Create a template containing an absolute file key
+        let temp_dir = TempDir::new().unwrap();
+        let absolute = temp_dir.path().join("absolute.txt");
+        let mut template = Template::from_main_and_cmake("int main() {}", "project(x)");
+        template
+            .files
+            .insert(absolute.to_string_lossy().into_owned(), "evil".to_string());
+
+        // Act: Attempt to copy the template
+        let result = template.copy_to(&temp_dir.path().join("project"));
+
+        // Assert: Verify the copy is refused
+        assert!(result.is_err());
+        assert!(!absolute.exists());
+    }
+
+    /// Tests that Template::copy_to() accepts `..` components that stay inside the destination.
+    ///
+    /// Only paths that actually leave the destination are dangerous; keys such as
+    /// `src/../notes.md` resolve within the project and are written normally.
+    #[test]
+    fn test_template_copy_allows_contained_parent_components() {
+        // Arrange: Create a template with a contained `..` component
+        let temp_dir = TempDir::new().unwrap();
+        let dest_dir = temp_dir.path().join("project");
+        let mut template = Template::from_main_and_cmake("int main() {}", "project(x)");
+        template
+            .files
+            .insert("src/../notes.md".to_string(), "notes".to_string());
+
+        // Act: Copy the template
+        template.copy_to(&dest_dir).unwrap();
+
+        // Assert: Verify the file landed at its normalized location
+        assert_eq!(fs::read_to_string(dest_dir.join("notes.md")).unwrap(), "notes");
+    }
+
+    /// Tests that merge_gitignore() appends only rules missing from an existing .gitignore.
+    ///
+    /// Users' own rules must survive initialization, and overlapping rules must not
+    /// be duplicated.
+    #[test]
+    fn test_merge_gitignore_with_overlap() {
+        // Arrange: An existing .gitignore sharing one rule with the template
+        let existing = "# mine\nbuild/\nsecrets.txt\n";
+        let template = "# Build directory\nbuild/\n\n*.o\n";
+
+        // Act: Merge the template rules
+        let merged = merge_gitignore(existing, template);
+
+        // Assert: Verify user rules are kept and only the missing rule is added
+        assert_eq!(merged, "# mine\nbuild/\nsecrets.txt\n\n# Added by procon_rs\n*.o\n");
+        assert_eq!(merged.matches("build/").count(), 1);
+    }
+
+    /// Tests that merge_gitignore() leaves the file untouched when nothing is missing.
+    #[test]
+    fn test_merge_gitignore_without_missing_rules() {
+        // Arrange: An existing .gitignore already containing every template rule
+        let existing = "build/\n*.o";
+
+        // Act: Merge the template rules
+        let merged = merge_gitignore(existing, "  build/\n*.o\n");
+
+        // Assert: Verify the content is unchanged
+        assert_eq!(merged, existing);
+    }
+
+    /// Tests that Template::copy_to() merges into an existing .gitignore instead of replacing it.
+    ///
+    /// This is the path used when scaffolding into a directory that already has
+    /// version-control rules of its own.
+    #[test]
+    fn test_template_copy_merges_existing_gitignore() {
+        // Arrange: A destination with its own .gitignore
+        let temp_dir = TempDir::new().unwrap();
+        let dest_dir = temp_dir.path().join("existing");
+        fs::create_dir_all(&dest_dir).unwrap();
+        fs::write(dest_dir.join(".gitignore"), "node_modules/\n").unwrap();
+
+        let mut template = Template::from_main_and_cmake("int main() {}", "project(x)");
+        template
+            .files
+            .insert(".gitignore".to_string(), "build/\nnode_modules/\n".to_string());
+
+        // Act: Copy the template into the existing directory
+        template.copy_to(&dest_dir).unwrap();
+
+        // Assert: Verify both the user's and the template's rules are present once
+        let gitignore = fs::read_to_string(dest_dir.join(".gitignore")).unwrap();
+        assert!(gitignore.starts_with("node_modules/\n"));
+        assert!(gitignore.contains("build/"));
+        assert_eq!(gitignore.matches("node_modules/").count(), 1);
+    }
+
+    /// Tests that identical templates produce the same content hash.
+    ///
+    /// Caches compare hashes to decide whether a template changed, so equal content
+    /// must hash equal regardless of how or in which order the files were added.
+    #[test]
+    fn test_template_content_hash_equal_for_identical_templates() {
+        // Arrange: Two templates with the same files inserted in different orders
+        let mut first = Template::from_main_and_cmake("int main() {}", "project(x)");
+        first.files.insert("lib/a.hpp".to_string(), "// a".to_string());
+        first.files.insert("lib/b.hpp".to_string(), "// b".to_string());
+
+        let mut second = Template::from_main_and_cmake("int main() {}", "project(x)");
+        second.files.insert("lib/b.hpp".to_string(), "// b".to_string());
+        second.files.insert("lib/a.hpp".to_string(), "// a".to_string());
+
+        // Act: Hash both templates
+        let first_hash = first.content_hash();
+        let second_hash = second.content_hash();
+
+        // Assert: Verify the hashes match and have a fixed-width hex form
+        assert_eq!(first_hash, second_hash);
+        assert_eq!(first_hash.len(), 16);
+    }
+
+    /// Tests that a single byte change in any file changes the content hash.
+    #[test]
+    fn test_template_content_hash_detects_single_byte_change() {
+        // Arrange: Two templates differing in one character of main.cpp
+        let original = Template::from_main_and_cmake("int main() {}", "project(x)");
+        let modified = Template::from_main_and_cmake("int main() {]", "project(x)");
+
+        // Act & Assert: Verify the hashes differ
+        assert_ne!(original.content_hash(), modified.content_hash());
+    }
+
+    /// Tests that moving content between path and file body changes the hash.
+    ///
+    /// Without length prefixes, `("ab", "c")` and `("a", "bc")` would hash identically.
+    #[test]
+    fn test_template_content_hash_separates_path_and_content() {
+        // Arrange: Two templates whose concatenated path+content is identical
+        let mut first = Template::from_main_and_cmake("", "");
+        first.files.insert("ab".to_string(), "c".to_string());
+        let mut second = Template::from_main_and_cmake("", "");
+        second.files.insert("a".to_string(), "bc".to_string());
+
+        // Act & Assert: Verify the hashes differ
+        assert_ne!(first.content_hash(), second.content_hash());
+    }
+
+    /// Tests that Template::extend_gitignore() adds extra rules next to the base entries once.
+    ///
+    /// Users configure extra rules instead of maintaining a whole custom template, so
+    /// the template's base rules must stay and overlapping rules must not repeat.
+    #[test]
+    fn test_template_extend_gitignore_without_duplicates() {
+        // Arrange: The builtin template and extras overlapping its base rules
+        let mut template = Template::from_builtin("default").unwrap();
+        let extras = vec![
+            "build/".to_string(),
+            "*.log".to_string(),
+            "*.log".to_string(),
+            "input.txt".to_string(),
+        ];
+
+        // Act: Extend the generated .gitignore
+        template.extend_gitignore(&extras);
+
+        // Assert: Verify base and extra rules each appear exactly once
+        let gitignore = &template.files[".gitignore"];
+        assert!(gitignore.contains(".vscode/"));
+        assert_eq!(gitignore.lines().filter(|line| *line == "build/").count(), 1);
+        assert_eq!(gitignore.lines().filter(|line| *line == "*.log").count(), 1);
+        assert!(gitignore.lines().any(|line| line == "input.txt"));
+    }
+
+    /// Tests that Template::extend_gitignore() creates a .gitignore when the template has none.
+    #[test]
+    fn test_template_extend_gitignore_creates_file() {
+        // Arrange: A template without a .gitignore
+        let mut template = Template::from_main_and_cmake("int main() {}", "project(x)");
+
+        // Act: Extend with one rule
+        template.extend_gitignore(&["out/".to_string()]);
+
+        // Assert: Verify the file now exists with the rule
+        assert!(template.files[".gitignore"].lines().any(|line| line == "out/"));
+    }
+
+    /// Tests that files containing NUL bytes are copied unchanged, without substitution.
+    ///
+    /// Some binary files happen to decode as UTF-8; treating them as text would let
+    /// variable substitution corrupt them.
+    #[test]
+    fn test_template_binary_file_copied_unchanged() {
+        // Arrange: A template with a NUL-containing file that is also valid UTF-8
+        let temp_dir = TempDir::new().unwrap();
+        let template_dir = temp_dir.path().join("with_binary");
+        fs::create_dir_all(template_dir.join("assets")).unwrap();
+        fs::write(template_dir.join("main.cpp"), "int main() { return 0; }").unwrap();
+        fs::write(template_dir.join("CMakeLists.txt"), "project({{PROJECT_NAME}})").unwrap();
+        let blob: Vec<u8> = b"\x00\x01font{{PROJECT_NAME}}\x00\x7f".to_vec();
+        fs::write(template_dir.join("assets/icon.bin"), &blob).unwrap();
+
+        // Act: Load, substitute, and copy the template
+        let template = Template::load_from_path(&template_dir).unwrap();
+        let processed = template.apply_variables("demo");
+        let dest_dir = temp_dir.path().join("demo");
+        processed.copy_to(&dest_dir).unwrap();
+
+        // Assert: Verify the file was classified as binary and copied byte-for-byte
+        assert!(processed.binary_files.contains_key("assets/icon.bin"));
+        assert!(!processed.files.contains_key("assets/icon.bin"));
+        assert_eq!(fs::read(dest_dir.join("assets/icon.bin")).unwrap(), blob);
+        assert_eq!(
+            fs::read_to_string(dest_dir.join("CMakeLists.txt")).unwrap(),
+            "project(demo)"
+        );
+    }
+
+    /// Tests that files which aren't valid UTF-8 are kept as binary instead of dropped.
+    ///
+    /// Assets like images need not contain a NUL byte early on, and templates used to
+    /// lose them silently.
+    #[test]
+    fn test_template_non_utf8_file_survives_load_and_copy() {
+        // Arrange: A template with a NUL-free blob that is not valid UTF-8
+        let temp_dir = TempDir::new().unwrap();
+        let template_dir = temp_dir.path().join("with_asset");
+        fs::create_dir_all(&template_dir).unwrap();
+        fs::write(template_dir.join("main.cpp"), "int main() { return 0; }").unwrap();
+        fs::write(template_dir.join("CMakeLists.txt"), "project({{PROJECT_NAME}})").unwrap();
+        let blob: Vec<u8> = b"\x89PNG\r\n\x1a\xff\xfe{{PROJECT_NAME}}\xc3".to_vec();
+        fs::write(template_dir.join("logo.png"), &blob).unwrap();
+
+        // Act: Load, substitute, and copy the template
+        let template = Template::load_from_path(&template_dir).unwrap();
+        let processed = template.apply_variables("demo");
+        let dest_dir = temp_dir.path().join("demo");
+        processed.copy_to(&dest_dir).unwrap();
+
+        // Assert: Verify the file was kept as binary and copied byte-for-byte
+        assert!(processed.binary_files.contains_key("logo.png"));
+        assert_eq!(fs::read(dest_dir.join("logo.png")).unwrap(), blob);
+    }
+
+    /// Tests the NUL-byte heuristic used to classify files as binary.
+    #[test]
+    fn test_is_binary_heuristic() {
+        // Act & Assert: Verify text is text and NULs within the first KB mean binary
+        assert!(!is_binary(b"int main() { return 0; }\n"));
+        assert!(is_binary(b"PNG\x00\x00"));
+
+        let mut late_nul = vec![b'a'; 2048];
+        late_nul[1500] = 0;
+        assert!(!is_binary(&late_nul));
+    }
+
+    /// Tests that a template built in memory can be substituted and copied out.
+    ///
+    /// The in-memory API lets substitution and copying be exercised without a
+    /// template directory on disk.
+    #[test]
+    fn test_template_from_files_round_trip() {
+        // Arrange: In-memory entries, including a manifest and a binary file
+        let template = Template::from_files([
+            ("main.cpp".to_string(), b"// {{PROJECT_NAME}}\n".to_vec()),
+            ("src/lib.hpp".to_string(), b"#pragma once\n".to_vec()),
+            ("assets/icon.bin".to_string(), b"\x00\x01".to_vec()),
+            (
+                "template.toml".to_string(),
+                b"post_message = \"Created {{PROJECT_NAME}}\"\n".to_vec(),
+            ),
+        ])
+        .unwrap();
+
+        // Act: Substitute and copy the template
+        let temp_dir = TempDir::new().unwrap();
+        let processed = template.apply_variables("demo");
+        processed.copy_to(temp_dir.path()).unwrap();
+
+        // Assert: Verify classification, substitution and the written files
+        assert_eq!(processed.file_paths(), vec!["assets/icon.bin", "main.cpp", "src/lib.hpp"]);
+        assert_eq!(
+            processed.manifest.unwrap().post_message.as_deref(),
+            Some("Created demo")
+        );
+        assert_eq!(fs::read_to_string(temp_dir.path().join("main.cpp")).unwrap(), "// demo\n");
+        assert_eq!(fs::read(temp_dir.path().join("assets/icon.bin")).unwrap(), b"\x00\x01");
+        assert!(!temp_dir.path().join("template.toml").exists());
+    }
+
+    /// Tests that in-memory entries with invalid paths are rejected.
+    #[test]
+    fn test_template_from_files_rejects_invalid_path() {
+        // Act: Build a template with an empty path segment
+        let result = Template::from_files([("src//main.cpp".to_string(), Vec::new())]);
+
+        // Assert: Verify the name is refused
+        assert!(result.is_err());
+    }
+
+    /// Tests that a manifest's required_files replaces the default list and is enforced.
+    ///
+    /// Template authors can insist on extra files such as a solver header.
+    #[test]
+    fn test_template_manifest_required_files() {
+        // Arrange: A template whose manifest requires solve.hpp, initially missing
+        let temp_dir = TempDir::new().unwrap();
+        let template_dir = temp_dir.path().join("solver");
+        fs::create_dir_all(&template_dir).unwrap();
+        fs::write(template_dir.join("main.cpp"), "#include \"solve.hpp\"\n").unwrap();
+        fs::write(template_dir.join("CMakeLists.txt"), "project({{PROJECT_NAME}})").unwrap();
+        fs::write(
+            template_dir.join("template.toml"),
+            "description = \"Solver split\"\n\
+             required_files = [\"main.cpp\", \"CMakeLists.txt\", \"solve.hpp\"]\n\
+             [variables]\nAUTHOR = \"anonymous\"\n",
+        )
+        .unwrap();
+
+        // Act: Load without and then with the required header
+        let missing = Template::load_from_path(&template_dir);
+        fs::write(template_dir.join("solve.hpp"), "#pragma once\n").unwrap();
+        let complete = Template::load_from_path(&template_dir).unwrap();
+
+        // Assert: Verify the missing header is reported and the manifest is stored
+        assert!(missing.unwrap_err().to_string().contains("solve.hpp not found in template"));
+        let manifest = complete.manifest.as_ref().unwrap();
+        assert_eq!(manifest.description.as_deref(), Some("Solver split"));
+        assert_eq!(manifest.variables["AUTHOR"], "anonymous");
+        assert!(complete.files.contains_key("solve.hpp"));
+    }
+
+    /// Tests that a manifest without required_files keeps the default requirements.
+    #[test]
+    fn test_template_manifest_without_required_files_keeps_defaults() {
+        // Arrange: A template with a manifest but no CMakeLists.txt
+        let temp_dir = TempDir::new().unwrap();
+        let template_dir = temp_dir.path().join("partial");
+        fs::create_dir_all(&template_dir).unwrap();
+        fs::write(template_dir.join("main.cpp"), "int main() {}\n").unwrap();
+        fs::write(template_dir.join("template.toml"), "description = \"Partial\"\n").unwrap();
+
+        // Act: Load the template
+        let result = Template::load_from_path(&template_dir);
+
+        // Assert: Verify the default requirement still applies
+        assert!(result.unwrap_err().to_string().contains("CMakeLists.txt not found in template"));
+    }
+
+    /// Tests that variables are collected from contents and paths, deduplicated and sorted.
+    ///
+    /// Braces that don't enclose an identifier, like `{{ }}` or `{{1}}`, are not variables.
+    #[test]
+    fn test_template_variables() {
+        // Arrange: Two files sharing a variable, with malformed placeholders mixed in
+        let template = Template::from_embedded_content(&[
+            ("main.cpp", "// {{PROJECT_NAME}} by {{AUTHOR}} {{ }} {{1}}"),
+            ("notes/{{TASK}}.md", "{{AUTHOR}} {{PROJECT_NAME"),
+        ]);
+
+        // Act: Collect the variables
+        let variables: Vec<String> = template.variables().into_iter().collect();
+
+        // Assert: Verify each name appears once, in order
+        assert_eq!(variables, ["AUTHOR", "PROJECT_NAME", "TASK"]);
+    }
+
+    /// Tests that a template extending the builtin default overrides and adds files.
+    ///
+    /// Authors tweak one file without copying the rest of the base template.
+    #[test]
+    fn test_template_extends_builtin() {
+        // Arrange: A child template with a new main.cpp and notes.md
+        let temp_dir = TempDir::new().unwrap();
+        let child_dir = temp_dir.path().join("child");
+        fs::create_dir_all(&child_dir).unwrap();
+        fs::write(child_dir.join("template.toml"), "extends = \"default\"\n").unwrap();
+        fs::write(child_dir.join("main.cpp"), "// child\n").unwrap();
+        fs::write(child_dir.join("notes.md"), "# Notes\n").unwrap();
+
+        // Act: Load the child template
+        let template = Template::load_from_path(&child_dir).unwrap();
+
+        // Assert: Verify the override, the addition and the inherited files
+        let builtin = Template::from_builtin("default").unwrap();
+        assert_eq!(template.files["main.cpp"], "// child\n");
+        assert_eq!(template.files["notes.md"], "# Notes\n");
+        assert_eq!(template.files["CMakeLists.txt"], builtin.files["CMakeLists.txt"]);
+        assert!(template.files.contains_key(".gitignore"));
+    }
+
+    /// Tests that a template can extend a sibling user template, inheriting its variables.
+    #[test]
+    fn test_template_extends_user_template() {
+        // Arrange: A base template with a variable and a child overriding its main.cpp
+        let temp_dir = TempDir::new().unwrap();
+        let base_dir = temp_dir.path().join("base");
+        let child_dir = temp_dir.path().join("child");
+        fs::create_dir_all(&base_dir).unwrap();
+        fs::create_dir_all(&child_dir).unwrap();
+        fs::write(base_dir.join("main.cpp"), "// base\n").unwrap();
+        fs::write(base_dir.join("CMakeLists.txt"), "project(base)\n").unwrap();
+        fs::write(base_dir.join("template.toml"), "[variables]\nAUTHOR = \"base\"\n").unwrap();
+        fs::write(child_dir.join("main.cpp"), "// child\n").unwrap();
+        fs::write(child_dir.join("template.toml"), "extends = \"base\"\n").unwrap();
+
+        // Act: Load the child template
+        let template = Template::load_from_path(&child_dir).unwrap();
+
+        // Assert: Verify the child wins and the rest comes from the sibling base
+        assert_eq!(template.files["main.cpp"], "// child\n");
+        assert_eq!(template.files["CMakeLists.txt"], "project(base)\n");
+        assert_eq!(template.manifest.unwrap().variables["AUTHOR"], "base");
+    }
+
+    /// Tests that templates extending each other are reported instead of recursing forever.
+    #[test]
+    fn test_template_extends_cycle() {
+        // Arrange: Two templates extending each other
+        let temp_dir = TempDir::new().unwrap();
+        for (name, base) in [("a", "b"), ("b", "a")] {
+            let dir = temp_dir.path().join(name);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("template.toml"), format!("extends = \"{}\"\n", base)).unwrap();
+        }
+
+        // Act: Load one of them
+        let result = Template::load_from_path(&temp_dir.path().join("a"));
+
+        // Assert: Verify the cycle is named in a configuration error
+        let message = result.unwrap_err().to_string();
+        assert!(message.contains("Template inheritance cycle: a -> b -> a"));
+    }
+
+    /// Tests that the loader returns the template from the first root that has it.
+    #[test]
+    fn test_template_loader_searches_roots_in_order() {
+        // Arrange: Two roots, both with "shared" and only the second with "team"
+        let temp_dir = TempDir::new().unwrap();
+        let first = temp_dir.path().join("first");
+        let second = temp_dir.path().join("second");
+        for dir in [first.join("shared"), second.join("shared"), second.join("team")] {
+            fs::create_dir_all(dir).unwrap();
+        }
+        let loader = TemplateLoader::with_roots(vec![first.clone(), second.clone()]);
+
+        // Act: Find both templates
+        let shared = loader.find_template("shared").unwrap();
+        let team = loader.find_template("team").unwrap();
+
+        // Assert: Verify the earlier root wins and later roots are still searched
+        assert_eq!(shared, first.join("shared"));
+        assert_eq!(team, second.join("team"));
+    }
+
+    /// Tests that a missing template's error lists every root that was searched.
+    #[test]
+    fn test_template_loader_not_found_lists_roots() {
+        // Arrange: Two empty roots
+        let temp_dir = TempDir::new().unwrap();
+        let first = temp_dir.path().join("first");
+        let second = temp_dir.path().join("second");
+        let loader = TemplateLoader::with_roots(vec![first.clone(), second.clone()]);
+
+        // Act: Look up a template that doesn't exist
+        let message = loader.find_template("missing").unwrap_err().to_string();
+
+        // Assert: Verify the message names the template and both roots
+        assert!(message.starts_with("Template 'missing' not found (searched: "));
+        assert!(message.contains(&first.display().to_string()));
+        assert!(message.contains(&second.display().to_string()));
+    }
+
+    /// Tests that entries matched by `.procon_rsignore` are not loaded.
+    ///
+    /// Build output and editor clutter in a template directory must not be copied
+    /// into every new project, while the required files are always kept.
+    #[test]
+    fn test_load_from_path_honors_ignore_file() {
+        // Arrange: A template with a build directory, editor clutter and an ignore file
+        let temp_dir = TempDir::new().unwrap();
+        let template_dir = temp_dir.path();
+        fs::write(template_dir.join("main.cpp"), "int main() {}").unwrap();
+        fs::write(template_dir.join("CMakeLists.txt"), "project(x)").unwrap();
+        fs::create_dir_all(template_dir.join("build/CMakeFiles")).unwrap();
+        fs::write(template_dir.join("build/a.out"), "binary").unwrap();
+        fs::write(template_dir.join("build/CMakeFiles/cache.txt"), "cache").unwrap();
+        fs::create_dir_all(template_dir.join("lib")).unwrap();
+        fs::write(template_dir.join("lib/.DS_Store"), "finder").unwrap();
+        fs::write(template_dir.join("lib/io.hpp"), "#pragma once").unwrap();
+        fs::write(
+            template_dir.join(".procon_rsignore"),
+            "# build output\nbuild/\n.DS_Store\nmain.cpp\n",
+        )
+        .unwrap();
+
+        // Act: Load the template
+        let template = Template::load_from_path(template_dir).unwrap();
+
+        // Assert: Verify ignored entries and the ignore file itself are skipped, with a
+        // warning for the required file that is kept
+        let paths = template.file_paths();
+        assert!(paths.iter().all(|path| !path.starts_with("build/")));
+        assert!(!paths.contains(&"lib/.DS_Store".to_string()));
+        assert!(!paths.contains(&".procon_rsignore".to_string()));
+        assert!(paths.contains(&"lib/io.hpp".to_string()));
+        assert!(paths.contains(&"main.cpp".to_string()));
+        assert_eq!(
+            template.warnings,
+            vec![".procon_rsignore would ignore required file main.cpp; keeping it".to_string()]
+        );
+    }
+
+    /// Tests that merging without overwrite keeps this template's conflicting files.
+    ///
+    /// Inheritance relies on this so a child template's files win over its base's.
+    #[test]
+    fn test_template_merge_keeps_existing_files() {
+        // Arrange: Two templates sharing main.cpp
+        let mut template = Template {
+            files: HashMap::from([("main.cpp".to_string(), "child".to_string())]),
+            binary_files: HashMap::new(),
+            symlinks: HashMap::new(),
+            manifest: None,
+            warnings: Vec::new(),
+        };
+        let other = Template {
+            files: HashMap::from([
+                ("main.cpp".to_string(), "base".to_string()),
+                ("README.md".to_string(), "readme".to_string()),
+            ]),
+            binary_files: HashMap::from([("main.cpp.bin".to_string(), vec![0u8])]),
+            symlinks: HashMap::new(),
+            manifest: None,
+            warnings: Vec::new(),
+        };
+
+        // Act: Merge without overwriting
+        let merged = template.merge(&other, false);
+
+        // Assert: Verify only the new files were added
+        assert_eq!(merged, 2);
+        assert_eq!(template.files["main.cpp"], "child");
+        assert_eq!(template.files["README.md"], "readme");
+        assert_eq!(template.binary_files["main.cpp.bin"], vec![0u8]);
+    }
+
+    /// Tests that merging with overwrite replaces conflicting files, even across kinds.
+    ///
+    /// A text file replacing a binary one must not leave both behind.
+    #[test]
+    fn test_template_merge_overwrites_conflicts() {
+        // Arrange: Two templates sharing main.cpp and logo.png
+        let mut template = Template {
+            files: HashMap::from([("main.cpp".to_string(), "child".to_string())]),
+            binary_files: HashMap::from([("logo.png".to_string(), vec![0u8])]),
+            symlinks: HashMap::new(),
+            manifest: None,
+            warnings: Vec::new(),
+        };
+        let other = Template {
+            files: HashMap::from([
+                ("main.cpp".to_string(), "base".to_string()),
+                ("logo.png".to_string(), "text logo".to_string()),
+            ]),
+            binary_files: HashMap::new(),
+            symlinks: HashMap::new(),
+            manifest: None,
+            warnings: Vec::new(),
+        };
+
+        // Act: Merge with overwriting
+        let merged = template.merge(&other, true);
+
+        // Assert: Verify both conflicting files were replaced
+        assert_eq!(merged, 2);
+        assert_eq!(template.files["main.cpp"], "base");
+        assert_eq!(template.files["logo.png"], "text logo");
+        assert!(template.binary_files.is_empty());
+    }
+
+    /// Tests that a manifest written only in YAML is read like template.toml.
+    ///
+    /// Its declared required files must be enforced, and the manifest itself must not
+    /// be copied into projects.
+    #[test]
+    fn test_template_yaml_manifest_required_files() {
+        // Arrange: A template whose YAML manifest requires a file it lacks
+        let temp_dir = TempDir::new().unwrap();
+        let template_dir = temp_dir.path().join("yaml_manifest");
+        fs::create_dir_all(&template_dir).unwrap();
+        fs::write(template_dir.join("main.cpp"), "int main() { return 0; }").unwrap();
+        fs::write(
+            template_dir.join("template.yaml"),
+            "post_message: Solve {{PROJECT_NAME}}\nrequired_files:\n  - main.cpp\n  - solve.hpp\n",
+        )
+        .unwrap();
+
+        // Act: Load the template before and after adding the required file
+        let missing = Template::load_from_path(&template_dir);
+        fs::write(template_dir.join("solve.hpp"), "#pragma once").unwrap();
+        let template = Template::load_from_path(&template_dir).unwrap();
+
+        // Assert: Verify the YAML manifest was enforced, parsed and left out of the files
+        let error = missing.unwrap_err().to_string();
+        assert!(error.contains("solve.hpp"), "{}", error);
+        let manifest = template.manifest.as_ref().unwrap();
+        assert_eq!(manifest.post_message.as_deref(), Some("Solve {{PROJECT_NAME}}"));
+        assert!(!template.files.contains_key("template.yaml"));
+    }
+
+    /// Tests that file_count() and total_bytes() cover text and binary files alike.
+    #[test]
+    fn test_template_file_count_and_total_bytes() {
+        // Arrange: A template with two text files and one binary file
+        let template = Template::from_files([
+            ("main.cpp".to_string(), b"int main() {}\n".to_vec()),
+            ("CMakeLists.txt".to_string(), b"project(x)\n".to_vec()),
+            ("data/blob.bin".to_string(), vec![0, 1, 2, 3]),
+        ])
+        .unwrap();
+
+        // Act: Measure the template
+        let count = template.file_count();
+        let bytes = template.total_bytes();
+
+        // Assert: Verify every file and byte was counted
+        assert_eq!(count, 3);
+        assert_eq!(bytes, 14 + 11 + 4);
+    }
+
+    /// Tests that a symlink in a template is reproduced as a symlink.
+    ///
+    /// Templates may link to a current version (`latest -> v2`); following the link
+    /// would duplicate the directory, and a link to a directory could loop forever.
+    #[test]
+    fn test_template_symlink_copied_as_symlink() {
+        if cfg!(windows) {
+            return;
+        }
+
+        // Arrange: A template with a directory and a symlink to it
+        let temp_dir = TempDir::new().unwrap();
+        let template_dir = temp_dir.path().join("with_link");
+        fs::create_dir_all(template_dir.join("v2")).unwrap();
+        fs::write(template_dir.join("main.cpp"), "int main() {}").unwrap();
+        fs::write(template_dir.join("CMakeLists.txt"), "project({{PROJECT_NAME}})").unwrap();
+        fs::write(template_dir.join("v2/solve.hpp"), "#pragma once").unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink("v2", template_dir.join("latest")).unwrap();
+
+        // Act: Load and copy the template
+        let template = Template::load_from_path(&template_dir).unwrap();
+        let dest_dir = temp_dir.path().join("project");
+        template.copy_to(&dest_dir).unwrap();
+
+        // Assert: Verify the link was recorded, not followed, and recreated as a link
+        assert_eq!(template.symlinks["latest"], std::path::PathBuf::from("v2"));
+        assert!(!template.files.contains_key("latest/solve.hpp"));
+        let link = dest_dir.join("latest");
+        assert!(fs::symlink_metadata(&link).unwrap().file_type().is_symlink());
+        assert_eq!(fs::read_link(&link).unwrap(), std::path::PathBuf::from("v2"));
+        assert_eq!(fs::read_to_string(link.join("solve.hpp")).unwrap(), "#pragma once");
+    }
+
+    /// Tests that a YAML manifest next to template.toml is ignored with a warning.
+    ///
+    /// The warning is returned rather than printed so callers can honor `--quiet`
+    /// and `--format json`.
+    #[test]
+    fn test_template_toml_manifest_shadows_yaml_with_warning() {
+        // Arrange: A template with both manifests
+        let temp_dir = TempDir::new().unwrap();
+        let template_dir = temp_dir.path().join("both_manifests");
+        fs::create_dir_all(&template_dir).unwrap();
+        fs::write(template_dir.join("main.cpp"), "int main() { return 0; }").unwrap();
+        fs::write(template_dir.join("CMakeLists.txt"), "project({{PROJECT_NAME}})").unwrap();
+        fs::write(template_dir.join("template.toml"), "post_message = \"from toml\"\n").unwrap();
+        fs::write(template_dir.join("template.yml"), "post_message: from yaml\n").unwrap();
+
+        // Act: Load the template
+        let template = Template::load_from_path(&template_dir).unwrap();
+
+        // Assert: Verify the TOML manifest won and the conflict is reported once
+        let manifest = template.manifest.as_ref().unwrap();
+        assert_eq!(manifest.post_message.as_deref(), Some("from toml"));
+        assert_eq!(template.warnings.len(), 1);
+        assert!(template.warnings[0].contains("template.yml"), "{}", template.warnings[0]);
+    }
+
+    /// Tests that symlinks pointing outside the project are refused before anything is written.
+    ///
+    /// A template from an untrusted source could otherwise plant a link to any path
+    /// on the machine, bypassing the checks on file paths.
+    #[test]
+    fn test_template_copy_rejects_escaping_symlinks() {
+        // Arrange: Templates linking to an absolute path, above the project and inside it
+        let temp_dir = TempDir::new().unwrap();
+        let with_link = |link: &str, target: &str| {
+            let mut template = Template::from_embedded_content(&[("main.cpp", "int main() {}")]);
+            template.symlinks.insert(link.to_string(), std::path::PathBuf::from(target));
+            template
+        };
+
+        // Act: Copy each template
+        let absolute = with_link("passwd", "/etc/passwd").copy_to(&temp_dir.path().join("absolute"));
+        let climbing = with_link("lib/up", "../../outside").copy_to(&temp_dir.path().join("climbing"));
+        let inside = with_link("lib/main", "../main.cpp").copy_to(&temp_dir.path().join("inside"));
+
+        // Assert: Verify only the link staying inside was accepted
+        assert!(matches!(absolute, Err(ProconError::UnsafeSymlinkTarget(_, _))));
+        assert!(matches!(climbing, Err(ProconError::UnsafeSymlinkTarget(_, _))));
+        assert!(!temp_dir.path().join("absolute").exists());
+        assert!(!temp_dir.path().join("climbing").exists());
+        assert!(inside.is_ok());
+    }
+}
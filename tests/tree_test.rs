@@ -0,0 +1,46 @@
+#[cfg(test)]
+mod tree_tests {
+    use procon_rs::tree::render_tree;
+
+    /// Tests that nested directories are grouped with correct connectors and indentation.
+    ///
+    /// Continuation bars must appear only under entries that have later siblings.
+    #[test]
+    fn test_render_tree_nested_directories() {
+        // Arrange: Paths spanning several directory levels, given out of order
+        let paths: Vec<String> = [
+            "main.cpp",
+            "lib/util/io.hpp",
+            "CMakeLists.txt",
+            "lib/graph.hpp",
+            "tests/01.in",
+        ]
+        .iter()
+        .map(|path| path.to_string())
+        .collect();
+
+        // Act: Render the tree
+        let tree = render_tree("demo", &paths);
+
+        // Assert: Verify the exact layout
+        assert_eq!(
+            tree,
+            "demo\n\
+             ├── CMakeLists.txt\n\
+             ├── lib\n\
+             │   ├── graph.hpp\n\
+             │   └── util\n\
+             │       └── io.hpp\n\
+             ├── main.cpp\n\
+             └── tests\n\
+             \u{20}   └── 01.in\n"
+        );
+    }
+
+    /// Tests that an empty template renders just the root line.
+    #[test]
+    fn test_render_tree_empty() {
+        // Act & Assert: Verify only the root is printed
+        assert_eq!(render_tree("empty", &[]), "empty\n");
+    }
+}